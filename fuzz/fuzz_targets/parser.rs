@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use writing_an_interpreter_book::{lexer::Lexer, parser::Parser};
+
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+    let mut parser = Parser::new(Lexer::new(&input));
+
+    parser.parse_program();
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use writing_an_interpreter_book::parser::Parser;
+
+// Any program that parses must survive being printed and parsed again,
+// and printing it must be idempotent
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+    let Ok(program) = Parser::parse(&input) else {
+        return;
+    };
+
+    for printed in [program.to_source(), program.to_pretty_string()] {
+        let reparsed = Parser::parse(&printed).expect("printed program doesn't parse");
+        assert_eq!(reparsed, program, "printed:\n{printed}");
+    }
+
+    let formatted = program.to_pretty_string();
+    let reformatted = Parser::parse(&formatted).unwrap().to_pretty_string();
+    assert_eq!(reformatted, formatted);
+});
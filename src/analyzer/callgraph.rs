@@ -0,0 +1,287 @@
+//! Call graph of the functions bound with `let` or `const`.
+//!
+//! Calls are attributed to the innermost bound function they're in, or to
+//! the top level of the program, and matched to the function they call
+//! with the scope analysis. Functions that are only used as values, e.g.
+//! passed to another function, are still considered reachable from where
+//! they're used.
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Write,
+};
+
+use crate::{
+    analyzer::scope,
+    ast::{
+        visit::{self, Visitor},
+        Expression, Program, Statement,
+    },
+    token::Span,
+};
+
+/// A function bound to a name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionNode {
+    pub name: String,
+    /// The span of the name where it's bound
+    pub span: Span,
+    /// Whether the function calls itself, directly or through other
+    /// functions
+    pub recursive: bool,
+    /// Whether the function can be called from the top level of the
+    /// program
+    pub reachable: bool,
+}
+
+/// A call from `caller` to `callee`, both indexes in
+/// [`CallGraph::functions`]. Calls from the top level of the program have
+/// no caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Call {
+    pub caller: Option<usize>,
+    pub callee: usize,
+}
+
+/// The functions of a program and the calls between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallGraph {
+    /// The functions, in source order
+    pub functions: Vec<FunctionNode>,
+    /// Each pair of caller and callee once, sorted by caller
+    pub calls: Vec<Call>,
+}
+
+impl CallGraph {
+    pub fn new(program: &Program) -> Self {
+        let resolution = scope::resolve(program);
+        let mut collector = Collector::default();
+        collector.visit_program(program);
+
+        // The binding and span of the literal of each function
+        let bound: Vec<(usize, Span)> = collector
+            .functions
+            .iter()
+            .filter_map(|&(name, literal)| {
+                let binding = resolution
+                    .bindings
+                    .binary_search_by_key(&name.start, |b| b.span.start)
+                    .ok()?;
+                Some((binding, literal))
+            })
+            .collect();
+
+        let mut calls = BTreeSet::new();
+        // The functions used, called or not, by each function and by the
+        // top level, in its last item
+        let mut uses = vec![HashSet::new(); bound.len() + 1];
+        for reference in resolution.references.iter() {
+            let Some(callee) = bound
+                .iter()
+                .position(|&(binding, _)| reference.binding == Some(binding))
+            else {
+                continue;
+            };
+
+            // Literals are in source order, so the last one containing the
+            // reference is the innermost
+            let caller = bound.iter().rposition(|&(_, literal)| {
+                literal.start <= reference.span.start && reference.span.end <= literal.end
+            });
+            uses[caller.unwrap_or(bound.len())].insert(callee);
+            if collector.callees.contains(&reference.span.start) {
+                calls.insert(Call { caller, callee });
+            }
+        }
+
+        let mut reachable = vec![false; bound.len()];
+        let mut pending: Vec<usize> = uses[bound.len()].iter().copied().collect();
+        while let Some(function) = pending.pop() {
+            if !reachable[function] {
+                reachable[function] = true;
+                pending.extend(uses[function].iter().copied());
+            }
+        }
+
+        let calls: Vec<Call> = calls.into_iter().collect();
+        let functions = bound
+            .iter()
+            .enumerate()
+            .map(|(i, &(binding, _))| FunctionNode {
+                name: resolution.bindings[binding].name.clone(),
+                span: resolution.bindings[binding].span,
+                recursive: calls_itself(&calls, i),
+                reachable: reachable[i],
+            })
+            .collect();
+
+        Self { functions, calls }
+    }
+
+    /// Returns the graph as a Graphviz `digraph`, with a node for the top
+    /// level of the program. Recursive functions are drawn in bold, and
+    /// unreachable ones dashed.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n  top [label=\"(top level)\", shape=box];\n");
+
+        for (i, function) in self.functions.iter().enumerate() {
+            let mut styles = Vec::new();
+            if function.recursive {
+                styles.push("bold");
+            }
+            if !function.reachable {
+                styles.push("dashed");
+            }
+
+            write!(out, "  fn{i} [label=\"{}\"", function.name).unwrap();
+            if !styles.is_empty() {
+                write!(out, ", style=\"{}\"", styles.join(",")).unwrap();
+            }
+            out.push_str("];\n");
+        }
+
+        for call in self.calls.iter() {
+            match call.caller {
+                Some(caller) => writeln!(out, "  fn{caller} -> fn{};", call.callee).unwrap(),
+                None => writeln!(out, "  top -> fn{};", call.callee).unwrap(),
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Checks if `function` can reach itself through `calls`.
+fn calls_itself(calls: &[Call], function: usize) -> bool {
+    let mut seen = HashSet::new();
+    let mut pending = vec![function];
+    while let Some(caller) = pending.pop() {
+        for call in calls.iter().filter(|call| call.caller == Some(caller)) {
+            if call.callee == function {
+                return true;
+            }
+            if seen.insert(call.callee) {
+                pending.push(call.callee);
+            }
+        }
+    }
+    false
+}
+
+/// Gathers the functions bound to names and the names being called.
+#[derive(Default)]
+struct Collector {
+    /// The span of the name and of the literal of each function
+    functions: Vec<(Span, Span)>,
+    /// Start of the identifiers being called, e.g. `f` in `f(x)`
+    callees: HashSet<usize>,
+}
+
+impl Visitor for Collector {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Statement::Let(s) = stmt {
+            if let Expression::Function(_) = &s.value {
+                self.functions.push((s.name.token.span, s.value.span()));
+            }
+        }
+
+        visit::walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        let function = match expression {
+            Expression::Call(e) => Some(e.function.as_ref()),
+            Expression::Pipe(e) => Some(e.call().function),
+            _ => None,
+        };
+        if let Some(Expression::Ident(callee)) = function {
+            self.callees.insert(callee.token.span.start);
+        }
+
+        visit::walk_expression(self, expression);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_call_graph() {
+        let source = "let even = fn(n) { if (n == 0) { true } else { odd(n - 1) } };
+let odd = fn(n) { if (n == 0) { false } else { even(n - 1) } };
+let twice = fn(f, x) { f(f(x)) };
+let inc = fn(x) { x + 1 };
+let unused = fn() { let helper = fn() { unused() }; helper() };
+even(twice(inc, 2));";
+        let graph = CallGraph::new(&Parser::parse(source).unwrap());
+
+        let summary: Vec<(&str, bool, bool)> = graph
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.recursive, f.reachable))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("even", true, true),
+                ("odd", true, true),
+                ("twice", false, true),
+                // Only passed as a value
+                ("inc", false, true),
+                ("unused", true, false),
+                ("helper", true, false),
+            ]
+        );
+
+        let calls: Vec<(Option<usize>, usize)> = graph
+            .calls
+            .iter()
+            .map(|call| (call.caller, call.callee))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                (None, 0),
+                (None, 2),
+                (Some(0), 1),
+                (Some(1), 0),
+                (Some(4), 5),
+                (Some(5), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_call_graph_pipes() {
+        let source = "let f = fn(x) { x }; let g = fn(x) { x |> f }; 1 |> g(2);";
+        let graph = CallGraph::new(&Parser::parse(source).unwrap());
+
+        let calls: Vec<(Option<usize>, usize)> = graph
+            .calls
+            .iter()
+            .map(|call| (call.caller, call.callee))
+            .collect();
+        assert_eq!(calls, vec![(None, 1), (Some(1), 0)]);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let source = "let f = fn() { f() }; let g = fn() {}; f();";
+        let graph = CallGraph::new(&Parser::parse(source).unwrap());
+
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {
+  top [label=\"(top level)\", shape=box];
+  fn0 [label=\"f\", style=\"bold\"];
+  fn1 [label=\"g\", style=\"dashed\"];
+  top -> fn0;
+  fn0 -> fn0;
+}
+"
+        );
+    }
+}
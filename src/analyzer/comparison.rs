@@ -0,0 +1,104 @@
+//! Detection of chained comparisons such as `1 < x < 3`.
+//!
+//! Comparisons don't chain in Monkey: `1 < x < 3` parses as `(1 < x) < 3`,
+//! comparing the boolean result of `1 < x` with an integer, which fails at
+//! runtime with a type error that doesn't point at the cause.
+
+use crate::{
+    analyzer::Diagnostic,
+    ast::{
+        visit::{self, Visitor},
+        Expression, Program,
+    },
+    token::TokenType,
+};
+
+pub fn check(program: &Program) -> Vec<Diagnostic> {
+    let mut checker = ComparisonChecker::default();
+    checker.visit_program(program);
+    checker.diagnostics
+}
+
+/// Returns the operator of `expression` if it's a comparison, whose result
+/// is a boolean.
+fn comparison(expression: &Expression) -> Option<&str> {
+    match expression {
+        Expression::Infix(e)
+            if matches!(
+                e.token.token_type,
+                TokenType::LessThan
+                    | TokenType::GreaterThan
+                    | TokenType::Equal
+                    | TokenType::NotEqual
+            ) =>
+        {
+            Some(&e.operator)
+        }
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct ComparisonChecker {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for ComparisonChecker {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Infix(e) = expression {
+            let orders = matches!(
+                e.token.token_type,
+                TokenType::LessThan | TokenType::GreaterThan
+            );
+            // Booleans can be compared for equality, but not ordered
+            if let (true, Some(left)) = (orders, comparison(&e.left)) {
+                let message = format!(
+                    "comparisons don't chain: the left side of this `{}` is the boolean \
+                     result of `{left}`",
+                    e.operator
+                );
+                self.diagnostics
+                    .push(Diagnostic::warning("A0005", message, expression.span()));
+            }
+        }
+
+        visit::walk_expression(self, expression);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{analyzer::analyze, parser::Parser};
+
+    fn warnings(source: &str) -> Vec<(String, &str)> {
+        let program = Parser::parse(source).unwrap();
+        analyze(&program)
+            .into_iter()
+            .map(|d| (d.message, &source[d.span.start..d.span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_chained_comparisons() {
+        assert_eq!(
+            warnings("let ok = 1 < x < 3;"),
+            vec![(
+                "comparisons don't chain: the left side of this `<` is the boolean result of `<`"
+                    .to_string(),
+                "1 < x < 3"
+            )]
+        );
+        assert_eq!(
+            warnings("f(a > b > c)"),
+            vec![(
+                "comparisons don't chain: the left side of this `>` is the boolean result of `>`"
+                    .to_string(),
+                "a > b > c"
+            )]
+        );
+
+        // Booleans can be compared for equality
+        assert!(warnings("a < b == c < d; a == b != c; a == b > c").is_empty());
+        assert!(warnings("a < b + c; a + b > c").is_empty());
+    }
+}
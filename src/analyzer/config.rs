@@ -0,0 +1,118 @@
+use crate::analyzer::{Diagnostic, Severity, CODES};
+
+/// Adjusts the severity of analyzer diagnostics by code.
+///
+/// ```
+/// use writing_an_interpreter_book::analyzer::{analyze_with, Severity, SeverityConfig};
+/// use writing_an_interpreter_book::parser::Parser;
+///
+/// let program = Parser::parse("return 1; 2; if (false) { 3 }").unwrap();
+///
+/// let mut config = SeverityConfig::default();
+/// config.deny("A0001").unwrap();
+/// config.allow("A0002").unwrap();
+///
+/// let diagnostics = analyze_with(&program, &config);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].severity, Severity::Error);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeverityConfig {
+    /// Whether every warning is reported as an error
+    pub deny_warnings: bool,
+    denied: Vec<&'static str>,
+    allowed: Vec<&'static str>,
+}
+
+impl SeverityConfig {
+    /// Reports diagnostics with `code` as errors. `warnings` denies every
+    /// warning.
+    pub fn deny(&mut self, code: &str) -> Result<(), String> {
+        if code == "warnings" {
+            self.deny_warnings = true;
+        } else {
+            self.denied.push(lookup(code)?);
+        }
+        Ok(())
+    }
+
+    /// Silences diagnostics with `code`, even if they're denied.
+    pub fn allow(&mut self, code: &str) -> Result<(), String> {
+        self.allowed.push(lookup(code)?);
+        Ok(())
+    }
+
+    /// Drops the allowed diagnostics and promotes the denied ones.
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|d| !self.allowed.contains(&d.code))
+            .map(|mut d| {
+                if self.deny_warnings || self.denied.contains(&d.code) {
+                    d.severity = Severity::Error;
+                }
+                d
+            })
+            .collect()
+    }
+}
+
+fn lookup(code: &str) -> Result<&'static str, String> {
+    CODES
+        .iter()
+        .find(|known| **known == code)
+        .copied()
+        .ok_or_else(|| format!("unknown diagnostic code \"{code}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{analyzer::analyze_with, parser::Parser};
+
+    fn diagnostics(source: &str, config: &SeverityConfig) -> Vec<(&'static str, Severity)> {
+        let program = Parser::parse(source).unwrap();
+        analyze_with(&program, config)
+            .into_iter()
+            .map(|d| (d.code, d.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        let source = "if (true) { 1 } else { 2 }; return 3; 4";
+
+        let config = SeverityConfig::default();
+        assert_eq!(
+            diagnostics(source, &config),
+            vec![("A0002", Severity::Warning), ("A0001", Severity::Warning)]
+        );
+
+        let mut config = SeverityConfig::default();
+        config.deny("warnings").unwrap();
+        config.allow("A0002").unwrap();
+        assert_eq!(
+            diagnostics(source, &config),
+            vec![("A0001", Severity::Error)]
+        );
+
+        let mut config = SeverityConfig::default();
+        config.deny("A0002").unwrap();
+        config.allow("A0002").unwrap();
+        assert_eq!(
+            diagnostics(source, &config),
+            vec![("A0001", Severity::Warning)]
+        );
+    }
+
+    #[test]
+    fn test_unknown_codes() {
+        let mut config = SeverityConfig::default();
+        assert_eq!(
+            config.deny("A9999"),
+            Err("unknown diagnostic code \"A9999\"".to_string())
+        );
+        // Parser errors are always errors
+        assert!(config.allow("P0001").is_err());
+    }
+}
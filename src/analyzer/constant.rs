@@ -0,0 +1,96 @@
+//! Detection of names declared again after a `const`.
+//!
+//! A `let` or `const` binding a name already bound by a `const` in the
+//! same scope, as in `const x = 1; let x = 2;`, replaces the constant for
+//! the rest of the scope. Since a `let` in a block binds its name in the
+//! enclosing function, this includes the blocks of `if`s and loops.
+//! Functions, `for` loops and `catch`es have scopes of their own, so their
+//! bindings only shadow the constant.
+//!
+//! Since a constant is never meant to be rebound, these are errors rather
+//! than warnings.
+
+use crate::{
+    analyzer::{
+        scope::{self, BindingKind},
+        Diagnostic,
+    },
+    ast::Program,
+};
+
+pub fn check(program: &Program) -> Vec<Diagnostic> {
+    let resolution = scope::resolve(program);
+    let mut diagnostics = Vec::new();
+
+    for scope in resolution.scopes.iter() {
+        let mut bindings: Vec<usize> = scope.bindings.iter().map(|&(index, _)| index).collect();
+        bindings.sort();
+
+        for (i, &index) in bindings.iter().enumerate() {
+            let binding = &resolution.bindings[index];
+            let constant = bindings[..i].iter().any(|&earlier| {
+                let earlier = &resolution.bindings[earlier];
+                earlier.kind == BindingKind::Const && earlier.name == binding.name
+            });
+            if constant {
+                let message = format!(
+                    "`{}` is already declared as a constant in this scope",
+                    binding.name
+                );
+                diagnostics.push(Diagnostic::error("A0006", message, binding.span));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analyzer::{analyze, Severity},
+        parser::Parser,
+        token::Span,
+    };
+
+    fn diagnostics(source: &str) -> Vec<(&'static str, Severity, Span)> {
+        let program = Parser::parse(source).unwrap();
+        analyze(&program)
+            .into_iter()
+            .map(|d| (d.code, d.severity, d.span))
+            .collect()
+    }
+
+    #[test]
+    fn test_constant_redeclared() {
+        assert_eq!(
+            diagnostics("const x = 1; let x = 2;"),
+            vec![("A0006", Severity::Error, Span::new(17, 18))]
+        );
+        assert_eq!(
+            diagnostics("const x = 1; const x = 2;"),
+            vec![("A0006", Severity::Error, Span::new(19, 20))]
+        );
+        // A `let` in a block binds its name in the enclosing scope
+        assert_eq!(
+            diagnostics("const x = 1; if (x) { let x = 2; }"),
+            vec![("A0006", Severity::Error, Span::new(26, 27))]
+        );
+        assert_eq!(
+            diagnostics("let f = fn() { const x = 1; let x = 2; x };"),
+            vec![("A0006", Severity::Error, Span::new(32, 33))]
+        );
+        // Only the bindings after the constant are reported
+        assert_eq!(
+            diagnostics("let x = 1; const x = 2; let x = 3;"),
+            vec![("A0006", Severity::Error, Span::new(28, 29))]
+        );
+
+        // Functions, loops and catches shadow the constant in their scope
+        assert!(diagnostics("const x = 1; let f = fn(x) { let y = x; y };").is_empty());
+        assert!(diagnostics("const x = 1; let f = fn() { let x = 2; x };").is_empty());
+        assert!(diagnostics("const x = 1; for (x in [1]) { x }").is_empty());
+        assert!(diagnostics("const x = 1; try { x } catch (x) { x }").is_empty());
+        assert!(diagnostics("const x = 1; let y = x;").is_empty());
+    }
+}
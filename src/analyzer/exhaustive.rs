@@ -0,0 +1,229 @@
+//! Exhaustiveness of `switch` statements over enum variants.
+//!
+//! A switch without a `default` arm whose cases are all variants of the
+//! same enum, as in `case Color.Red:` or `case Shape.Circle(r):`, must list
+//! every variant of it. Cases binding the fields of a variant must bind
+//! all of them.
+
+use std::collections::HashMap;
+
+use crate::{
+    analyzer::Diagnostic,
+    ast::{
+        statements::SwitchStatement,
+        visit::{self, Visitor},
+        Expression, Program, Statement,
+    },
+};
+
+pub fn check(program: &Program) -> Vec<Diagnostic> {
+    let mut collector = EnumCollector::default();
+    collector.visit_program(program);
+
+    let mut checker = ExhaustivenessChecker {
+        enums: collector.enums,
+        diagnostics: Vec::new(),
+    };
+    checker.visit_program(program);
+    checker.diagnostics
+}
+
+/// Gathers the variant names of every enum declared in the program, with
+/// their number of fields, so switches can be checked against enums
+/// declared after them.
+#[derive(Default)]
+struct EnumCollector {
+    enums: HashMap<String, Vec<(String, usize)>>,
+}
+
+impl Visitor for EnumCollector {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Statement::Enum(s) = stmt {
+            let variants = s
+                .variants
+                .iter()
+                .map(|v| (v.name.value.clone(), v.fields.len()))
+                .collect();
+            self.enums.insert(s.name.value.clone(), variants);
+        }
+
+        visit::walk_statement(self, stmt);
+    }
+}
+
+struct ExhaustivenessChecker {
+    enums: HashMap<String, Vec<(String, usize)>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ExhaustivenessChecker {
+    fn check_switch(&mut self, switch: &SwitchStatement) {
+        let mut enum_name: Option<&str> = None;
+        let mut covered: Vec<&str> = Vec::new();
+
+        for value in switch.cases.iter().flat_map(|case| case.values.iter()) {
+            // Only cases like `Color.Red` or `Shape.Circle(r)` on a declared
+            // enum are checked
+            let (member, fields) = match value {
+                Expression::Member(member) => (member, None),
+                Expression::Call(call) => match call.function.as_ref() {
+                    Expression::Member(member) => (member, Some(call.arguments.len())),
+                    _ => return,
+                },
+                _ => return,
+            };
+            let Expression::Ident(object) = member.object.as_ref() else {
+                return;
+            };
+            let Some(variants) = self.enums.get(&object.value) else {
+                return;
+            };
+
+            if enum_name.is_some_and(|name| name != object.value) {
+                return;
+            }
+            enum_name = Some(&object.value);
+
+            let variant = variants
+                .iter()
+                .find(|(name, _)| *name == member.property.value);
+            let message = match (variant, fields) {
+                (None, _) => Some(format!(
+                    "enum {} has no variant {}",
+                    object.value, member.property.value
+                )),
+                (Some((name, want)), Some(got)) if got != *want => Some(format!(
+                    "variant {}.{name} has {want} {}, got {got}",
+                    object.value,
+                    if *want == 1 { "field" } else { "fields" }
+                )),
+                _ => None,
+            };
+            if let Some(message) = message {
+                self.diagnostics
+                    .push(Diagnostic::warning("A0004", message, value.span()));
+            }
+            covered.push(&member.property.value);
+        }
+
+        let Some(enum_name) = enum_name else {
+            return;
+        };
+        if switch.default.is_some() {
+            return;
+        }
+
+        let missing: Vec<&str> = self.enums[enum_name]
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|variant| !covered.contains(variant))
+            .collect();
+
+        if !missing.is_empty() {
+            let message = format!(
+                "switch over enum {enum_name} is not exhaustive, missing {}",
+                missing.join(", ")
+            );
+            self.diagnostics
+                .push(Diagnostic::warning("A0003", message, switch.span));
+        }
+    }
+}
+
+impl Visitor for ExhaustivenessChecker {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Statement::Switch(s) = stmt {
+            self.check_switch(s);
+        }
+
+        visit::walk_statement(self, stmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{analyzer::analyze, parser::Parser};
+
+    fn warnings(source: &str) -> Vec<(String, &str)> {
+        let program = Parser::parse(source).unwrap();
+        analyze(&program)
+            .into_iter()
+            .map(|d| (d.message, &source[d.span.start..d.span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_variants() {
+        let source = "enum Color { Red, Green, Blue } switch (c) { case Color.Red: 1 }";
+        assert_eq!(
+            warnings(source),
+            vec![(
+                "switch over enum Color is not exhaustive, missing Green, Blue".to_string(),
+                "switch (c) { case Color.Red: 1 }"
+            )]
+        );
+
+        let source = "switch (s) { case Shape.Circle: 1 } enum Shape { Circle(r), Rect(w, h) }";
+        assert_eq!(
+            warnings(source),
+            vec![(
+                "switch over enum Shape is not exhaustive, missing Rect".to_string(),
+                "switch (s) { case Shape.Circle: 1 }"
+            )]
+        );
+
+        // Binding the fields of a variant still names it
+        let source = "enum Shape { Circle(r), Rect(w, h) } switch (s) { case Shape.Circle(r): r }";
+        assert_eq!(
+            warnings(source),
+            vec![(
+                "switch over enum Shape is not exhaustive, missing Rect".to_string(),
+                "switch (s) { case Shape.Circle(r): r }"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_exhaustive_switches() {
+        let sources = [
+            "enum Color { Red, Green } switch (c) { case Color.Red, Color.Green: 1 }",
+            "enum Color { Red, Green } switch (c) { case Color.Red: 1 default: 2 }",
+            "enum Shape { Circle(r), Rect(w, h) } switch (s) { case Shape.Circle(r): r case Shape.Rect(w, h): w }",
+            // Not a switch over a known enum
+            "switch (c) { case Other.Red: 1 }",
+            "enum Color { Red, Green } switch (c) { case Color.Red: 1 case 2: 3 }",
+            "switch (c) { case 1: 2 }",
+        ];
+
+        for source in sources {
+            assert!(warnings(source).is_empty(), "source: {source}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_variant() {
+        let source = "enum Color { Red } switch (c) { case Color.Red, Color.Pink: 1 }";
+        assert_eq!(
+            warnings(source),
+            vec![("enum Color has no variant Pink".to_string(), "Color.Pink")]
+        );
+    }
+
+    #[test]
+    fn test_wrong_number_of_fields() {
+        let source = "enum Shape { Circle(r), Rect(w, h) } switch (s) { case Shape.Circle(r, x): r case Shape.Rect(w): w }";
+        assert_eq!(
+            warnings(source),
+            vec![
+                (
+                    "variant Shape.Circle has 1 field, got 2".to_string(),
+                    "Shape.Circle(r, x)"
+                ),
+                (
+                    "variant Shape.Rect has 2 fields, got 1".to_string(),
+                    "Shape.Rect(w)"
+                ),
+            ]
+        );
+    }
+}
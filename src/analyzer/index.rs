@@ -0,0 +1,357 @@
+//! Lookup of the bindings behind identifiers and of the scopes around
+//! offsets, for editor tooling such as go-to-definition and completion.
+
+use std::collections::HashSet;
+
+use crate::{
+    analyzer::scope::{self, Binding, Resolution, Scope, ScopeKind},
+    ast::Program,
+    token::Span,
+};
+
+/// Every binding of a program and the uses of its names, as resolved by
+/// the scope analysis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Index {
+    resolution: Resolution,
+}
+
+impl Index {
+    pub fn new(program: &Program) -> Self {
+        Self {
+            resolution: scope::resolve(program),
+        }
+    }
+
+    /// Returns the binding the identifier at `offset` in the source refers
+    /// to, or the binding itself when `offset` is in the name it binds.
+    ///
+    /// The end of a name counts as in it, so a cursor right after the name
+    /// finds it too. `None` is returned for names that are never bound and
+    /// offsets outside of identifiers.
+    pub fn definition_at(&self, offset: usize) -> Option<&Binding> {
+        let contains = |span: Span| span.start <= offset && offset <= span.end;
+
+        if let Some(reference) = self
+            .resolution
+            .references
+            .iter()
+            .find(|reference| contains(reference.span))
+        {
+            return reference
+                .binding
+                .map(|index| &self.resolution.bindings[index]);
+        }
+
+        self.resolution
+            .bindings
+            .iter()
+            .find(|binding| contains(binding.span))
+    }
+
+    /// Returns the spans of every use of `binding` in the program, in
+    /// source order, e.g. to rename it along with its name or to find out
+    /// it's never used. The program is a single file, as there are no
+    /// imports yet.
+    ///
+    /// `binding` must come from this index, such as the result of
+    /// [`Index::definition_at`]; bindings of other programs have no uses.
+    pub fn references(&self, binding: &Binding) -> Vec<Span> {
+        let bindings = &self.resolution.bindings;
+        let Ok(index) = bindings.binary_search_by_key(&binding.span.start, |b| b.span.start) else {
+            return Vec::new();
+        };
+        if bindings[index] != *binding {
+            return Vec::new();
+        }
+
+        self.resolution
+            .references
+            .iter()
+            .filter(|reference| reference.binding == Some(index))
+            .map(|reference| reference.span)
+            .collect()
+    }
+
+    /// Returns every binding of the program, in source order.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.resolution.bindings
+    }
+
+    /// Returns every scope of the program, in source order, starting with
+    /// the program itself.
+    pub fn scopes(&self) -> &[Scope] {
+        &self.resolution.scopes
+    }
+
+    /// Returns the bindings made in `scope`, in source order.
+    pub fn bindings_in(&self, scope: &Scope) -> Vec<&Binding> {
+        let mut bindings: Vec<&Binding> = scope
+            .bindings
+            .iter()
+            .map(|&(index, _)| &self.resolution.bindings[index])
+            .collect();
+        bindings.sort_by_key(|binding| binding.span.start);
+        bindings
+    }
+
+    /// Returns the scopes containing `offset`, innermost first and ending
+    /// with the program.
+    pub fn scopes_at(&self, offset: usize) -> Vec<&Scope> {
+        let scopes = &self.resolution.scopes;
+        // Scopes start after the ones containing them
+        let mut next = scopes
+            .iter()
+            .rposition(|scope| scope.span.start <= offset && offset < scope.span.end);
+
+        let mut chain = Vec::new();
+        while let Some(index) = next {
+            chain.push(&scopes[index]);
+            next = scopes[index].parent;
+        }
+        chain
+    }
+
+    /// Returns the bindings a name used at `offset` could refer to, one per
+    /// name, innermost first.
+    ///
+    /// Names bound later in the scopes of enclosing functions are visible
+    /// too, as function bodies run after them.
+    pub fn visible_at(&self, offset: usize) -> Vec<&Binding> {
+        let mut names = HashSet::new();
+        let mut visible = Vec::new();
+        // Whether `offset` is in a function nested in the current scope
+        let mut in_function = false;
+
+        for scope in self.scopes_at(offset) {
+            for &(index, visible_from) in scope.bindings.iter().rev() {
+                let binding = &self.resolution.bindings[index];
+                if (in_function || visible_from <= offset) && names.insert(&binding.name) {
+                    visible.push(binding);
+                }
+            }
+            in_function |= scope.kind == ScopeKind::Function;
+        }
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{analyzer::BindingKind, parser::Parser};
+
+    /// Returns the names of `bindings`.
+    fn names(bindings: Vec<&Binding>) -> Vec<&str> {
+        bindings
+            .iter()
+            .map(|binding| binding.name.as_str())
+            .collect()
+    }
+
+    /// Returns the offset of the `n`th occurrence (from 0) of `name` in
+    /// `source`.
+    fn offset(source: &str, name: &str, n: usize) -> usize {
+        source.match_indices(name).nth(n).unwrap().0
+    }
+
+    #[test]
+    fn test_definition_at() {
+        let source = "let x = 1;
+let add = fn(a, [b], {c}, ...rest) { a + b + c + x + y(rest) };
+let x = x + 1;
+if (x) { let inner = 2; }
+for (i in [x]) { i; }
+try { inner } catch (e) { e };
+add(i, e);";
+        let program = Parser::parse(source).unwrap();
+        let index = Index::new(&program);
+        let definition = |name: &str, n: usize| {
+            index
+                .definition_at(offset(source, name, n))
+                .map(|binding| (binding.kind, binding.span.start))
+        };
+
+        let tests = vec![
+            // The binding itself
+            ("add", 0, Some((BindingKind::Let, offset(source, "add", 0)))),
+            // Parameters, including patterns and rest parameters
+            (
+                "a +",
+                0,
+                Some((BindingKind::Parameter, offset(source, "a,", 0))),
+            ),
+            (
+                "b +",
+                0,
+                Some((BindingKind::Parameter, offset(source, "b]", 0))),
+            ),
+            ("c +", 0, Some((BindingKind::Key, offset(source, "c}", 0)))),
+            (
+                "rest) }",
+                0,
+                Some((BindingKind::Parameter, offset(source, "rest", 0))),
+            ),
+            // Function bodies see the bindings made after them
+            (
+                "x + y",
+                0,
+                Some((BindingKind::Let, offset(source, "x = x", 0))),
+            ),
+            // The value of a let is resolved before its name is bound
+            (
+                "x + 1",
+                0,
+                Some((BindingKind::Let, offset(source, "x = 1", 0))),
+            ),
+            (
+                "x])",
+                0,
+                Some((BindingKind::Let, offset(source, "x = x", 0))),
+            ),
+            (
+                "i;",
+                0,
+                Some((BindingKind::Loop, offset(source, "i in", 0))),
+            ),
+            // Only functions have their own environment
+            (
+                "inner }",
+                0,
+                Some((BindingKind::Let, offset(source, "inner =", 0))),
+            ),
+            (
+                "e }",
+                0,
+                Some((BindingKind::Catch, offset(source, "e)", 0))),
+            ),
+            // Unbound names
+            ("y", 0, None),
+            ("i,", 0, None),
+            ("e);", 0, None),
+        ];
+
+        for (name, n, expected) in tests {
+            assert_eq!(definition(name, n), expected, "name: {name}");
+        }
+
+        // The end of a name counts as in it
+        let end = offset(source, "rest) }", 0) + "rest".len();
+        assert_eq!(index.definition_at(end).unwrap().name, "rest");
+        assert_eq!(index.definition_at(offset(source, "1;", 0)), None);
+    }
+
+    #[test]
+    fn test_references() {
+        let source = "let f = fn(n) { f(n - 1) }; f(f(1)); let g = f;";
+        let program = Parser::parse(source).unwrap();
+        let index = Index::new(&program);
+
+        let f = &index.bindings()[0];
+        assert_eq!(f.name, "f");
+        let starts: Vec<usize> = index.references(f).iter().map(|span| span.start).collect();
+        assert_eq!(starts, vec![16, 28, 30, 45]);
+
+        let n = &index.bindings()[1];
+        assert_eq!(n.name, "n");
+        assert_eq!(index.references(n), vec![Span::new(18, 19)]);
+
+        let g = index.definition_at(41).unwrap();
+        assert_eq!(g.name, "g");
+        assert!(index.references(g).is_empty());
+
+        // Bindings of other programs
+        let other = Parser::parse("let n = 1; n").unwrap();
+        let other = Index::new(&other);
+        assert!(index.references(&other.bindings()[0]).is_empty());
+    }
+
+    #[test]
+    fn test_scopes_at() {
+        let source = "let a = 1;
+let f = fn(x) {
+    for (i in [x]) { i }
+    try { x } catch (e) { e }
+};";
+        let program = Parser::parse(source).unwrap();
+        let index = Index::new(&program);
+        let kinds = |offset: usize| -> Vec<ScopeKind> {
+            index
+                .scopes_at(offset)
+                .iter()
+                .map(|scope| scope.kind)
+                .collect()
+        };
+
+        assert_eq!(kinds(0), vec![ScopeKind::Program]);
+        assert_eq!(
+            kinds(offset(source, "i }", 0)),
+            vec![ScopeKind::Loop, ScopeKind::Function, ScopeKind::Program]
+        );
+        assert_eq!(
+            kinds(offset(source, "e }", 0)),
+            vec![ScopeKind::Catch, ScopeKind::Function, ScopeKind::Program]
+        );
+        assert_eq!(
+            kinds(offset(source, "x }", 0)),
+            vec![ScopeKind::Function, ScopeKind::Program]
+        );
+        assert_eq!(kinds(source.len()), vec![ScopeKind::Program]);
+
+        let scopes = index.scopes();
+        assert_eq!(scopes.len(), 4);
+        assert_eq!(scopes[2].kind, ScopeKind::Loop);
+        assert_eq!(scopes[2].parent, Some(1));
+        assert_eq!(names(index.bindings_in(&scopes[0])), vec!["a", "f"]);
+        assert_eq!(names(index.bindings_in(&scopes[1])), vec!["x"]);
+    }
+
+    #[test]
+    fn test_visible_at() {
+        let source = "let a = 1;
+let f = fn(x, a) {
+    let y = x;
+    for (i in [y]) { i }
+};
+let b = 2;";
+        let program = Parser::parse(source).unwrap();
+        let index = Index::new(&program);
+        let visible = |at: &str| -> Vec<(&str, BindingKind)> {
+            index
+                .visible_at(offset(source, at, 0))
+                .iter()
+                .map(|binding| (binding.name.as_str(), binding.kind))
+                .collect()
+        };
+
+        assert_eq!(visible("let a"), vec![]);
+        // Bindings are visible after their statement
+        assert_eq!(visible("let f"), vec![("a", BindingKind::Let)]);
+        // Function bodies see every binding of the enclosing scopes, and
+        // parameters shadow them
+        assert_eq!(
+            visible("let y"),
+            vec![
+                ("a", BindingKind::Parameter),
+                ("x", BindingKind::Parameter),
+                ("b", BindingKind::Let),
+                ("f", BindingKind::Let),
+            ]
+        );
+        assert_eq!(
+            visible("i }"),
+            vec![
+                ("i", BindingKind::Loop),
+                ("y", BindingKind::Let),
+                ("a", BindingKind::Parameter),
+                ("x", BindingKind::Parameter),
+                ("b", BindingKind::Let),
+                ("f", BindingKind::Let),
+            ]
+        );
+        assert_eq!(
+            visible("let b"),
+            vec![("f", BindingKind::Let), ("a", BindingKind::Let)]
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Size and complexity measures of a program, reported by `monkey stats`.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    ast::{
+        expressions::FunctionLiteral,
+        statements::BlockStatement,
+        visit::{self, Visitor},
+        Expression, Program, Statement,
+    },
+    token::{Span, TokenType},
+};
+
+/// The measures of a program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    /// The number of statements of each kind, e.g. `let` or `expression`,
+    /// including the ones in blocks
+    pub statements: BTreeMap<&'static str, usize>,
+    /// Every function literal, in source order
+    pub functions: Vec<FunctionMetrics>,
+    /// The largest number of blocks around a statement
+    pub max_depth: usize,
+    /// The largest number of calls and member accesses applied one after
+    /// the other, e.g. 3 for `a.b(c).d`. Pipes count as the calls they
+    /// stand for.
+    pub longest_chain: usize,
+}
+
+/// The measures of a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionMetrics {
+    /// The name the function is bound to, if any
+    pub name: Option<String>,
+    pub span: Span,
+    /// One more than the number of branches of the function: conditions,
+    /// cases, loops, catches and the `??` and `?.` operators. Branches
+    /// of the functions nested in it are left out.
+    pub complexity: usize,
+}
+
+impl Metrics {
+    pub fn new(program: &Program) -> Self {
+        let mut collector = MetricsCollector {
+            metrics: Metrics {
+                statements: BTreeMap::new(),
+                functions: Vec::new(),
+                max_depth: 0,
+                longest_chain: 0,
+            },
+            depth: 0,
+            name: None,
+        };
+        collector.visit_program(program);
+        collector.metrics
+    }
+
+    /// Returns the total number of statements.
+    pub fn statement_count(&self) -> usize {
+        self.statements.values().sum()
+    }
+}
+
+/// Returns the name of the kind of `stmt`.
+fn statement_kind(stmt: &Statement) -> &'static str {
+    match stmt {
+        Statement::Let(s) if s.is_const() => "const",
+        Statement::Let(_) => "let",
+        Statement::Return(_) => "return",
+        Statement::Throw(_) => "throw",
+        Statement::Defer(_) => "defer",
+        Statement::Switch(_) => "switch",
+        Statement::DoWhile(_) => "do-while",
+        Statement::ForIn(_) => "for",
+        Statement::Enum(_) => "enum",
+        Statement::Expression(_) => "expression",
+    }
+}
+
+/// Returns the number of calls and member accesses applied one after the
+/// other at the end of `expression`.
+fn chain_length(expression: &Expression) -> usize {
+    match expression {
+        Expression::Call(e) => 1 + chain_length(&e.function),
+        Expression::Member(e) => 1 + chain_length(&e.object),
+        Expression::Pipe(e) => 1 + chain_length(e.call().function),
+        _ => 0,
+    }
+}
+
+struct MetricsCollector {
+    metrics: Metrics,
+    /// The number of blocks around the current node
+    depth: usize,
+    /// The name of the binding whose value is being visited
+    name: Option<String>,
+}
+
+impl Visitor for MetricsCollector {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        *self
+            .metrics
+            .statements
+            .entry(statement_kind(stmt))
+            .or_default() += 1;
+        self.metrics.max_depth = self.metrics.max_depth.max(self.depth);
+
+        self.name = match stmt {
+            Statement::Let(s) => Some(s.name.value.clone()),
+            _ => None,
+        };
+        visit::walk_statement(self, stmt);
+    }
+
+    fn visit_block(&mut self, block: &BlockStatement) {
+        self.depth += 1;
+        visit::walk_block(self, block);
+        self.depth -= 1;
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        // Only the value of a `let` itself is bound to its name
+        let name = self.name.take();
+
+        if let Expression::Function(function) = expression {
+            self.metrics.functions.push(FunctionMetrics {
+                name,
+                span: expression.span(),
+                complexity: complexity(function),
+            });
+        }
+        self.metrics.longest_chain = self.metrics.longest_chain.max(chain_length(expression));
+
+        visit::walk_expression(self, expression);
+    }
+}
+
+/// Returns the cyclomatic complexity of `function`, see
+/// [`FunctionMetrics::complexity`].
+fn complexity(function: &FunctionLiteral) -> usize {
+    let mut counter = BranchCounter { branches: 0 };
+    counter.visit_block(&function.body);
+    counter.branches + 1
+}
+
+struct BranchCounter {
+    branches: usize,
+}
+
+impl Visitor for BranchCounter {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Switch(s) => self.branches += s.cases.len(),
+            Statement::DoWhile(_) | Statement::ForIn(_) => self.branches += 1,
+            _ => {}
+        }
+        visit::walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            // Nested functions are measured on their own
+            Expression::Function(_) => return,
+            Expression::If(_) | Expression::Try(_) => self.branches += 1,
+            Expression::Infix(e) if e.token.token_type == TokenType::Coalesce => self.branches += 1,
+            Expression::Member(e) if e.is_optional() => self.branches += 1,
+            _ => {}
+        }
+        visit::walk_expression(self, expression);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_metrics() {
+        let source = "const limit = 10;
+let classify = fn(n) {
+    if (n > limit) {
+        return 0;
+    }
+    switch (n) {
+        case 1: return a?.b ?? c;
+        case 2: for (x in xs) { if (x) { return x; } }
+    }
+    let inner = fn() { if (n) { 1 } else { 2 } };
+    return inner;
+};
+[1, 2] |> map(fn(x) { x }) |> list.sum().items.first();";
+        let metrics = Metrics::new(&Parser::parse(source).unwrap());
+
+        assert_eq!(
+            metrics.statements,
+            BTreeMap::from([
+                ("const", 1),
+                ("expression", 7),
+                ("for", 1),
+                ("let", 2),
+                ("return", 4),
+                ("switch", 1),
+            ])
+        );
+        assert_eq!(metrics.statement_count(), 16);
+        // The `if` in the `for` in the case of the function
+        assert_eq!(metrics.max_depth, 4);
+        // `.sum`, `()`, `.items`, `.first` and `()`
+        assert_eq!(metrics.longest_chain, 5);
+
+        let functions: Vec<(Option<&str>, usize)> = metrics
+            .functions
+            .iter()
+            .map(|f| (f.name.as_deref(), f.complexity))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![
+                // if, 2 cases, for, if, ?. and ??
+                (Some("classify"), 8),
+                (Some("inner"), 2),
+                (None, 1),
+            ]
+        );
+    }
+}
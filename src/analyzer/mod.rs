@@ -0,0 +1,212 @@
+//! Semantic analysis of parsed programs.
+//!
+//! The analyzer never rejects a program, it only reports [`Diagnostic`]s
+//! about code that is valid but likely to be a mistake. Its scope analysis
+//! also backs the [`Index`] used by editor tooling.
+
+mod callgraph;
+mod comparison;
+mod config;
+mod constant;
+mod exhaustive;
+mod index;
+mod metrics;
+pub(crate) mod scope;
+mod unreachable;
+
+pub use callgraph::{Call, CallGraph, FunctionNode};
+pub use config::SeverityConfig;
+pub use index::Index;
+pub use metrics::{FunctionMetrics, Metrics};
+pub use scope::{Binding, BindingKind, Scope, ScopeKind};
+
+use crate::{
+    ast::{printers::json::write_string, Program},
+    parser::ParserError,
+    token::Span,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A problem found in the source, along with the span it applies to.
+///
+/// Like [`ParserError`]s, each kind of diagnostic has a stable code:
+///
+/// | Code  | Diagnostic                                        | Severity |
+/// |-------|---------------------------------------------------|----------|
+/// | A0001 | code after a `return` or `throw`                  | warning  |
+/// | A0002 | branch of an `if` with a literal condition        | warning  |
+/// | A0003 | switch missing some variants of an enum           | warning  |
+/// | A0004 | case with an unknown variant or wrong field count | warning  |
+/// | A0005 | comparison of the result of another comparison    | warning  |
+/// | A0006 | name declared again after a `const` in its scope  | error    |
+///
+/// The severity of each code can be changed with a [`SeverityConfig`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    pub fn warning(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Self {
+            code,
+            severity: Severity::Warning,
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn error(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Self {
+            code,
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Serializes the diagnostic as a single line JSON object, locating it
+    /// in `source`, the contents of `file`.
+    pub fn to_json(&self, file: &str, source: &str) -> String {
+        let (line, column) = self.span.location(source);
+
+        let mut out = String::from("{\"file\": ");
+        write_string(&mut out, file);
+        out.push_str(&format!(
+            ", \"code\": \"{}\", \"severity\": \"{}\", \"message\": ",
+            self.code,
+            self.severity.name()
+        ));
+        write_string(&mut out, &self.message);
+        out.push_str(&format!(
+            ", \"span\": {{\"start\": {}, \"end\": {}}}, \"line\": {line}, \"column\": {column}}}",
+            self.span.start, self.span.end
+        ));
+        out
+    }
+
+    /// Renders the diagnostic for a terminal: its code and message,
+    /// followed by the line of `source` it starts at, with `^` markers
+    /// under the span. Spans covering several lines are marked up to the
+    /// end of the first one.
+    pub fn render_snippet(&self, source: &str) -> String {
+        let (line, column) = self.span.location(source);
+        let text = source.lines().nth(line - 1).unwrap_or("");
+
+        let line_start = source
+            .get(..self.span.start)
+            .map_or(0, |before| before.rfind('\n').map_or(0, |i| i + 1));
+        let line_end = line_start + text.len();
+        let marked = source
+            .get(self.span.start..self.span.end.min(line_end))
+            .unwrap_or("");
+        let width = marked.chars().count().max(1);
+
+        let gutter = " ".repeat(line.to_string().len());
+        format!(
+            "{}[{}]: {}\n{gutter} |\n{line} | {text}\n{gutter} | {}{}\n",
+            self.severity.name(),
+            self.code,
+            self.message,
+            " ".repeat(column - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl From<&ParserError> for Diagnostic {
+    fn from(error: &ParserError) -> Self {
+        Self {
+            code: error.code,
+            severity: Severity::Error,
+            message: error.message.clone(),
+            span: error.span,
+        }
+    }
+}
+
+/// The codes of every diagnostic the analyzer can report.
+pub const CODES: [&str; 6] = ["A0001", "A0002", "A0003", "A0004", "A0005", "A0006"];
+
+/// Runs every analysis pass over `program`, returning the diagnostics in
+/// source order.
+pub fn analyze(program: &Program) -> Vec<Diagnostic> {
+    analyze_with(program, &SeverityConfig::default())
+}
+
+/// Like [`analyze`], with the severities adjusted by `config`.
+pub fn analyze_with(program: &Program, config: &SeverityConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = unreachable::check(program);
+    diagnostics.extend(exhaustive::check(program));
+    diagnostics.extend(comparison::check(program));
+    diagnostics.extend(constant::check(program));
+    diagnostics.sort_by_key(|d| d.span.start);
+    config.apply(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_to_json() {
+        let source = "let a = 1;\nreturn a; \"b\"";
+        let diagnostic = Diagnostic::warning("A0001", "say \"hi\"", Span::new(21, 24));
+
+        assert_eq!(
+            diagnostic.to_json("dir/file.monkey", source),
+            r#"{"file": "dir/file.monkey", "code": "A0001", "severity": "warning", "message": "say \"hi\"", "span": {"start": 21, "end": 24}, "line": 2, "column": 11}"#
+        );
+    }
+
+    #[test]
+    fn test_render_snippet() {
+        let source = "let a = 1;\nlet é = a +;\n";
+        let diagnostic = Diagnostic::warning("A0001", "oops", Span::new(20, 23));
+        let expected = "warning[A0001]: oops
+  |
+2 | let é = a +;
+  |         ^^^
+";
+        assert_eq!(diagnostic.render_snippet(source), expected);
+
+        let errors = Parser::parse("let x = ").unwrap_err();
+        let expected = "error[P0002]: no prefix parse function for \"end of input\" found
+  |
+1 | let x = 
+  |         ^
+";
+        assert_eq!(
+            Diagnostic::from(&errors[0]).render_snippet("let x = "),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_from_parser_error() {
+        let errors = Parser::parse("let = 1").unwrap_err();
+        let diagnostic = Diagnostic::from(&errors[0]);
+
+        assert_eq!(diagnostic.code, "P0001");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.span, Span::new(4, 5));
+    }
+}
@@ -0,0 +1,402 @@
+//! Resolution of identifiers to the bindings they refer to.
+//!
+//! As in the book's evaluator, only functions get an environment of their
+//! own: a `let` in another block, such as a branch of an `if`, binds its
+//! name in the enclosing function or program. The bindings of a `for` loop
+//! and the parameter of a `catch` are only visible in their block.
+//!
+//! The body of a function only runs once it's called, so it's resolved at
+//! the end of the scope the function is in, seeing every name bound there.
+//! This lets functions call the ones declared after them.
+
+use std::{collections::HashMap, mem, slice};
+
+use crate::{
+    ast::{
+        expressions::{FunctionLiteral, IdentExpression},
+        patterns::Pattern,
+        statements::BlockStatement,
+        Expression, Program, Statement,
+    },
+    token::Span,
+};
+
+/// What introduced a binding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingKind {
+    Let,
+    Const,
+    /// A parameter of a function, including the names bound by its array
+    /// patterns and its rest parameter
+    Parameter,
+    /// A name bound by a hash pattern, which is also the key its value is
+    /// read from
+    Key,
+    /// A binding of a `for` loop
+    Loop,
+    /// The parameter of a `catch`
+    Catch,
+    Enum,
+}
+
+/// A name bound in the program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    /// The span of the name where it's bound
+    pub span: Span,
+}
+
+/// A use of a name in an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub span: Span,
+    /// Index of the binding in [`Resolution::bindings`], or `None` when the
+    /// name is never bound, e.g. a builtin function
+    pub binding: Option<usize>,
+}
+
+/// What a [`Scope`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScopeKind {
+    Program,
+    Function,
+    /// A `for` loop, binding its names in its body
+    Loop,
+    /// A `catch` block, binding its parameter
+    Catch,
+}
+
+/// A region of the program with bindings of its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// The span of the function, the `for` loop, or the parameter and
+    /// block of the `catch`. The program scope contains every offset, so
+    /// its span ends at `usize::MAX`.
+    pub span: Span,
+    /// Index of the enclosing scope in
+    /// [`Index::scopes`](crate::analyzer::Index::scopes), `None` for the
+    /// program
+    pub parent: Option<usize>,
+    /// The bindings made in the scope, as indexes in
+    /// [`Resolution::bindings`], each with the offset from which it's
+    /// visible, e.g. the end of its `let` statement
+    pub(crate) bindings: Vec<(usize, usize)>,
+}
+
+/// The bindings, references and scopes of a program, each in source
+/// order.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Resolution {
+    pub bindings: Vec<Binding>,
+    pub references: Vec<Reference>,
+    pub scopes: Vec<Scope>,
+}
+
+/// Resolves every identifier of `program` to its binding.
+pub fn resolve(program: &Program) -> Resolution {
+    resolve_renamed(program, HashMap::new())
+}
+
+/// Resolves `program` as if the identifiers starting at the offsets of
+/// `renames` had the names they're mapped to, to check that renaming them
+/// keeps every reference bound to the same binding.
+pub(crate) fn resolve_renamed<'a>(
+    program: &'a Program,
+    renames: HashMap<usize, &'a str>,
+) -> Resolution {
+    let mut resolver = Resolver {
+        renames,
+        ..Resolver::default()
+    };
+    resolver.push_scope(ScopeKind::Program, Span::new(0, usize::MAX));
+    resolver.statements(&program.statements);
+    resolver.pop_scope();
+
+    // Function bodies are resolved last, so the bindings and scopes are
+    // sorted and renumbered where they're referred to
+    let mut resolution = resolver.resolution;
+    let bindings = sort_by_start(&mut resolution.bindings, |b| b.span);
+    let scopes = sort_by_start(&mut resolution.scopes, |s| s.span);
+    for reference in resolution.references.iter_mut() {
+        reference.binding = reference.binding.map(|i| bindings[i]);
+    }
+    for scope in resolution.scopes.iter_mut() {
+        scope.parent = scope.parent.map(|i| scopes[i]);
+        for (binding, _) in scope.bindings.iter_mut() {
+            *binding = bindings[*binding];
+        }
+    }
+    resolution
+        .references
+        .sort_by_key(|reference| reference.span.start);
+    resolution
+}
+
+/// Sorts `items` by the start of their span, returning the new index of
+/// each item by its old one.
+fn sort_by_start<T>(items: &mut Vec<T>, span: impl Fn(&T) -> Span) -> Vec<usize> {
+    let mut indexed: Vec<(usize, T)> = mem::take(items).into_iter().enumerate().collect();
+    indexed.sort_by_key(|(_, item)| span(item).start);
+
+    let mut positions = vec![0; indexed.len()];
+    for (new, (old, _)) in indexed.iter().enumerate() {
+        positions[*old] = new;
+    }
+    *items = indexed.into_iter().map(|(_, item)| item).collect();
+    positions
+}
+
+/// A scope being resolved.
+#[derive(Default)]
+struct Frame<'a> {
+    /// Index of the scope in [`Resolution::scopes`]
+    scope: usize,
+    /// The names bound so far, each with the index of its binding
+    names: Vec<(&'a str, usize)>,
+    /// Functions to resolve when the scope ends
+    functions: Vec<&'a FunctionLiteral>,
+}
+
+#[derive(Default)]
+struct Resolver<'a> {
+    frames: Vec<Frame<'a>>,
+    /// Names to use instead of those of the identifiers starting at the
+    /// given offsets
+    renames: HashMap<usize, &'a str>,
+    resolution: Resolution,
+}
+
+impl<'a> Resolver<'a> {
+    fn push_scope(&mut self, kind: ScopeKind, span: Span) {
+        let scope = self.resolution.scopes.len();
+        self.resolution.scopes.push(Scope {
+            kind,
+            span,
+            parent: self.frames.last().map(|frame| frame.scope),
+            bindings: Vec::new(),
+        });
+        self.frames.push(Frame {
+            scope,
+            ..Frame::default()
+        });
+    }
+
+    /// Ends the innermost scope, after resolving the functions in it.
+    fn pop_scope(&mut self) {
+        let functions = self
+            .frames
+            .last_mut()
+            .map(|frame| mem::take(&mut frame.functions))
+            .unwrap_or_default();
+        for function in functions {
+            let span = function.token.span.to(function.body.span);
+            self.push_scope(ScopeKind::Function, span);
+            for parameter in function.parameters.iter() {
+                self.pattern(parameter, span.start);
+            }
+            if let Some(rest) = &function.rest {
+                self.bind(rest, BindingKind::Parameter, span.start);
+            }
+            self.statements(&function.body.statements);
+            self.pop_scope();
+        }
+        self.frames.pop();
+    }
+
+    fn name(&self, ident: &'a IdentExpression) -> &'a str {
+        self.renames
+            .get(&ident.token.span.start)
+            .copied()
+            .unwrap_or(&ident.value)
+    }
+
+    /// Binds `ident` in the innermost scope, visible from the offset
+    /// `visible_from` on.
+    fn bind(&mut self, ident: &'a IdentExpression, kind: BindingKind, visible_from: usize) {
+        let name = self.name(ident);
+        let index = self.resolution.bindings.len();
+        self.resolution.bindings.push(Binding {
+            name: name.to_string(),
+            kind,
+            span: ident.token.span,
+        });
+        if let Some(frame) = self.frames.last_mut() {
+            frame.names.push((name, index));
+            self.resolution.scopes[frame.scope]
+                .bindings
+                .push((index, visible_from));
+        }
+    }
+
+    fn pattern(&mut self, pattern: &'a Pattern, visible_from: usize) {
+        match pattern {
+            Pattern::Ident(name) => self.bind(name, BindingKind::Parameter, visible_from),
+            Pattern::Array(p) => {
+                for element in p.elements.iter() {
+                    self.pattern(element, visible_from);
+                }
+            }
+            Pattern::Hash(p) => {
+                for key in p.keys.iter() {
+                    self.bind(key, BindingKind::Key, visible_from);
+                }
+            }
+        }
+    }
+
+    fn reference(&mut self, ident: &'a IdentExpression) {
+        let name = self.name(ident);
+        let binding = self.frames.iter().rev().find_map(|frame| {
+            frame
+                .names
+                .iter()
+                .rev()
+                .find(|(bound, _)| *bound == name)
+                .map(|&(_, index)| index)
+        });
+        self.resolution.references.push(Reference {
+            span: ident.token.span,
+            binding,
+        });
+    }
+
+    fn statements(&mut self, statements: &'a [Statement]) {
+        for stmt in statements.iter() {
+            self.statement(stmt);
+        }
+    }
+
+    /// Resolves a block which shares the scope it's in.
+    fn block(&mut self, block: &'a BlockStatement) {
+        self.statements(&block.statements);
+    }
+
+    /// Resolves a block in a scope of its own spanning `span`, after
+    /// binding `names` in it.
+    fn scoped_block(
+        &mut self,
+        kind: ScopeKind,
+        span: Span,
+        names: &'a [IdentExpression],
+        binding_kind: BindingKind,
+        block: &'a BlockStatement,
+    ) {
+        self.push_scope(kind, span);
+        for name in names.iter() {
+            self.bind(name, binding_kind, span.start);
+        }
+        self.block(block);
+        self.pop_scope();
+    }
+
+    fn statement(&mut self, stmt: &'a Statement) {
+        let end = stmt.span().end;
+        match stmt {
+            Statement::Let(s) => {
+                self.expression(&s.value);
+                let kind = match s.is_const() {
+                    true => BindingKind::Const,
+                    false => BindingKind::Let,
+                };
+                self.bind(&s.name, kind, end);
+            }
+            Statement::Return(s) => self.expression(&s.value),
+            Statement::Throw(s) => self.expression(&s.value),
+            Statement::Defer(s) => self.expression(&s.value),
+            Statement::Switch(s) => {
+                self.expression(&s.subject);
+                for case in s.cases.iter() {
+                    for value in case.values.iter() {
+                        self.expression(value);
+                    }
+                    self.block(&case.body);
+                }
+                if let Some(default) = &s.default {
+                    self.block(default);
+                }
+            }
+            Statement::DoWhile(s) => {
+                self.block(&s.body);
+                self.expression(&s.condition);
+            }
+            Statement::ForIn(s) => {
+                self.expression(&s.iterable);
+                self.scoped_block(
+                    ScopeKind::Loop,
+                    stmt.span(),
+                    &s.bindings,
+                    BindingKind::Loop,
+                    &s.body,
+                );
+            }
+            Statement::Enum(s) => self.bind(&s.name, BindingKind::Enum, end),
+            Statement::Expression(s) => self.expression(&s.expression),
+        }
+    }
+
+    fn expression(&mut self, expression: &'a Expression) {
+        match expression {
+            Expression::Ident(e) => self.reference(e),
+            Expression::Integer(_)
+            | Expression::Float(_)
+            | Expression::Boolean(_)
+            | Expression::Placeholder(_) => {}
+            Expression::Prefix(e) => self.expression(&e.right),
+            Expression::Infix(e) => {
+                self.expression(&e.left);
+                self.expression(&e.right);
+            }
+            Expression::If(e) => {
+                self.expression(&e.condition);
+                self.block(&e.consequence);
+                if let Some(alternative) = &e.alternative {
+                    self.block(alternative);
+                }
+            }
+            Expression::Try(e) => {
+                self.block(&e.body);
+                self.scoped_block(
+                    ScopeKind::Catch,
+                    e.param.token.span.to(e.handler.span),
+                    slice::from_ref(&e.param),
+                    BindingKind::Catch,
+                    &e.handler,
+                );
+            }
+            Expression::Function(e) => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.functions.push(e);
+                }
+            }
+            // The property is looked up in the object, not in scope
+            Expression::Member(e) => self.expression(&e.object),
+            Expression::Set(e) => {
+                for element in e.elements.iter() {
+                    self.expression(element);
+                }
+            }
+            Expression::Array(e) => {
+                for element in e.elements.iter() {
+                    self.expression(element);
+                }
+            }
+            Expression::Spread(e) => self.expression(&e.value),
+            Expression::Pipe(e) => {
+                self.expression(&e.left);
+                self.expression(&e.right);
+            }
+            Expression::Call(e) => {
+                self.expression(&e.function);
+                for argument in e.arguments.iter() {
+                    self.expression(argument);
+                }
+                for argument in e.named_arguments.iter() {
+                    self.expression(&argument.value);
+                }
+            }
+        }
+    }
+}
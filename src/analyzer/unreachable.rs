@@ -0,0 +1,160 @@
+//! Detection of code that can never run: statements after a `return` or
+//! `throw` and branches of `if` expressions with a literal condition.
+
+use crate::{
+    analyzer::Diagnostic,
+    ast::{
+        statements::BlockStatement,
+        visit::{self, Visitor},
+        Expression, Program, Statement,
+    },
+};
+
+pub fn check(program: &Program) -> Vec<Diagnostic> {
+    let mut checker = UnreachableChecker::default();
+    checker.visit_program(program);
+    checker.diagnostics
+}
+
+#[derive(Default)]
+struct UnreachableChecker {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl UnreachableChecker {
+    /// Reports the statements following the first `return` or `throw` of
+    /// `statements` as a single span, from the first dead statement to the
+    /// last one.
+    fn check_statements(&mut self, statements: &[Statement]) {
+        let Some(i) = statements
+            .iter()
+            .position(|stmt| matches!(stmt, Statement::Return(_) | Statement::Throw(_)))
+        else {
+            return;
+        };
+
+        if let [first, .., last] | [first @ last] = &statements[i + 1..] {
+            let message = format!(
+                "unreachable code after {} statement",
+                statements[i].token_literal()
+            );
+            self.diagnostics.push(Diagnostic::warning(
+                "A0001",
+                message,
+                first.span().to(last.span()),
+            ));
+        }
+    }
+}
+
+impl Visitor for UnreachableChecker {
+    fn visit_program(&mut self, program: &Program) {
+        self.check_statements(&program.statements);
+        visit::walk_program(self, program);
+    }
+
+    fn visit_block(&mut self, block: &BlockStatement) {
+        self.check_statements(&block.statements);
+        visit::walk_block(self, block);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::If(e) = expression {
+            if let Expression::Boolean(condition) = e.condition.as_ref() {
+                let dead = if condition.value {
+                    e.alternative.as_ref().map(|alt| ("else", alt.span))
+                } else {
+                    Some(("if", e.consequence.span))
+                };
+
+                if let Some((branch, span)) = dead {
+                    let message = format!(
+                        "unreachable {branch} branch, the condition is always {}",
+                        condition.value
+                    );
+                    self.diagnostics
+                        .push(Diagnostic::warning("A0002", message, span));
+                }
+            }
+        }
+
+        visit::walk_expression(self, expression);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{analyzer::analyze, parser::Parser, token::Span};
+
+    fn warnings(source: &str) -> Vec<(String, &str)> {
+        let program = Parser::parse(source).unwrap();
+        analyze(&program)
+            .into_iter()
+            .map(|d| (d.message, &source[d.span.start..d.span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_code_after_return() {
+        let source = "let a = 1; return a; a + 1; let b = 2;";
+        assert_eq!(
+            warnings(source),
+            vec![(
+                "unreachable code after return statement".to_string(),
+                "a + 1; let b = 2"
+            )]
+        );
+
+        let source = "if (a) { return 1; b } else { return 2 }";
+        assert_eq!(
+            warnings(source),
+            vec![("unreachable code after return statement".to_string(), "b")]
+        );
+
+        let source = "switch (a) { case 1: return 2; b; c default: d }";
+        assert_eq!(
+            warnings(source),
+            vec![(
+                "unreachable code after return statement".to_string(),
+                "b; c"
+            )]
+        );
+
+        let source = "try { throw 1; a } catch (e) { e }";
+        assert_eq!(
+            warnings(source),
+            vec![("unreachable code after throw statement".to_string(), "a")]
+        );
+
+        assert!(warnings("let a = 1; return a;").is_empty());
+    }
+
+    #[test]
+    fn test_literal_conditions() {
+        let source = "if (true) { a } else { b }; if (false) { c }";
+        assert_eq!(
+            warnings(source),
+            vec![
+                (
+                    "unreachable else branch, the condition is always true".to_string(),
+                    "{ b }"
+                ),
+                (
+                    "unreachable if branch, the condition is always false".to_string(),
+                    "{ c }"
+                ),
+            ]
+        );
+
+        assert!(warnings("if (true) { a }; if (!false) { b }").is_empty());
+    }
+
+    #[test]
+    fn test_diagnostic_span() {
+        let program = Parser::parse("return 1;\n  x").unwrap();
+        let diagnostics = analyze(&program);
+
+        assert_eq!(diagnostics[0].code, "A0001");
+        assert_eq!(diagnostics[0].span, Span::new(12, 13));
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use crate::{
+    ast::{statements::BlockStatement, Statement},
+    token::Comment,
+};
+
+/// The comments of a program parsed with
+/// [`Parser::keep_comments`](crate::parser::Parser::keep_comments),
+/// attached to the nodes around them:
+///
+/// - a comment on the same line as the end of a statement trails it
+/// - other comments lead the innermost statement that follows or contains
+///   them
+/// - comments with no statement after them in their block are dangling at
+///   the end of the block, or at the end of the program at the top level
+///
+/// Expressions have no comments of their own: a comment between the
+/// arguments of a call or the elements of a list leads the statement
+/// containing it, so the formatter moves it before that statement.
+///
+/// The statements preceded by a blank line, or by a blank line before their
+/// leading comments, are kept too, for the formatter to keep them apart.
+///
+/// Statements and blocks are looked up by where they start, so the
+/// comments only match the program they were parsed with.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Comments {
+    leading: BTreeMap<usize, Vec<Comment>>,
+    trailing: BTreeMap<usize, Comment>,
+    dangling: BTreeMap<usize, Vec<Comment>>,
+    program_end: Vec<Comment>,
+    blank_lines: BTreeSet<usize>,
+}
+
+impl Comments {
+    /// Returns the comments on the lines before `statement`.
+    pub fn leading(&self, statement: &Statement) -> &[Comment] {
+        self.leading
+            .get(&statement.span().start)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn add_leading(&mut self, statement: &Statement, comment: Comment) {
+        self.leading
+            .entry(statement.span().start)
+            .or_default()
+            .push(comment);
+    }
+
+    /// Returns the comment after the end of `statement`, on the same line.
+    pub fn trailing(&self, statement: &Statement) -> Option<&Comment> {
+        self.trailing.get(&statement.span().start)
+    }
+
+    pub fn set_trailing(&mut self, statement: &Statement, comment: Comment) {
+        self.trailing.insert(statement.span().start, comment);
+    }
+
+    /// Returns the comments after the last statement of `block`.
+    pub fn dangling(&self, block: &BlockStatement) -> &[Comment] {
+        self.dangling
+            .get(&block.span.start)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn add_dangling(&mut self, block: &BlockStatement, comment: Comment) {
+        self.dangling
+            .entry(block.span.start)
+            .or_default()
+            .push(comment);
+    }
+
+    /// Whether there is a blank line before `statement` and its leading
+    /// comments.
+    pub fn blank_line_before(&self, statement: &Statement) -> bool {
+        self.blank_lines.contains(&statement.span().start)
+    }
+
+    pub fn add_blank_line_before(&mut self, statement: &Statement) {
+        self.blank_lines.insert(statement.span().start);
+    }
+
+    /// Returns the comments after the last statement of the program.
+    pub fn program_end(&self) -> &[Comment] {
+        &self.program_end
+    }
+
+    pub fn add_program_end(&mut self, comment: Comment) {
+        self.program_end.push(comment);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty()
+            && self.trailing.is_empty()
+            && self.dangling.is_empty()
+            && self.program_end.is_empty()
+    }
+
+    /// Returns every comment, in source order.
+    pub fn iter(&self) -> impl Iterator<Item = &Comment> {
+        let mut comments: Vec<&Comment> = self
+            .leading
+            .values()
+            .chain(self.dangling.values())
+            .flatten()
+            .chain(self.trailing.values())
+            .chain(self.program_end.iter())
+            .collect();
+        comments.sort_by_key(|comment| comment.span.start);
+        comments.into_iter()
+    }
+}
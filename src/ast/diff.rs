@@ -0,0 +1,287 @@
+//! Structural differences between two programs.
+//!
+//! Nodes are compared ignoring their tokens, like their `PartialEq`
+//! implementations, so reformatting a program or moving it around in the
+//! file changes nothing. Statements are matched by the longest common
+//! subsequence of the two lists, and a changed node is descended into
+//! while its shape stays the same, e.g. a function keeping its parameters,
+//! so each [`Change`] is as small as possible.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    ast::{statements::BlockStatement, Expression, Program, Statement},
+    token::Span,
+};
+
+/// A difference between two programs, with the span of the node in the
+/// old program, the new one, or both.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    /// A statement or block only in the new program
+    Added { new: Span },
+    /// A statement or block only in the old program
+    Removed { old: Span },
+    /// A node replaced by a different one
+    Modified { old: Span, new: Span },
+}
+
+/// Returns the changes turning `old` into `new`, in source order.
+pub fn diff(old: &Program, new: &Program) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_statements(&old.statements, &new.statements, &mut changes);
+    changes
+}
+
+/// Matches the statements of `old` and `new` by their longest common
+/// subsequence. In each run of unmatched statements, those of the same
+/// kind are compared pairwise and the rest are removed or added.
+fn diff_statements(old: &[Statement], new: &[Statement], changes: &mut Vec<Change>) {
+    // lengths[i][j] is the length of the common subsequence of old[i..]
+    // and new[j..]
+    let mut lengths = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff_run(&removed, &added, changes);
+            removed.clear();
+            added.clear();
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            removed.push(&old[i]);
+            i += 1;
+        } else {
+            added.push(&new[j]);
+            j += 1;
+        }
+    }
+    diff_run(&removed, &added, changes);
+}
+
+/// Compares a run of statements of the old program with the run of the
+/// new program that replaced it.
+fn diff_run(removed: &[&Statement], added: &[&Statement], changes: &mut Vec<Change>) {
+    let pairs = removed.len().min(added.len());
+    for (old, new) in removed.iter().zip(added.iter()) {
+        if core::mem::discriminant(*old) == core::mem::discriminant(*new) {
+            diff_statement(old, new, changes);
+        } else {
+            changes.push(Change::Removed { old: old.span() });
+            changes.push(Change::Added { new: new.span() });
+        }
+    }
+    for old in removed[pairs..].iter() {
+        changes.push(Change::Removed { old: old.span() });
+    }
+    for new in added[pairs..].iter() {
+        changes.push(Change::Added { new: new.span() });
+    }
+}
+
+fn diff_statement(old: &Statement, new: &Statement, changes: &mut Vec<Change>) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Statement::Let(o), Statement::Let(n))
+            if o.is_const() == n.is_const() && o.name == n.name =>
+        {
+            diff_expression(&o.value, &n.value, changes)
+        }
+        (Statement::Return(o), Statement::Return(n)) => {
+            diff_expression(&o.value, &n.value, changes)
+        }
+        (Statement::Throw(o), Statement::Throw(n)) => diff_expression(&o.value, &n.value, changes),
+        (Statement::Defer(o), Statement::Defer(n)) => diff_expression(&o.value, &n.value, changes),
+        (Statement::Expression(o), Statement::Expression(n)) => {
+            diff_expression(&o.expression, &n.expression, changes)
+        }
+        (Statement::ForIn(o), Statement::ForIn(n))
+            if o.bindings == n.bindings && o.iterable == n.iterable =>
+        {
+            diff_blocks(&o.body, &n.body, changes)
+        }
+        (Statement::DoWhile(o), Statement::DoWhile(n)) if o.condition == n.condition => {
+            diff_blocks(&o.body, &n.body, changes)
+        }
+        (Statement::Switch(o), Statement::Switch(n))
+            if o.subject == n.subject
+                && o.cases.len() == n.cases.len()
+                && o.cases
+                    .iter()
+                    .zip(n.cases.iter())
+                    .all(|(o, n)| o.values == n.values) =>
+        {
+            for (o, n) in o.cases.iter().zip(n.cases.iter()) {
+                diff_blocks(&o.body, &n.body, changes);
+            }
+            diff_optional_blocks(&o.default, &n.default, changes);
+        }
+        _ => changes.push(Change::Modified {
+            old: old.span(),
+            new: new.span(),
+        }),
+    }
+}
+
+fn diff_blocks(old: &BlockStatement, new: &BlockStatement, changes: &mut Vec<Change>) {
+    diff_statements(&old.statements, &new.statements, changes);
+}
+
+fn diff_optional_blocks(
+    old: &Option<BlockStatement>,
+    new: &Option<BlockStatement>,
+    changes: &mut Vec<Change>,
+) {
+    match (old, new) {
+        (Some(old), Some(new)) => diff_blocks(old, new, changes),
+        (Some(old), None) => changes.push(Change::Removed { old: old.span }),
+        (None, Some(new)) => changes.push(Change::Added { new: new.span }),
+        (None, None) => {}
+    }
+}
+
+/// Compares `old` and `new` pairwise, as long as there are as many of
+/// each. Returns whether they could be compared.
+fn diff_expression_lists(
+    old: &[Expression],
+    new: &[Expression],
+    changes: &mut Vec<Change>,
+) -> bool {
+    if old.len() != new.len() {
+        return false;
+    }
+    for (old, new) in old.iter().zip(new.iter()) {
+        diff_expression(old, new, changes);
+    }
+    true
+}
+
+fn diff_expression(old: &Expression, new: &Expression, changes: &mut Vec<Change>) {
+    if old == new {
+        return;
+    }
+
+    let compared = match (old, new) {
+        (Expression::Prefix(o), Expression::Prefix(n)) if o.operator == n.operator => {
+            diff_expression(&o.right, &n.right, changes);
+            true
+        }
+        (Expression::Infix(o), Expression::Infix(n)) if o.operator == n.operator => {
+            diff_expression(&o.left, &n.left, changes);
+            diff_expression(&o.right, &n.right, changes);
+            true
+        }
+        (Expression::If(o), Expression::If(n)) if o.condition == n.condition => {
+            diff_blocks(&o.consequence, &n.consequence, changes);
+            diff_optional_blocks(&o.alternative, &n.alternative, changes);
+            true
+        }
+        (Expression::Try(o), Expression::Try(n)) if o.param == n.param => {
+            diff_blocks(&o.body, &n.body, changes);
+            diff_blocks(&o.handler, &n.handler, changes);
+            true
+        }
+        (Expression::Function(o), Expression::Function(n))
+            if o.parameters == n.parameters && o.rest == n.rest =>
+        {
+            diff_blocks(&o.body, &n.body, changes);
+            true
+        }
+        (Expression::Call(o), Expression::Call(n))
+            if o.function == n.function && o.named_arguments == n.named_arguments =>
+        {
+            diff_expression_lists(&o.arguments, &n.arguments, changes)
+        }
+        (Expression::Array(o), Expression::Array(n)) => {
+            diff_expression_lists(&o.elements, &n.elements, changes)
+        }
+        (Expression::Member(o), Expression::Member(n))
+            if o.is_optional() == n.is_optional() && o.property == n.property =>
+        {
+            diff_expression(&o.object, &n.object, changes);
+            true
+        }
+        _ => false,
+    };
+
+    if !compared {
+        changes.push(Change::Modified {
+            old: old.span(),
+            new: new.span(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    /// Returns the changes between `old` and `new` with the source text of
+    /// their spans.
+    fn changes<'a>(old: &'a str, new: &'a str) -> Vec<(&'a str, &'a str)> {
+        let text = |source: &'a str, span: Span| &source[span.start..span.end];
+        diff(&Parser::parse(old).unwrap(), &Parser::parse(new).unwrap())
+            .into_iter()
+            .map(|change| match change {
+                Change::Added { new: span } => ("", text(new, span)),
+                Change::Removed { old: span } => (text(old, span), ""),
+                Change::Modified { old: o, new: n } => (text(old, o), text(new, n)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let tests = vec![
+            // Formatting doesn't matter
+            ("let x = 1 + 2;", "let   x =\n  (1 + 2)", vec![]),
+            // Statements added, removed and replaced
+            ("a; b; c;", "a; x; c; d;", vec![("b", "x"), ("", "d")]),
+            ("a; b; c;", "c;", vec![("a", ""), ("b", "")]),
+            ("a; let x = 1;", "let x = 1; a;", vec![("a", ""), ("", "a")]),
+            (
+                "return 1;",
+                "throw 1;",
+                vec![("return 1", ""), ("", "throw 1")],
+            ),
+            // Changes are found inside nodes keeping their shape
+            (
+                "let f = fn(x) { let y = x * 2; y }",
+                "let f = fn(x) { let y = x * 3; log(y); y }",
+                vec![("2", "3"), ("", "log(y)")],
+            ),
+            (
+                "if (a) { b } else { c }",
+                "if (a) { b }",
+                vec![("{ c }", "")],
+            ),
+            ("f(1, 2)", "f(1, 3)", vec![("2", "3")]),
+            ("f(1, 2)", "f(1)", vec![("f(1, 2)", "f(1)")]),
+            // Renaming a binding replaces the statement
+            ("let x = 1;", "let y = 1;", vec![("let x = 1", "let y = 1")]),
+            (
+                "let f = fn(x) { x }",
+                "let f = fn(y) { x }",
+                vec![("fn(x) { x }", "fn(y) { x }")],
+            ),
+        ];
+
+        for (old, new, expected) in tests {
+            assert_eq!(changes(old, new), expected, "old: {old}, new: {new}");
+        }
+    }
+}
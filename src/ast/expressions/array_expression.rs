@@ -0,0 +1,44 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{Expression, ExpressionTrait, NodeTrait},
+    token::{Span, Token},
+};
+
+/// `[<elements>]`, where elements may be spread, as in `[1, ...rest]`.
+#[derive(Debug)]
+pub struct ArrayLiteral {
+    /// The `[` token
+    pub token: Token,
+    pub elements: Vec<Expression>,
+    /// The span from the `[` to the `]`
+    pub span: Span,
+}
+
+impl Display for ArrayLiteral {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let elements: Vec<String> = self.elements.iter().map(ToString::to_string).collect();
+        write!(f, "[{}]", elements.join(", "))
+    }
+}
+
+impl PartialEq for ArrayLiteral {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl NodeTrait for ArrayLiteral {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for ArrayLiteral {
+    fn expression_node(&self) {}
+}
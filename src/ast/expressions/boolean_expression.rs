@@ -0,0 +1,35 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+#[derive(Debug)]
+pub struct BooleanLiteral {
+    pub token: Token,
+    pub value: bool,
+}
+
+impl Display for BooleanLiteral {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl PartialEq for BooleanLiteral {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl NodeTrait for BooleanLiteral {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for BooleanLiteral {
+    fn expression_node(&self) {}
+}
@@ -0,0 +1,92 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{expressions::IdentExpression, Expression, ExpressionTrait, NodeTrait},
+    token::{Span, Token},
+};
+
+/// `<function>(<arguments>, <named arguments>)`, e.g. `point(1, y: 2)`.
+/// Named arguments always follow the positional ones.
+#[derive(Debug)]
+pub struct CallExpression {
+    /// The `(` token
+    pub token: Token,
+    pub function: Box<Expression>,
+    pub arguments: Vec<Expression>,
+    pub named_arguments: Vec<NamedArgument>,
+    /// The span from the start of `function` to the closing parenthesis
+    pub span: Span,
+}
+
+/// A `<name>: <value>` argument, bound to the parameter called `name`.
+#[derive(Debug)]
+pub struct NamedArgument {
+    pub name: IdentExpression,
+    pub value: Expression,
+}
+
+impl CallExpression {
+    /// Whether some of the arguments are placeholders, making the call a
+    /// partial application, e.g. `f(_, 2)`.
+    pub fn is_partial(&self) -> bool {
+        self.arguments
+            .iter()
+            .chain(self.named_arguments.iter().map(|arg| &arg.value))
+            .any(|arg| matches!(arg, Expression::Placeholder(_)))
+    }
+
+    /// Returns the arguments as written between the parentheses, using
+    /// `print` for each value.
+    pub fn argument_list(&self, print: impl Fn(&Expression) -> String) -> String {
+        let positional = self.arguments.iter().map(&print);
+        let named = self
+            .named_arguments
+            .iter()
+            .map(|arg| format!("{}: {}", arg.name.value, print(&arg.value)));
+
+        positional.chain(named).collect::<Vec<String>>().join(", ")
+    }
+}
+
+impl Display for CallExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}({})",
+            self.function,
+            self.argument_list(ToString::to_string)
+        )
+    }
+}
+
+impl PartialEq for CallExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.function == other.function
+            && self.arguments == other.arguments
+            && self.named_arguments == other.named_arguments
+    }
+}
+
+impl PartialEq for NamedArgument {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
+impl NodeTrait for CallExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for CallExpression {
+    fn expression_node(&self) {}
+}
@@ -0,0 +1,38 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+/// A float like `2.5` or `1e9`. The value is always finite.
+#[derive(Debug)]
+pub struct FloatLiteral {
+    pub token: Token,
+    pub value: f64,
+}
+
+impl Display for FloatLiteral {
+    /// Writes the shortest representation that reads back as the same
+    /// float, e.g. `1000000000.0` for `1e9`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.value)
+    }
+}
+
+impl PartialEq for FloatLiteral {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl NodeTrait for FloatLiteral {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for FloatLiteral {
+    fn expression_node(&self) {}
+}
@@ -0,0 +1,65 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{
+        expressions::IdentExpression, patterns::Pattern, statements::BlockStatement,
+        ExpressionTrait, NodeTrait,
+    },
+    token::Token,
+};
+
+/// `fn(<parameters>, ...<rest>) { <body> }`, where the optional `rest`
+/// parameter collects the arguments left over after `parameters`.
+#[derive(Debug)]
+pub struct FunctionLiteral {
+    pub token: Token,
+    pub parameters: Vec<Pattern>,
+    pub rest: Option<IdentExpression>,
+    pub body: BlockStatement,
+}
+
+impl FunctionLiteral {
+    /// Returns the parameters as written between the parentheses, e.g.
+    /// `a, [b, c], ...rest`.
+    pub fn parameter_list(&self) -> String {
+        let mut parameters: Vec<String> = self.parameters.iter().map(ToString::to_string).collect();
+        if let Some(rest) = &self.rest {
+            parameters.push(format!("...{}", rest.value));
+        }
+        parameters.join(", ")
+    }
+}
+
+impl Display for FunctionLiteral {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}({}) {}",
+            self.token_literal(),
+            self.parameter_list(),
+            self.body
+        )
+    }
+}
+
+impl PartialEq for FunctionLiteral {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.parameters == other.parameters && self.rest == other.rest && self.body == other.body
+    }
+}
+
+impl NodeTrait for FunctionLiteral {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for FunctionLiteral {
+    fn expression_node(&self) {}
+}
@@ -1,21 +1,30 @@
-use std::fmt::Display;
+use alloc::string::String;
+use core::fmt::Display;
 
 use crate::{
     ast::{ExpressionTrait, NodeTrait},
     token::Token,
 };
 
+#[derive(Debug)]
 pub struct IdentExpression {
     pub token: Token,
     pub value: String,
 }
 
 impl Display for IdentExpression {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.value)
     }
 }
 
+impl PartialEq for IdentExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
 impl NodeTrait for IdentExpression {
     fn token_literal(&self) -> &str {
         &self.token.literal
@@ -0,0 +1,44 @@
+use alloc::boxed::Box;
+use core::fmt::Display;
+
+use crate::{
+    ast::{statements::BlockStatement, Expression, ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+#[derive(Debug)]
+pub struct IfExpression {
+    pub token: Token,
+    pub condition: Box<Expression>,
+    pub consequence: BlockStatement,
+    pub alternative: Option<BlockStatement>,
+}
+
+impl Display for IfExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "if{} {}", self.condition, self.consequence)?;
+        if let Some(alternative) = &self.alternative {
+            write!(f, "else {alternative}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for IfExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.condition == other.condition
+            && self.consequence == other.consequence
+            && self.alternative == other.alternative
+    }
+}
+
+impl NodeTrait for IfExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for IfExpression {
+    fn expression_node(&self) {}
+}
@@ -1,10 +1,12 @@
-use std::fmt::Display;
+use alloc::{boxed::Box, string::String};
+use core::fmt::Display;
 
 use crate::{
     ast::{Expression, ExpressionTrait, NodeTrait},
     token::Token,
 };
 
+#[derive(Debug)]
 pub struct InfixExpression {
     pub token: Token,
     pub left: Box<Expression>,
@@ -13,11 +15,18 @@ pub struct InfixExpression {
 }
 
 impl Display for InfixExpression {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "({} {} {})", self.left, self.operator, self.right)
     }
 }
 
+impl PartialEq for InfixExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.operator == other.operator && self.left == other.left && self.right == other.right
+    }
+}
+
 impl NodeTrait for InfixExpression {
     fn token_literal(&self) -> &str {
         &self.token.literal
@@ -1,21 +1,29 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use crate::{
     ast::{ExpressionTrait, NodeTrait},
     token::Token,
 };
 
+#[derive(Debug)]
 pub struct IntegerLiteral {
     pub token: Token,
     pub value: i64,
 }
 
 impl Display for IntegerLiteral {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.value)
     }
 }
 
+impl PartialEq for IntegerLiteral {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
 impl NodeTrait for IntegerLiteral {
     fn token_literal(&self) -> &str {
         &self.token.literal
@@ -0,0 +1,61 @@
+use alloc::boxed::Box;
+use core::fmt::Display;
+
+use crate::{
+    ast::{expressions::IdentExpression, Expression, ExpressionTrait, NodeTrait},
+    token::{Token, TokenType},
+};
+
+/// `<object>.<property>`. Calling a member, as in `obj.method(args)`, is
+/// a method call, with `obj` bound to `self` inside the method.
+///
+/// The optional form `<object>?.<property>` is told apart by its token,
+/// and is null instead of an error when the object is null.
+#[derive(Debug)]
+pub struct MemberExpression {
+    /// The `.` or `?.` token
+    pub token: Token,
+    pub object: Box<Expression>,
+    pub property: IdentExpression,
+}
+
+impl MemberExpression {
+    /// Checks if the member was accessed with `?.`
+    pub fn is_optional(&self) -> bool {
+        self.token.token_type == TokenType::QuestionDot
+    }
+
+    /// Returns the operator accessing the member
+    pub fn operator(&self) -> &str {
+        if self.is_optional() {
+            "?."
+        } else {
+            "."
+        }
+    }
+}
+
+impl Display for MemberExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}{}{}", self.object, self.operator(), self.property)
+    }
+}
+
+impl PartialEq for MemberExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.is_optional() == other.is_optional()
+            && self.object == other.object
+            && self.property == other.property
+    }
+}
+
+impl NodeTrait for MemberExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for MemberExpression {
+    fn expression_node(&self) {}
+}
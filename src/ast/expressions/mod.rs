@@ -1,9 +1,33 @@
+mod array_expression;
+mod boolean_expression;
+mod call_expression;
+mod float_expression;
+mod function_expression;
 mod ident_expression;
+mod if_expression;
 mod infix_expression;
 mod integer_expression;
+mod member_expression;
+mod pipe_expression;
+mod placeholder_expression;
 mod prefix_expression;
+mod set_expression;
+mod spread_expression;
+mod try_expression;
 
+pub use array_expression::ArrayLiteral;
+pub use boolean_expression::BooleanLiteral;
+pub use call_expression::{CallExpression, NamedArgument};
+pub use float_expression::FloatLiteral;
+pub use function_expression::FunctionLiteral;
 pub use ident_expression::IdentExpression;
+pub use if_expression::IfExpression;
 pub use infix_expression::InfixExpression;
 pub use integer_expression::IntegerLiteral;
+pub use member_expression::MemberExpression;
+pub use pipe_expression::{PipeExpression, PipedCall};
+pub use placeholder_expression::PlaceholderExpression;
 pub use prefix_expression::PrefixExpression;
+pub use set_expression::SetLiteral;
+pub use spread_expression::SpreadExpression;
+pub use try_expression::TryExpression;
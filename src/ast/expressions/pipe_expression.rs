@@ -0,0 +1,84 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt::Display;
+
+use crate::{
+    ast::{expressions::NamedArgument, Expression, ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+/// `<left> |> <right>`, calling `right` with `left` as its first argument,
+/// e.g. `x |> f` for `f(x)`. When `right` is a call written without
+/// parentheses around it, `left` goes before its arguments instead, so
+/// `x |> g(1)` is `g(x, 1)` while `x |> (g(1))` is `g(1)(x)`.
+#[derive(Debug)]
+pub struct PipeExpression {
+    /// The `|>` token
+    pub token: Token,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Whether `left` goes before the arguments of `right`, a call not
+    /// wrapped in parentheses
+    pub splices: bool,
+}
+
+/// The call a [`PipeExpression`] stands for, borrowing its parts.
+#[derive(Debug)]
+pub struct PipedCall<'a> {
+    pub function: &'a Expression,
+    pub arguments: Vec<&'a Expression>,
+    pub named_arguments: &'a [NamedArgument],
+}
+
+impl PipeExpression {
+    /// Returns the call the pipe stands for, e.g. `g(x, 1)` for
+    /// `x |> g(1)`.
+    pub fn call(&self) -> PipedCall<'_> {
+        match self.right.as_ref() {
+            Expression::Call(call) if self.splices => PipedCall {
+                function: &call.function,
+                arguments: core::iter::once(self.left.as_ref())
+                    .chain(call.arguments.iter())
+                    .collect(),
+                named_arguments: &call.named_arguments,
+            },
+            function => PipedCall {
+                function,
+                arguments: vec![self.left.as_ref()],
+                named_arguments: &[],
+            },
+        }
+    }
+
+    /// Whether `right` has to be wrapped in parentheses to parse back into
+    /// the same pipe, being a call that doesn't take `left` as an argument.
+    pub fn parenthesizes_right(&self) -> bool {
+        !self.splices && matches!(self.right.as_ref(), Expression::Call(_))
+    }
+}
+
+impl Display for PipeExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.parenthesizes_right() {
+            write!(f, "({} |> ({}))", self.left, self.right)
+        } else {
+            write!(f, "({} |> {})", self.left, self.right)
+        }
+    }
+}
+
+impl PartialEq for PipeExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.left == other.left && self.right == other.right && self.splices == other.splices
+    }
+}
+
+impl NodeTrait for PipeExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for PipeExpression {
+    fn expression_node(&self) {}
+}
@@ -0,0 +1,36 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+/// The `_` in a call like `f(_, 2)`, marking an argument left out of a
+/// partial application.
+#[derive(Debug)]
+pub struct PlaceholderExpression {
+    pub token: Token,
+}
+
+impl Display for PlaceholderExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.token_literal())
+    }
+}
+
+impl PartialEq for PlaceholderExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl NodeTrait for PlaceholderExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for PlaceholderExpression {
+    fn expression_node(&self) {}
+}
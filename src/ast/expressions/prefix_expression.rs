@@ -1,10 +1,12 @@
-use std::fmt::Display;
+use alloc::{boxed::Box, string::String};
+use core::fmt::Display;
 
 use crate::{
     ast::{Expression, ExpressionTrait, NodeTrait},
     token::Token,
 };
 
+#[derive(Debug)]
 pub struct PrefixExpression {
     pub token: Token,
     pub operator: String,
@@ -12,11 +14,18 @@ pub struct PrefixExpression {
 }
 
 impl Display for PrefixExpression {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "({}{})", self.operator, self.right)
     }
 }
 
+impl PartialEq for PrefixExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.operator == other.operator && self.right == other.right
+    }
+}
+
 impl NodeTrait for PrefixExpression {
     fn token_literal(&self) -> &str {
         &self.token.literal
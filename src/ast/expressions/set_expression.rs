@@ -0,0 +1,44 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{Expression, ExpressionTrait, NodeTrait},
+    token::{Span, Token},
+};
+
+/// `#{<elements>}`, a set of unique values.
+#[derive(Debug)]
+pub struct SetLiteral {
+    /// The `#{` token
+    pub token: Token,
+    pub elements: Vec<Expression>,
+    /// The span from the `#{` to the closing brace
+    pub span: Span,
+}
+
+impl Display for SetLiteral {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let elements: Vec<String> = self.elements.iter().map(ToString::to_string).collect();
+        write!(f, "#{{{}}}", elements.join(", "))
+    }
+}
+
+impl PartialEq for SetLiteral {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl NodeTrait for SetLiteral {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for SetLiteral {
+    fn expression_node(&self) {}
+}
@@ -0,0 +1,39 @@
+use alloc::boxed::Box;
+use core::fmt::Display;
+
+use crate::{
+    ast::{Expression, ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+/// `...<value>`, expanding an array in place. Only valid as an argument
+/// of a call or an element of an array literal.
+#[derive(Debug)]
+pub struct SpreadExpression {
+    /// The `...` token
+    pub token: Token,
+    pub value: Box<Expression>,
+}
+
+impl Display for SpreadExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}{}", self.token_literal(), self.value)
+    }
+}
+
+impl PartialEq for SpreadExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl NodeTrait for SpreadExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for SpreadExpression {
+    fn expression_node(&self) {}
+}
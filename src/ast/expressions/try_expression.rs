@@ -0,0 +1,39 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{expressions::IdentExpression, statements::BlockStatement, ExpressionTrait, NodeTrait},
+    token::Token,
+};
+
+/// `try { <body> } catch (<param>) { <handler> }`, where `param` is bound
+/// to the thrown value while running `handler`.
+#[derive(Debug)]
+pub struct TryExpression {
+    pub token: Token,
+    pub body: BlockStatement,
+    pub param: IdentExpression,
+    pub handler: BlockStatement,
+}
+
+impl Display for TryExpression {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "try {}catch({}) {}", self.body, self.param, self.handler)
+    }
+}
+
+impl PartialEq for TryExpression {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.body == other.body && self.param == other.param && self.handler == other.handler
+    }
+}
+
+impl NodeTrait for TryExpression {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl ExpressionTrait for TryExpression {
+    fn expression_node(&self) {}
+}
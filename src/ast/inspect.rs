@@ -0,0 +1,408 @@
+//! A uniform view of the nodes of the AST, for tools walking the tree one
+//! node at a time, such as the `:inspect` command of the REPL.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    ast::{
+        expressions::{IdentExpression, NamedArgument},
+        patterns::Pattern,
+        statements::{BlockStatement, EnumVariant, SwitchCase},
+        Expression, Program, Statement,
+    },
+    token::{Span, Token},
+};
+
+/// A reference to a node of any type.
+#[derive(Debug, Clone, Copy)]
+pub enum Node<'a> {
+    Program(&'a Program),
+    Statement(&'a Statement),
+    Expression(&'a Expression),
+    Block(&'a BlockStatement),
+    Pattern(&'a Pattern),
+    /// A name that isn't an expression, e.g. the name bound by a `let`
+    Ident(&'a IdentExpression),
+    SwitchCase(&'a SwitchCase),
+    EnumVariant(&'a EnumVariant),
+    NamedArgument(&'a NamedArgument),
+}
+
+impl<'a> Node<'a> {
+    /// Returns the name of the type of the node, e.g. `InfixExpression`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Node::Program(_) => "Program",
+            Node::Statement(stmt) => match stmt {
+                Statement::Let(_) => "LetStatement",
+                Statement::Return(_) => "ReturnStatement",
+                Statement::Throw(_) => "ThrowStatement",
+                Statement::Defer(_) => "DeferStatement",
+                Statement::Switch(_) => "SwitchStatement",
+                Statement::DoWhile(_) => "DoWhileStatement",
+                Statement::ForIn(_) => "ForInStatement",
+                Statement::Enum(_) => "EnumStatement",
+                Statement::Expression(_) => "ExpressionStatement",
+            },
+            Node::Expression(expression) => match expression {
+                Expression::Ident(_) => "Ident",
+                Expression::Integer(_) => "Integer",
+                Expression::Float(_) => "Float",
+                Expression::Boolean(_) => "Boolean",
+                Expression::Prefix(_) => "PrefixExpression",
+                Expression::Infix(_) => "InfixExpression",
+                Expression::If(_) => "IfExpression",
+                Expression::Try(_) => "TryExpression",
+                Expression::Function(_) => "FunctionLiteral",
+                Expression::Call(_) => "CallExpression",
+                Expression::Pipe(_) => "PipeExpression",
+                Expression::Placeholder(_) => "Placeholder",
+                Expression::Set(_) => "SetLiteral",
+                Expression::Member(_) => "MemberExpression",
+                Expression::Array(_) => "ArrayLiteral",
+                Expression::Spread(_) => "SpreadExpression",
+            },
+            Node::Block(_) => "BlockStatement",
+            Node::Pattern(pattern) => match pattern {
+                Pattern::Ident(_) => "Ident",
+                Pattern::Array(_) => "ArrayPattern",
+                Pattern::Hash(_) => "HashPattern",
+            },
+            Node::Ident(_) => "Ident",
+            Node::SwitchCase(_) => "SwitchCase",
+            Node::EnumVariant(_) => "EnumVariant",
+            Node::NamedArgument(_) => "NamedArgument",
+        }
+    }
+
+    /// Returns the token the parser made the node from, e.g. the operator
+    /// of an infix expression. Programs have none.
+    pub fn token(&self) -> Option<&'a Token> {
+        let token = match self {
+            Node::Program(_) => return None,
+            Node::Statement(stmt) => match stmt {
+                Statement::Let(s) => &s.token,
+                Statement::Return(s) => &s.token,
+                Statement::Throw(s) => &s.token,
+                Statement::Defer(s) => &s.token,
+                Statement::Switch(s) => &s.token,
+                Statement::DoWhile(s) => &s.token,
+                Statement::ForIn(s) => &s.token,
+                Statement::Enum(s) => &s.token,
+                Statement::Expression(s) => &s.token,
+            },
+            Node::Expression(expression) => match expression {
+                Expression::Ident(e) => &e.token,
+                Expression::Integer(e) => &e.token,
+                Expression::Float(e) => &e.token,
+                Expression::Boolean(e) => &e.token,
+                Expression::Prefix(e) => &e.token,
+                Expression::Infix(e) => &e.token,
+                Expression::If(e) => &e.token,
+                Expression::Try(e) => &e.token,
+                Expression::Function(e) => &e.token,
+                Expression::Call(e) => &e.token,
+                Expression::Pipe(e) => &e.token,
+                Expression::Placeholder(e) => &e.token,
+                Expression::Set(e) => &e.token,
+                Expression::Member(e) => &e.token,
+                Expression::Array(e) => &e.token,
+                Expression::Spread(e) => &e.token,
+            },
+            Node::Block(block) => &block.token,
+            Node::Pattern(pattern) => match pattern {
+                Pattern::Ident(p) => &p.token,
+                Pattern::Array(p) => &p.token,
+                Pattern::Hash(p) => &p.token,
+            },
+            Node::Ident(ident) => &ident.token,
+            Node::SwitchCase(case) => &case.token,
+            Node::EnumVariant(variant) => &variant.name.token,
+            Node::NamedArgument(argument) => &argument.name.token,
+        };
+        Some(token)
+    }
+
+    /// Returns the source span of the node. The span of a program goes from
+    /// its first statement to its last one.
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Program(program) => match program.statements.as_slice() {
+                [first, .., last] | [first @ last] => first.span().to(last.span()),
+                [] => Span::default(),
+            },
+            Node::Statement(stmt) => stmt.span(),
+            Node::Expression(expression) => expression.span(),
+            Node::Block(block) => block.span,
+            Node::Pattern(pattern) => pattern.span(),
+            Node::Ident(ident) => ident.token.span,
+            Node::SwitchCase(case) => case.token.span.to(case.body.span),
+            Node::EnumVariant(variant) => {
+                let end = variant.fields.last().unwrap_or(&variant.name);
+                variant.name.token.span.to(end.token.span)
+            }
+            Node::NamedArgument(argument) => argument.name.token.span.to(argument.value.span()),
+        }
+    }
+
+    /// Prints the node as Monkey source code. Statements, expressions and
+    /// blocks are pretty-printed, see
+    /// [`Program::to_pretty_string`](crate::ast::Program::to_pretty_string).
+    pub fn to_pretty_string(&self) -> String {
+        match self {
+            Node::Program(program) => program.to_pretty_string(),
+            Node::Statement(stmt) => stmt.to_pretty_string(),
+            Node::Expression(expression) => expression.to_pretty_string(),
+            Node::Block(block) => block.to_pretty_string(),
+            Node::Pattern(pattern) => pattern.to_string(),
+            Node::Ident(ident) => ident.value.clone(),
+            Node::SwitchCase(case) => case.to_string(),
+            Node::EnumVariant(variant) => variant.to_string(),
+            Node::NamedArgument(argument) => {
+                let value = argument.value.to_pretty_string();
+                format!("{}: {value}", argument.name.value)
+            }
+        }
+    }
+
+    /// Returns the children of the node in source order, each with the
+    /// name of the field holding it. The items of a list all have the name
+    /// of the list, e.g. `arguments`.
+    pub fn children(&self) -> Vec<(&'static str, Node<'a>)> {
+        let statements = |name, statements: &'a [Statement]| {
+            statements.iter().map(move |s| (name, Node::Statement(s)))
+        };
+        let expressions = |name, expressions: &'a [Expression]| {
+            expressions.iter().map(move |e| (name, Node::Expression(e)))
+        };
+        let idents = |name, idents: &'a [IdentExpression]| {
+            idents.iter().map(move |i| (name, Node::Ident(i)))
+        };
+
+        match self {
+            Node::Program(program) => statements("statements", &program.statements).collect(),
+            Node::Statement(stmt) => match stmt {
+                Statement::Let(s) => vec![
+                    ("name", Node::Ident(&s.name)),
+                    ("value", Node::Expression(&s.value)),
+                ],
+                Statement::Return(s) => vec![("value", Node::Expression(&s.value))],
+                Statement::Throw(s) => vec![("value", Node::Expression(&s.value))],
+                Statement::Defer(s) => vec![("value", Node::Expression(&s.value))],
+                Statement::Switch(s) => {
+                    let mut children = vec![("subject", Node::Expression(&s.subject))];
+                    children.extend(s.cases.iter().map(|case| ("cases", Node::SwitchCase(case))));
+                    children.extend(
+                        s.default
+                            .iter()
+                            .map(|block| ("default", Node::Block(block))),
+                    );
+                    children
+                }
+                Statement::DoWhile(s) => vec![
+                    ("body", Node::Block(&s.body)),
+                    ("condition", Node::Expression(&s.condition)),
+                ],
+                Statement::ForIn(s) => {
+                    let mut children: Vec<_> = idents("bindings", &s.bindings).collect();
+                    children.push(("iterable", Node::Expression(&s.iterable)));
+                    children.push(("body", Node::Block(&s.body)));
+                    children
+                }
+                Statement::Enum(s) => {
+                    let mut children = vec![("name", Node::Ident(&s.name))];
+                    children.extend(
+                        s.variants
+                            .iter()
+                            .map(|variant| ("variants", Node::EnumVariant(variant))),
+                    );
+                    children
+                }
+                Statement::Expression(s) => vec![("expression", Node::Expression(&s.expression))],
+            },
+            Node::Expression(expression) => match expression {
+                Expression::Ident(_)
+                | Expression::Integer(_)
+                | Expression::Float(_)
+                | Expression::Boolean(_)
+                | Expression::Placeholder(_) => Vec::new(),
+                Expression::Prefix(e) => vec![("right", Node::Expression(&e.right))],
+                Expression::Infix(e) => vec![
+                    ("left", Node::Expression(&e.left)),
+                    ("right", Node::Expression(&e.right)),
+                ],
+                Expression::Pipe(e) => vec![
+                    ("left", Node::Expression(&e.left)),
+                    ("right", Node::Expression(&e.right)),
+                ],
+                Expression::If(e) => {
+                    let mut children = vec![
+                        ("condition", Node::Expression(&e.condition)),
+                        ("consequence", Node::Block(&e.consequence)),
+                    ];
+                    children.extend(
+                        e.alternative
+                            .iter()
+                            .map(|block| ("alternative", Node::Block(block))),
+                    );
+                    children
+                }
+                Expression::Try(e) => vec![
+                    ("body", Node::Block(&e.body)),
+                    ("param", Node::Ident(&e.param)),
+                    ("handler", Node::Block(&e.handler)),
+                ],
+                Expression::Function(e) => {
+                    let mut children: Vec<_> = e
+                        .parameters
+                        .iter()
+                        .map(|pattern| ("parameters", Node::Pattern(pattern)))
+                        .collect();
+                    children.extend(e.rest.iter().map(|rest| ("rest", Node::Ident(rest))));
+                    children.push(("body", Node::Block(&e.body)));
+                    children
+                }
+                Expression::Call(e) => {
+                    let mut children = vec![("function", Node::Expression(&e.function))];
+                    children.extend(expressions("arguments", &e.arguments));
+                    children.extend(
+                        e.named_arguments
+                            .iter()
+                            .map(|argument| ("named_arguments", Node::NamedArgument(argument))),
+                    );
+                    children
+                }
+                Expression::Set(e) => expressions("elements", &e.elements).collect(),
+                Expression::Member(e) => vec![
+                    ("object", Node::Expression(&e.object)),
+                    ("property", Node::Ident(&e.property)),
+                ],
+                Expression::Array(e) => expressions("elements", &e.elements).collect(),
+                Expression::Spread(e) => vec![("value", Node::Expression(&e.value))],
+            },
+            Node::Block(block) => statements("statements", &block.statements).collect(),
+            Node::Pattern(pattern) => match pattern {
+                Pattern::Ident(_) => Vec::new(),
+                Pattern::Array(p) => p
+                    .elements
+                    .iter()
+                    .map(|element| ("elements", Node::Pattern(element)))
+                    .collect(),
+                Pattern::Hash(p) => idents("keys", &p.keys).collect(),
+            },
+            Node::Ident(_) => Vec::new(),
+            Node::SwitchCase(case) => {
+                let mut children: Vec<_> = expressions("values", &case.values).collect();
+                children.push(("body", Node::Block(&case.body)));
+                children
+            }
+            Node::EnumVariant(variant) => {
+                let mut children = vec![("name", Node::Ident(&variant.name))];
+                children.extend(idents("fields", &variant.fields));
+                children
+            }
+            Node::NamedArgument(argument) => vec![
+                ("name", Node::Ident(&argument.name)),
+                ("value", Node::Expression(&argument.value)),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    /// Returns the field, kind and token literal of each child of `node`.
+    fn children<'a>(node: &Node<'a>) -> Vec<(&'static str, &'static str, &'a str)> {
+        node.children()
+            .iter()
+            .map(|(field, child)| {
+                (
+                    *field,
+                    child.kind(),
+                    child.token().unwrap().literal.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_node() {
+        let source = "let x = f(1 + 2 * 3, [a], by: 2);";
+        let program = Parser::parse(source).unwrap();
+        let root = Node::Program(&program);
+        assert_eq!(root.kind(), "Program");
+        assert!(root.token().is_none());
+        assert_eq!(root.span(), Span::new(0, source.len() - 1));
+
+        let (_, let_stmt) = root.children()[0];
+        assert_eq!(
+            children(&let_stmt),
+            vec![("name", "Ident", "x"), ("value", "CallExpression", "(")]
+        );
+
+        let (_, call) = let_stmt.children()[1];
+        assert_eq!(
+            children(&call),
+            vec![
+                ("function", "Ident", "f"),
+                ("arguments", "InfixExpression", "+"),
+                ("arguments", "ArrayLiteral", "["),
+                ("named_arguments", "NamedArgument", "by"),
+            ]
+        );
+
+        // Pratt parsing binds `*` tighter than `+`
+        let (_, sum) = call.children()[1];
+        assert_eq!(sum.to_pretty_string(), "1 + 2 * 3");
+        assert_eq!(&source[sum.span().start..sum.span().end], "1 + 2 * 3");
+        let (_, product) = sum.children()[1];
+        assert_eq!(product.kind(), "InfixExpression");
+        assert_eq!(product.token().unwrap().literal, "*");
+        assert_eq!(product.to_pretty_string(), "2 * 3");
+
+        let (_, named) = call.children()[3];
+        assert_eq!(named.to_pretty_string(), "by: 2");
+        assert_eq!(&source[named.span().start..named.span().end], "by: 2");
+    }
+
+    #[test]
+    fn test_node_blocks() {
+        let source = "switch (x) { case 1: a default: fn([b], ...c) { b } }";
+        let program = Parser::parse(source).unwrap();
+        let (_, switch) = Node::Program(&program).children()[0];
+        assert_eq!(
+            children(&switch),
+            vec![
+                ("subject", "Ident", "x"),
+                ("cases", "SwitchCase", "case"),
+                ("default", "BlockStatement", ":"),
+            ]
+        );
+
+        let (_, case) = switch.children()[1];
+        assert_eq!(
+            children(&case),
+            vec![("values", "Integer", "1"), ("body", "BlockStatement", ":")]
+        );
+
+        let (_, default) = switch.children()[2];
+        let (_, stmt) = default.children()[0];
+        let (_, function) = stmt.children()[0];
+        assert_eq!(
+            children(&function),
+            vec![
+                ("parameters", "ArrayPattern", "["),
+                ("rest", "Ident", "c"),
+                ("body", "BlockStatement", "{"),
+            ]
+        );
+    }
+}
@@ -1,10 +1,31 @@
+mod comments;
+mod diff;
 pub mod expressions;
+mod inspect;
+pub mod patterns;
+pub(crate) mod printers;
 pub mod statements;
+pub mod visit;
 
-use std::fmt::Display;
+use alloc::vec::Vec;
+use core::fmt::Display;
 
-use expressions::{IdentExpression, InfixExpression, IntegerLiteral, PrefixExpression};
-use statements::{ExpressionStatement, LetStatement, ReturnStatement};
+use expressions::{
+    ArrayLiteral, BooleanLiteral, CallExpression, FloatLiteral, FunctionLiteral, IdentExpression,
+    IfExpression, InfixExpression, IntegerLiteral, MemberExpression, PipeExpression,
+    PlaceholderExpression, PrefixExpression, SetLiteral, SpreadExpression, TryExpression,
+};
+use statements::{
+    DeferStatement, DoWhileStatement, EnumStatement, ExpressionStatement, ForInStatement,
+    LetStatement, ReturnStatement, SwitchStatement, ThrowStatement,
+};
+
+pub use comments::Comments;
+pub use diff::{diff, Change};
+pub use inspect::Node;
+pub use printers::{BraceStyle, FormatConfig};
+
+use crate::token::Span;
 
 pub trait NodeTrait: Display {
     fn token_literal(&self) -> &str;
@@ -19,9 +40,16 @@ pub trait ExpressionTrait: NodeTrait {
     fn expression_node(&self);
 }
 
+#[derive(Debug, PartialEq)]
 pub enum Statement {
     Let(LetStatement),
     Return(ReturnStatement),
+    Throw(ThrowStatement),
+    Defer(DeferStatement),
+    Switch(SwitchStatement),
+    DoWhile(DoWhileStatement),
+    ForIn(ForInStatement),
+    Enum(EnumStatement),
     Expression(ExpressionStatement),
 }
 
@@ -31,55 +59,150 @@ impl Statement {
         match self {
             Let(s) => s.token_literal(),
             Return(s) => s.token_literal(),
+            Throw(s) => s.token_literal(),
+            Defer(s) => s.token_literal(),
+            Switch(s) => s.token_literal(),
+            DoWhile(s) => s.token_literal(),
+            ForIn(s) => s.token_literal(),
+            Enum(s) => s.token_literal(),
             Expression(s) => s.token_literal(),
         }
     }
+
+    /// Returns the source span of the statement, excluding the trailing
+    /// semicolon.
+    pub fn span(&self) -> Span {
+        use Statement::*;
+        match self {
+            Let(s) => s.token.span.to(s.value.span()),
+            Return(s) => s.token.span.to(s.value.span()),
+            Throw(s) => s.token.span.to(s.value.span()),
+            Defer(s) => s.token.span.to(s.value.span()),
+            Switch(s) => s.span,
+            DoWhile(s) => s.span,
+            ForIn(s) => s.token.span.to(s.body.span),
+            Enum(s) => s.span,
+            Expression(s) => s.expression.span(),
+        }
+    }
 }
 
 impl Display for Statement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use Statement::*;
         match self {
             Let(s) => write!(f, "{s}"),
             Return(s) => write!(f, "{s}"),
+            Throw(s) => write!(f, "{s}"),
+            Defer(s) => write!(f, "{s}"),
+            Switch(s) => write!(f, "{s}"),
+            DoWhile(s) => write!(f, "{s}"),
+            ForIn(s) => write!(f, "{s}"),
+            Enum(s) => write!(f, "{s}"),
             Expression(s) => write!(f, "{s}"),
         }
     }
 }
 
+#[derive(Debug, PartialEq)]
 pub enum Expression {
     Ident(IdentExpression),
     Integer(IntegerLiteral),
+    Float(FloatLiteral),
+    Boolean(BooleanLiteral),
     Prefix(PrefixExpression),
     Infix(InfixExpression),
+    If(IfExpression),
+    Try(TryExpression),
+    Function(FunctionLiteral),
+    Call(CallExpression),
+    Pipe(PipeExpression),
+    Placeholder(PlaceholderExpression),
+    Set(SetLiteral),
+    Member(MemberExpression),
+    Array(ArrayLiteral),
+    Spread(SpreadExpression),
+}
+
+impl Expression {
+    /// Returns the source span of the expression. Parentheses around it
+    /// aren't part of the tree, so they're not included.
+    pub fn span(&self) -> Span {
+        use Expression::*;
+        match self {
+            Ident(e) => e.token.span,
+            Integer(e) => e.token.span,
+            Float(e) => e.token.span,
+            Boolean(e) => e.token.span,
+            Prefix(e) => e.token.span.to(e.right.span()),
+            Infix(e) => e.left.span().to(e.right.span()),
+            If(e) => {
+                let end = e.alternative.as_ref().unwrap_or(&e.consequence).span;
+                e.token.span.to(end)
+            }
+            Try(e) => e.token.span.to(e.handler.span),
+            Function(e) => e.token.span.to(e.body.span),
+            Call(e) => e.span,
+            Pipe(e) => e.left.span().to(e.right.span()),
+            Placeholder(e) => e.token.span,
+            Set(e) => e.span,
+            Member(e) => e.object.span().to(e.property.token.span),
+            Array(e) => e.span,
+            Spread(e) => e.token.span.to(e.value.span()),
+        }
+    }
 }
 
 impl Display for Expression {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use Expression::*;
         match self {
             Ident(e) => write!(f, "{e}"),
             Integer(e) => write!(f, "{e}"),
+            Float(e) => write!(f, "{e}"),
+            Boolean(e) => write!(f, "{e}"),
             Prefix(e) => write!(f, "{e}"),
             Infix(e) => write!(f, "{e}"),
+            If(e) => write!(f, "{e}"),
+            Try(e) => write!(f, "{e}"),
+            Function(e) => write!(f, "{e}"),
+            Call(e) => write!(f, "{e}"),
+            Pipe(e) => write!(f, "{e}"),
+            Placeholder(e) => write!(f, "{e}"),
+            Set(e) => write!(f, "{e}"),
+            Member(e) => write!(f, "{e}"),
+            Array(e) => write!(f, "{e}"),
+            Spread(e) => write!(f, "{e}"),
         }
     }
 }
 
+#[derive(Debug, Default)]
 pub struct Program {
     pub statements: Vec<Statement>,
+    /// Only filled when parsing with
+    /// [`Parser::keep_comments`](crate::parser::Parser::keep_comments)
+    pub comments: Comments,
 }
 
 impl Program {
     pub fn new() -> Self {
         Self {
             statements: Vec::new(),
+            comments: Comments::default(),
         }
     }
 }
 
+impl PartialEq for Program {
+    /// Compares the statements of the programs, ignoring their comments.
+    fn eq(&self, other: &Self) -> bool {
+        self.statements == other.statements
+    }
+}
+
 impl Display for Program {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for stmt in self.statements.iter() {
             write!(f, "{}", stmt)?;
         }
@@ -98,6 +221,7 @@ impl NodeTrait for Program {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::token::{Token, TokenType};
 
@@ -106,27 +230,21 @@ mod tests {
     #[test]
     fn test_to_string() {
         let statements: Vec<Statement> = vec![Statement::Let(LetStatement {
-            token: Token {
-                token_type: TokenType::Let,
-                literal: "let".to_string(),
-            },
+            token: Token::new(TokenType::Let, "let".to_string()),
             name: IdentExpression {
-                token: Token {
-                    token_type: TokenType::Ident,
-                    literal: "myVar".to_string(),
-                },
+                token: Token::new(TokenType::Ident, "myVar".to_string()),
                 value: "myVar".to_string(),
             },
             value: Expression::Ident(IdentExpression {
-                token: Token {
-                    token_type: TokenType::Ident,
-                    literal: "anotherVar".to_string(),
-                },
+                token: Token::new(TokenType::Ident, "anotherVar".to_string()),
                 value: "anotherVar".to_string(),
             }),
         })];
 
-        let program = Program { statements };
+        let program = Program {
+            statements,
+            ..Program::default()
+        };
         assert_eq!(program.to_string(), "let myVar = anotherVar;");
     }
 }
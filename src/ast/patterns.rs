@@ -0,0 +1,82 @@
+//! Patterns bind the parts of a value to names, e.g. the `[a, b]` and
+//! `{x, y}` parameters of `fn([a, b], {x, y}) { ... }`.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::expressions::IdentExpression,
+    token::{Span, Token},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Pattern {
+    /// Binds the whole value
+    Ident(IdentExpression),
+    Array(ArrayPattern),
+    Hash(HashPattern),
+}
+
+/// `[<pattern>, <pattern>]`, binding the elements of an array in order.
+#[derive(Debug)]
+pub struct ArrayPattern {
+    /// The `[` token
+    pub token: Token,
+    pub elements: Vec<Pattern>,
+    /// The span from the `[` to the `]`
+    pub span: Span,
+}
+
+/// `{<key>, <key>}`, binding the values of a hash to names equal to
+/// their keys.
+#[derive(Debug)]
+pub struct HashPattern {
+    /// The `{` token
+    pub token: Token,
+    pub keys: Vec<IdentExpression>,
+    /// The span from the `{` to the `}`
+    pub span: Span,
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Ident(p) => p.token.span,
+            Pattern::Array(p) => p.span,
+            Pattern::Hash(p) => p.span,
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Pattern::Ident(p) => write!(f, "{p}"),
+            Pattern::Array(p) => {
+                let elements: Vec<String> = p.elements.iter().map(ToString::to_string).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Pattern::Hash(p) => {
+                let keys: Vec<String> = p.keys.iter().map(ToString::to_string).collect();
+                write!(f, "{{{}}}", keys.join(", "))
+            }
+        }
+    }
+}
+
+impl PartialEq for ArrayPattern {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.elements == other.elements
+    }
+}
+
+impl PartialEq for HashPattern {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.keys == other.keys
+    }
+}
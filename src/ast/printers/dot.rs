@@ -0,0 +1,318 @@
+use alloc::{format, string::String};
+use core::fmt::Write;
+
+use crate::ast::{patterns::Pattern, statements::BlockStatement, Expression, Program, Statement};
+
+/// Accumulates the nodes and edges of a Graphviz graph, handing out
+/// sequential node ids.
+struct DotGraph {
+    body: String,
+    next_id: usize,
+}
+
+impl DotGraph {
+    fn add_node(&mut self, label: &str) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        writeln!(self.body, "  node{id} [label=\"{}\"];", escape(label)).unwrap();
+        id
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, label: &str) {
+        if label.is_empty() {
+            writeln!(self.body, "  node{from} -> node{to};").unwrap();
+        } else {
+            writeln!(self.body, "  node{from} -> node{to} [label=\"{label}\"];").unwrap();
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) -> usize {
+        match stmt {
+            Statement::Let(s) => {
+                let id = self.add_node(&format!("LetStatement\\n{}", s.keyword()));
+                let name = self.add_node(&format!("Identifier\\n{}", s.name.value));
+                self.add_edge(id, name, "name");
+                let value = self.expression(&s.value);
+                self.add_edge(id, value, "value");
+                id
+            }
+            Statement::Return(s) => {
+                let id = self.add_node("ReturnStatement");
+                let value = self.expression(&s.value);
+                self.add_edge(id, value, "value");
+                id
+            }
+            Statement::Throw(s) => {
+                let id = self.add_node("ThrowStatement");
+                let value = self.expression(&s.value);
+                self.add_edge(id, value, "value");
+                id
+            }
+            Statement::Defer(s) => {
+                let id = self.add_node("DeferStatement");
+                let value = self.expression(&s.value);
+                self.add_edge(id, value, "value");
+                id
+            }
+            Statement::Switch(s) => {
+                let id = self.add_node("SwitchStatement");
+                let subject = self.expression(&s.subject);
+                self.add_edge(id, subject, "subject");
+                for case in s.cases.iter() {
+                    let case_id = self.add_node("SwitchCase");
+                    self.add_edge(id, case_id, "case");
+                    for value in case.values.iter() {
+                        let value = self.expression(value);
+                        self.add_edge(case_id, value, "value");
+                    }
+                    let body = self.block(&case.body);
+                    self.add_edge(case_id, body, "body");
+                }
+                if let Some(default) = &s.default {
+                    let default = self.block(default);
+                    self.add_edge(id, default, "default");
+                }
+                id
+            }
+            Statement::DoWhile(s) => {
+                let id = self.add_node("DoWhileStatement");
+                let body = self.block(&s.body);
+                self.add_edge(id, body, "body");
+                let condition = self.expression(&s.condition);
+                self.add_edge(id, condition, "condition");
+                id
+            }
+            Statement::ForIn(s) => {
+                let id = self.add_node("ForInStatement");
+                for binding in s.bindings.iter() {
+                    let binding = self.add_node(&format!("Identifier\\n{}", binding.value));
+                    self.add_edge(id, binding, "binding");
+                }
+                let iterable = self.expression(&s.iterable);
+                self.add_edge(id, iterable, "iterable");
+                let body = self.block(&s.body);
+                self.add_edge(id, body, "body");
+                id
+            }
+            Statement::Enum(s) => {
+                let id = self.add_node(&format!("EnumStatement\\n{}", s.name.value));
+                for variant in s.variants.iter() {
+                    let variant_id =
+                        self.add_node(&format!("EnumVariant\\n{}", variant.name.value));
+                    self.add_edge(id, variant_id, "variant");
+                    for field in variant.fields.iter() {
+                        let field = self.add_node(&format!("Identifier\\n{}", field.value));
+                        self.add_edge(variant_id, field, "field");
+                    }
+                }
+                id
+            }
+            Statement::Expression(s) => {
+                let id = self.add_node("ExpressionStatement");
+                let expression = self.expression(&s.expression);
+                self.add_edge(id, expression, "");
+                id
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) -> usize {
+        match expression {
+            Expression::Ident(e) => self.add_node(&format!("Identifier\\n{}", e.value)),
+            Expression::Integer(e) => self.add_node(&format!("IntegerLiteral\\n{}", e.value)),
+            Expression::Float(e) => self.add_node(&format!("FloatLiteral\\n{e}")),
+            Expression::Placeholder(_) => self.add_node("Placeholder"),
+            Expression::Boolean(e) => self.add_node(&format!("BooleanLiteral\\n{}", e.value)),
+            Expression::Prefix(e) => {
+                let id = self.add_node(&format!("PrefixExpression\\n{}", e.operator));
+                let right = self.expression(&e.right);
+                self.add_edge(id, right, "right");
+                id
+            }
+            Expression::Infix(e) => {
+                let id = self.add_node(&format!("InfixExpression\\n{}", e.operator));
+                let left = self.expression(&e.left);
+                self.add_edge(id, left, "left");
+                let right = self.expression(&e.right);
+                self.add_edge(id, right, "right");
+                id
+            }
+            Expression::Pipe(e) => {
+                let id = self.add_node(if e.splices {
+                    "PipeExpression\\nsplices"
+                } else {
+                    "PipeExpression"
+                });
+                let left = self.expression(&e.left);
+                self.add_edge(id, left, "left");
+                let right = self.expression(&e.right);
+                self.add_edge(id, right, "right");
+                id
+            }
+            Expression::If(e) => {
+                let id = self.add_node("IfExpression");
+                let condition = self.expression(&e.condition);
+                self.add_edge(id, condition, "condition");
+                let consequence = self.block(&e.consequence);
+                self.add_edge(id, consequence, "consequence");
+                if let Some(alternative) = &e.alternative {
+                    let alternative = self.block(alternative);
+                    self.add_edge(id, alternative, "alternative");
+                }
+                id
+            }
+            Expression::Member(e) => {
+                let id = self.add_node(&format!(
+                    "MemberExpression\\n{}{}",
+                    e.operator(),
+                    e.property.value
+                ));
+                let object = self.expression(&e.object);
+                self.add_edge(id, object, "object");
+                id
+            }
+            Expression::Set(e) => {
+                let id = self.add_node("SetLiteral");
+                for element in e.elements.iter() {
+                    let element = self.expression(element);
+                    self.add_edge(id, element, "");
+                }
+                id
+            }
+            Expression::Array(e) => {
+                let id = self.add_node("ArrayLiteral");
+                for element in e.elements.iter() {
+                    let element = self.expression(element);
+                    self.add_edge(id, element, "");
+                }
+                id
+            }
+            Expression::Spread(e) => {
+                let id = self.add_node("SpreadExpression");
+                let value = self.expression(&e.value);
+                self.add_edge(id, value, "value");
+                id
+            }
+            Expression::Call(e) => {
+                let id = self.add_node("CallExpression");
+                let function = self.expression(&e.function);
+                self.add_edge(id, function, "function");
+                for argument in e.arguments.iter() {
+                    let argument = self.expression(argument);
+                    self.add_edge(id, argument, "argument");
+                }
+                for argument in e.named_arguments.iter() {
+                    let value = self.expression(&argument.value);
+                    self.add_edge(id, value, &argument.name.value);
+                }
+                id
+            }
+            Expression::Function(e) => {
+                let id = self.add_node("FunctionLiteral");
+                for parameter in e.parameters.iter() {
+                    let parameter = self.pattern(parameter);
+                    self.add_edge(id, parameter, "parameter");
+                }
+                if let Some(rest) = &e.rest {
+                    let rest = self.add_node(&format!("Identifier\\n{}", rest.value));
+                    self.add_edge(id, rest, "rest");
+                }
+                let body = self.block(&e.body);
+                self.add_edge(id, body, "body");
+                id
+            }
+            Expression::Try(e) => {
+                let id = self.add_node("TryExpression");
+                let body = self.block(&e.body);
+                self.add_edge(id, body, "body");
+                let param = self.add_node(&format!("Identifier\\n{}", e.param.value));
+                self.add_edge(id, param, "param");
+                let handler = self.block(&e.handler);
+                self.add_edge(id, handler, "handler");
+                id
+            }
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) -> usize {
+        match pattern {
+            Pattern::Ident(p) => self.add_node(&format!("Identifier\\n{}", p.value)),
+            Pattern::Array(p) => {
+                let id = self.add_node("ArrayPattern");
+                for element in p.elements.iter() {
+                    let element = self.pattern(element);
+                    self.add_edge(id, element, "");
+                }
+                id
+            }
+            Pattern::Hash(p) => {
+                let id = self.add_node("HashPattern");
+                for key in p.keys.iter() {
+                    let key = self.add_node(&format!("Identifier\\n{}", key.value));
+                    self.add_edge(id, key, "key");
+                }
+                id
+            }
+        }
+    }
+
+    fn block(&mut self, block: &BlockStatement) -> usize {
+        let id = self.add_node("BlockStatement");
+        for stmt in block.statements.iter() {
+            let child = self.statement(stmt);
+            self.add_edge(id, child, "");
+        }
+        id
+    }
+}
+
+/// Escapes double quotes in a label. Backslashes are left untouched so
+/// labels can use Graphviz escapes such as `\n`.
+fn escape(label: &str) -> String {
+    label.replace('"', "\\\"")
+}
+
+impl Program {
+    /// Returns the program as a Graphviz `digraph`, which can be rendered
+    /// with e.g. `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph {
+            body: String::new(),
+            next_id: 0,
+        };
+
+        let root = graph.add_node("Program");
+        for stmt in self.statements.iter() {
+            let id = graph.statement(stmt);
+            graph.add_edge(root, id, "");
+        }
+
+        format!("digraph ast {{\n{}}}\n", graph.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, parser::Parser};
+
+    #[test]
+    fn test_to_dot() {
+        let mut parser = Parser::new(Lexer::new("a + 1"));
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty());
+
+        let expected = r#"digraph ast {
+  node0 [label="Program"];
+  node1 [label="ExpressionStatement"];
+  node2 [label="InfixExpression\n+"];
+  node3 [label="Identifier\na"];
+  node2 -> node3 [label="left"];
+  node4 [label="IntegerLiteral\n1"];
+  node2 -> node4 [label="right"];
+  node1 -> node2;
+  node0 -> node1;
+}
+"#;
+        assert_eq!(program.to_dot(), expected);
+    }
+}
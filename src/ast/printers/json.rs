@@ -0,0 +1,398 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use crate::ast::{
+    expressions::IdentExpression, patterns::Pattern, statements::BlockStatement, Expression,
+    Program, Statement,
+};
+
+/// Minimal JSON value, enough to serialize the AST without pulling in
+/// a serialization library.
+enum Json {
+    Null,
+    Bool(bool),
+    String(String),
+    Number(i64),
+    Float(f64),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    fn node(node_type: &str, fields: Vec<(&'static str, Json)>) -> Json {
+        let mut object = vec![("type", Json::String(node_type.to_string()))];
+        object.extend(fields);
+        Json::Object(object)
+    }
+
+    /// Writes the value indented by `indent` levels of two spaces.
+    fn write(&self, out: &mut String, indent: usize) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => write!(out, "{b}").unwrap(),
+            Json::String(s) => write_string(out, s),
+            Json::Number(n) => write!(out, "{n}").unwrap(),
+            Json::Float(n) => write!(out, "{n:?}").unwrap(),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    item.write(out, indent + 1);
+                }
+                newline(out, indent);
+                out.push(']');
+            }
+            Json::Object(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    newline(out, indent + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                }
+                newline(out, indent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn newline(out: &mut String, indent: usize) {
+    out.push('\n');
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+pub(crate) fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl Program {
+    /// Serializes the program as an indented JSON document. Every node is
+    /// an object with a `type` field naming the node.
+    pub fn to_json(&self) -> String {
+        let statements = self.statements.iter().map(statement_json).collect();
+        let program = Json::node("Program", vec![("statements", Json::Array(statements))]);
+
+        let mut out = String::new();
+        program.write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+}
+
+fn statement_json(stmt: &Statement) -> Json {
+    match stmt {
+        Statement::Let(s) => Json::node(
+            "LetStatement",
+            vec![
+                ("kind", Json::String(s.keyword().to_string())),
+                ("name", ident_json(&s.name)),
+                ("value", expression_json(&s.value)),
+            ],
+        ),
+        Statement::Return(s) => Json::node(
+            "ReturnStatement",
+            vec![("value", expression_json(&s.value))],
+        ),
+        Statement::Throw(s) => {
+            Json::node("ThrowStatement", vec![("value", expression_json(&s.value))])
+        }
+        Statement::Defer(s) => {
+            Json::node("DeferStatement", vec![("value", expression_json(&s.value))])
+        }
+        Statement::Switch(s) => Json::node(
+            "SwitchStatement",
+            vec![
+                ("subject", expression_json(&s.subject)),
+                (
+                    "cases",
+                    Json::Array(
+                        s.cases
+                            .iter()
+                            .map(|case| {
+                                Json::node(
+                                    "SwitchCase",
+                                    vec![
+                                        (
+                                            "values",
+                                            Json::Array(
+                                                case.values.iter().map(expression_json).collect(),
+                                            ),
+                                        ),
+                                        ("body", block_json(&case.body)),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                    ),
+                ),
+                ("default", s.default.as_ref().map_or(Json::Null, block_json)),
+            ],
+        ),
+        Statement::DoWhile(s) => Json::node(
+            "DoWhileStatement",
+            vec![
+                ("body", block_json(&s.body)),
+                ("condition", expression_json(&s.condition)),
+            ],
+        ),
+        Statement::ForIn(s) => Json::node(
+            "ForInStatement",
+            vec![
+                (
+                    "bindings",
+                    Json::Array(s.bindings.iter().map(ident_json).collect()),
+                ),
+                ("iterable", expression_json(&s.iterable)),
+                ("body", block_json(&s.body)),
+            ],
+        ),
+        Statement::Enum(s) => Json::node(
+            "EnumStatement",
+            vec![
+                ("name", ident_json(&s.name)),
+                (
+                    "variants",
+                    Json::Array(
+                        s.variants
+                            .iter()
+                            .map(|variant| {
+                                Json::node(
+                                    "EnumVariant",
+                                    vec![
+                                        ("name", ident_json(&variant.name)),
+                                        (
+                                            "fields",
+                                            Json::Array(
+                                                variant.fields.iter().map(ident_json).collect(),
+                                            ),
+                                        ),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                    ),
+                ),
+            ],
+        ),
+        Statement::Expression(s) => Json::node(
+            "ExpressionStatement",
+            vec![("expression", expression_json(&s.expression))],
+        ),
+    }
+}
+
+fn expression_json(expression: &Expression) -> Json {
+    match expression {
+        Expression::Ident(e) => ident_json(e),
+        Expression::Integer(e) => {
+            Json::node("IntegerLiteral", vec![("value", Json::Number(e.value))])
+        }
+        Expression::Float(e) => Json::node("FloatLiteral", vec![("value", Json::Float(e.value))]),
+        Expression::Placeholder(_) => Json::node("Placeholder", vec![]),
+        Expression::Boolean(e) => {
+            Json::node("BooleanLiteral", vec![("value", Json::Bool(e.value))])
+        }
+        Expression::Prefix(e) => Json::node(
+            "PrefixExpression",
+            vec![
+                ("operator", Json::String(e.operator.clone())),
+                ("right", expression_json(&e.right)),
+            ],
+        ),
+        Expression::Infix(e) => Json::node(
+            "InfixExpression",
+            vec![
+                ("operator", Json::String(e.operator.clone())),
+                ("left", expression_json(&e.left)),
+                ("right", expression_json(&e.right)),
+            ],
+        ),
+        Expression::Pipe(e) => Json::node(
+            "PipeExpression",
+            vec![
+                ("left", expression_json(&e.left)),
+                ("right", expression_json(&e.right)),
+                ("splices", Json::Bool(e.splices)),
+            ],
+        ),
+        Expression::If(e) => Json::node(
+            "IfExpression",
+            vec![
+                ("condition", expression_json(&e.condition)),
+                ("consequence", block_json(&e.consequence)),
+                (
+                    "alternative",
+                    e.alternative.as_ref().map_or(Json::Null, block_json),
+                ),
+            ],
+        ),
+        Expression::Member(e) => Json::node(
+            "MemberExpression",
+            vec![
+                ("object", expression_json(&e.object)),
+                ("property", ident_json(&e.property)),
+                ("optional", Json::Bool(e.is_optional())),
+            ],
+        ),
+        Expression::Set(e) => Json::node(
+            "SetLiteral",
+            vec![(
+                "elements",
+                Json::Array(e.elements.iter().map(expression_json).collect()),
+            )],
+        ),
+        Expression::Array(e) => Json::node(
+            "ArrayLiteral",
+            vec![(
+                "elements",
+                Json::Array(e.elements.iter().map(expression_json).collect()),
+            )],
+        ),
+        Expression::Spread(e) => Json::node(
+            "SpreadExpression",
+            vec![("value", expression_json(&e.value))],
+        ),
+        Expression::Call(e) => Json::node(
+            "CallExpression",
+            vec![
+                ("function", expression_json(&e.function)),
+                (
+                    "arguments",
+                    Json::Array(e.arguments.iter().map(expression_json).collect()),
+                ),
+                (
+                    "namedArguments",
+                    Json::Array(
+                        e.named_arguments
+                            .iter()
+                            .map(|arg| {
+                                Json::node(
+                                    "NamedArgument",
+                                    vec![
+                                        ("name", ident_json(&arg.name)),
+                                        ("value", expression_json(&arg.value)),
+                                    ],
+                                )
+                            })
+                            .collect(),
+                    ),
+                ),
+            ],
+        ),
+        Expression::Function(e) => Json::node(
+            "FunctionLiteral",
+            vec![
+                (
+                    "parameters",
+                    Json::Array(e.parameters.iter().map(pattern_json).collect()),
+                ),
+                ("rest", e.rest.as_ref().map_or(Json::Null, ident_json)),
+                ("body", block_json(&e.body)),
+            ],
+        ),
+        Expression::Try(e) => Json::node(
+            "TryExpression",
+            vec![
+                ("body", block_json(&e.body)),
+                ("param", ident_json(&e.param)),
+                ("handler", block_json(&e.handler)),
+            ],
+        ),
+    }
+}
+
+fn block_json(block: &BlockStatement) -> Json {
+    let statements = block.statements.iter().map(statement_json).collect();
+    Json::node(
+        "BlockStatement",
+        vec![("statements", Json::Array(statements))],
+    )
+}
+
+fn pattern_json(pattern: &Pattern) -> Json {
+    match pattern {
+        Pattern::Ident(p) => ident_json(p),
+        Pattern::Array(p) => Json::node(
+            "ArrayPattern",
+            vec![(
+                "elements",
+                Json::Array(p.elements.iter().map(pattern_json).collect()),
+            )],
+        ),
+        Pattern::Hash(p) => Json::node(
+            "HashPattern",
+            vec![("keys", Json::Array(p.keys.iter().map(ident_json).collect()))],
+        ),
+    }
+}
+
+fn ident_json(ident: &IdentExpression) -> Json {
+    Json::node(
+        "Identifier",
+        vec![("value", Json::String(ident.value.clone()))],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_string_escapes() {
+        let mut out = String::new();
+        write_string(&mut out, "a\"b\\c\n\u{1}");
+        assert_eq!(out, r#""a\"b\\c\n\u0001""#);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut parser = crate::parser::Parser::new(crate::lexer::Lexer::new("-a;"));
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty());
+
+        let expected = r#"{
+  "type": "Program",
+  "statements": [
+    {
+      "type": "ExpressionStatement",
+      "expression": {
+        "type": "PrefixExpression",
+        "operator": "-",
+        "right": {
+          "type": "Identifier",
+          "value": "a"
+        }
+      }
+    }
+  ]
+}
+"#;
+        assert_eq!(program.to_json(), expected);
+    }
+}
@@ -0,0 +1,13 @@
+//! Alternative textual representations of the AST.
+//!
+//! Each printer adds a `to_*` method to [`Program`](crate::ast::Program), so
+//! callers can choose the representation that best fits their use case.
+
+mod dot;
+pub(crate) mod json;
+mod pretty;
+mod sexpr;
+mod source;
+mod tree;
+
+pub use pretty::{BraceStyle, FormatConfig};
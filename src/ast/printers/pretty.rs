@@ -0,0 +1,831 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    ast::{statements::BlockStatement, Comments, Expression, Program, Statement},
+    parser::Precedence,
+    token::Comment,
+    token::TokenType,
+};
+
+/// Style options of the pretty printer, see
+/// [`Program::to_pretty_string_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatConfig {
+    /// Columns per level of indentation
+    pub indent_width: usize,
+    /// Whether to indent with a tab per level, counted as `indent_width`
+    /// columns, instead of spaces
+    pub use_tabs: bool,
+    pub brace_style: BraceStyle,
+    /// Column past which lists of arguments, parameters or elements are
+    /// broken into one item per line. Nothing else is broken, so lines
+    /// without such a list, like a long chain of infix operators, can
+    /// still go past it.
+    pub max_width: usize,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            use_tabs: false,
+            brace_style: BraceStyle::SameLine,
+            max_width: 100,
+        }
+    }
+}
+
+/// Where the opening brace of a block goes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BraceStyle {
+    /// At the end of the line starting the block, e.g. `if (a) {`
+    SameLine,
+    /// On a line of its own, with `else`, `catch` and `while` on the line
+    /// after the closing brace
+    NextLine,
+}
+
+impl BraceStyle {
+    pub fn from_name(name: &str) -> Option<BraceStyle> {
+        match name {
+            "same_line" => Some(BraceStyle::SameLine),
+            "next_line" => Some(BraceStyle::NextLine),
+            _ => None,
+        }
+    }
+}
+
+impl Program {
+    /// Prints the program as Monkey source code, one statement per line,
+    /// only adding the parentheses needed to preserve the tree, e.g.
+    /// `(a + b) * c` instead of the `((a + b) * c)` of `to_source()`.
+    ///
+    /// Blocks span several lines, indented by four spaces, and the `;` after
+    /// an expression ending in a block is left out when it isn't needed.
+    ///
+    /// The comments kept by the parser, if any, are printed around the
+    /// statements they're attached to, along with a single blank line
+    /// wherever the source had one or more between statements.
+    pub fn to_pretty_string(&self) -> String {
+        self.to_pretty_string_with(&FormatConfig::default())
+    }
+
+    /// Prints the program like [`Program::to_pretty_string`], in the style
+    /// described by `config`.
+    pub fn to_pretty_string_with(&self, config: &FormatConfig) -> String {
+        let printer = Printer::new(config, &self.comments);
+        printer
+            .statements(&self.statements, 0)
+            .into_iter()
+            .chain(printer.comment_lines(self.comments.program_end()))
+            .map(|line| format!("{line}\n"))
+            .collect()
+    }
+}
+
+impl BlockStatement {
+    /// Prints the block with its statements on their own lines, see
+    /// [`Program::to_pretty_string`].
+    pub fn to_pretty_string(&self) -> String {
+        Printer::new(&FormatConfig::default(), &Comments::default()).block(self, 0)
+    }
+}
+
+impl Statement {
+    /// Prints the statement with minimal parentheses, see
+    /// [`Program::to_pretty_string`].
+    pub fn to_pretty_string(&self) -> String {
+        Printer::new(&FormatConfig::default(), &Comments::default()).statement(self, 0)
+    }
+}
+
+impl Expression {
+    /// Prints the expression with minimal parentheses, see
+    /// [`Program::to_pretty_string`].
+    pub fn to_pretty_string(&self) -> String {
+        Printer::new(&FormatConfig::default(), &Comments::default()).expression(
+            self,
+            Precedence::Lowest.value(),
+            Cursor::line(0),
+        )
+    }
+
+    /// Whether the printed expression ends with the closing brace of a
+    /// block, e.g. `a + if (b) { c }`.
+    fn ends_with_block(&self) -> bool {
+        match self {
+            Expression::If(_) | Expression::Try(_) | Expression::Function(_) => true,
+            Expression::Prefix(e) => e.right.ends_with_block(),
+            // Custom operators always wrap their operands in parentheses
+            Expression::Infix(e) if e.token.token_type == TokenType::Operator => false,
+            Expression::Infix(e) => e.right.ends_with_block(),
+            Expression::Pipe(e) => e.right.ends_with_block(),
+            _ => false,
+        }
+    }
+
+    /// The precedence the expression was parsed with, used to decide
+    /// whether it needs parentheses inside another expression.
+    fn precedence(&self) -> usize {
+        match self {
+            Expression::Ident(_)
+            | Expression::Integer(_)
+            | Expression::Float(_)
+            | Expression::Boolean(_)
+            | Expression::Placeholder(_)
+            | Expression::Set(_)
+            | Expression::Array(_)
+            | Expression::Member(_)
+            | Expression::If(_)
+            | Expression::Try(_)
+            | Expression::Function(_)
+            | Expression::Call(_) => Precedence::Call.value(),
+            Expression::Prefix(_) => Precedence::Prefix.value(),
+            // Spreads are only found in argument and element lists, never
+            // as an operand
+            Expression::Spread(_) => Precedence::Lowest.value(),
+            // The precedence of custom operators is only known to the parser
+            // that registered them, so they're always parenthesized
+            Expression::Infix(e) if e.token.token_type == TokenType::Operator => {
+                Precedence::Lowest.value()
+            }
+            Expression::Infix(e) => e.token.token_type.precedence().value(),
+            Expression::Pipe(_) => Precedence::Pipe.value(),
+        }
+    }
+}
+
+/// Prints nodes in the style of a [`FormatConfig`].
+///
+/// Nodes are printed as if they started at the beginning of a line: the
+/// lines after their first are indented relative to it, and indented again
+/// by the nodes containing them. Their actual position is only needed to
+/// decide whether lists fit on a line.
+#[derive(Clone, Copy)]
+struct Printer<'c> {
+    config: &'c FormatConfig,
+    /// The comments of the program being printed
+    comments: &'c Comments,
+    /// Whether the output is only printed to measure its first line, in
+    /// which case lists are never broken and blocks are left empty
+    measuring: bool,
+}
+
+/// A position in the printed output.
+#[derive(Clone, Copy)]
+struct Cursor {
+    /// Column where the text of the current line starts
+    indent: usize,
+    column: usize,
+}
+
+impl Cursor {
+    /// Returns the position at the start of a line indented to `indent`.
+    fn line(indent: usize) -> Self {
+        Self {
+            indent,
+            column: indent,
+        }
+    }
+}
+
+impl<'c> Printer<'c> {
+    fn new(config: &'c FormatConfig, comments: &'c Comments) -> Self {
+        Self {
+            config,
+            comments,
+            measuring: false,
+        }
+    }
+
+    /// Returns the whitespace for `levels` levels of indentation.
+    fn indent(&self, levels: usize) -> String {
+        if self.config.use_tabs {
+            "\t".repeat(levels)
+        } else {
+            " ".repeat(levels * self.config.indent_width)
+        }
+    }
+
+    /// Returns the number of columns `text` takes on a single line.
+    fn width(&self, text: &str) -> usize {
+        text.chars()
+            .map(|c| match c {
+                '\t' => self.config.indent_width,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Returns the position after printing `text` from `cursor`.
+    fn advance(&self, cursor: Cursor, text: &str) -> Cursor {
+        match text.rsplit_once('\n') {
+            Some((_, last)) => Cursor {
+                column: cursor.indent + self.width(last),
+                ..cursor
+            },
+            None => Cursor {
+                column: cursor.column + self.width(text),
+                ..cursor
+            },
+        }
+    }
+
+    /// Returns the text of each comment, to be printed on lines of their
+    /// own.
+    fn comment_lines<'a>(&self, comments: &'a [Comment]) -> impl Iterator<Item = String> + 'a {
+        comments.iter().map(|comment| comment.text.clone())
+    }
+
+    /// Prints each statement with its comments and the blank line before
+    /// it, if there was one, dropping the `;` after
+    /// expression statements ending in a block unless the next statement
+    /// could otherwise continue the expression, e.g. `if (a) { b }; -c`.
+    fn statements(&self, statements: &[Statement], indent: usize) -> Vec<String> {
+        let printed: Vec<String> = statements
+            .iter()
+            .map(|stmt| self.statement(stmt, indent))
+            .collect();
+
+        printed
+            .iter()
+            .enumerate()
+            .map(|(i, stmt)| {
+                let continues = printed.get(i + 1).is_some_and(|next| {
+                    next.starts_with(|c: char| !c.is_alphanumeric() && c != '_' && c != '!')
+                });
+
+                let ends_with_block = match &statements[i] {
+                    Statement::Expression(s) => s.expression.ends_with_block(),
+                    _ => false,
+                };
+
+                let mut stmt = match stmt.strip_suffix("};") {
+                    Some(stmt) if ends_with_block && !continues => format!("{stmt}}}"),
+                    _ => stmt.clone(),
+                };
+                if let Some(comment) = self.comments.trailing(&statements[i]) {
+                    stmt = format!("{stmt} {}", comment.text);
+                }
+
+                let mut lines = Vec::new();
+                if i > 0 && self.comments.blank_line_before(&statements[i]) {
+                    lines.push(String::new());
+                }
+                lines.extend(self.comment_lines(self.comments.leading(&statements[i])));
+                lines.push(stmt);
+                lines.join("\n")
+            })
+            .collect()
+    }
+
+    /// Appends the statements to `out`, one per line, indented by `levels`
+    /// levels more than the line starting at column `indent`.
+    fn push_indented(
+        &self,
+        out: &mut String,
+        statements: &[Statement],
+        levels: usize,
+        indent: usize,
+    ) {
+        let prefix = self.indent(levels);
+        let indent = indent + levels * self.config.indent_width;
+        for stmt in self.statements(statements, indent) {
+            for line in stmt.lines() {
+                if !line.is_empty() {
+                    out.push_str(&prefix);
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    /// Prints the block with its statements on their own lines, where
+    /// `indent` is the column of the line the block starts on.
+    fn block(&self, block: &BlockStatement, indent: usize) -> String {
+        if block.statements.is_empty() && self.comments.dangling(block).is_empty() {
+            return "{}".to_string();
+        }
+        if self.measuring {
+            return "{\n}".to_string();
+        }
+
+        let mut out = String::from("{\n");
+        self.push_block_body(&mut out, block, 1, indent);
+        out.push('}');
+        out
+    }
+
+    /// Appends the statements of the block to `out` like
+    /// [`Printer::push_indented`], followed by its dangling comments.
+    fn push_block_body(
+        &self,
+        out: &mut String,
+        block: &BlockStatement,
+        levels: usize,
+        indent: usize,
+    ) {
+        self.push_indented(out, &block.statements, levels, indent);
+        for line in self.comment_lines(self.comments.dangling(block)) {
+            out.push_str(&self.indent(levels));
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    /// Joins the start of a construct, such as `if (a)`, with its block.
+    fn attach(&self, header: &str, block: &str) -> String {
+        match self.config.brace_style {
+            BraceStyle::NextLine if block != "{}" => format!("{header}\n{block}"),
+            _ => format!("{header} {block}"),
+        }
+    }
+
+    /// Returns what separates a block from the `keyword` continuing its
+    /// construct, e.g. the ` else` of `} else`.
+    fn after_block(&self, keyword: &str) -> String {
+        match self.config.brace_style {
+            BraceStyle::SameLine => format!(" {keyword}"),
+            BraceStyle::NextLine => format!("\n{keyword}"),
+        }
+    }
+
+    /// Prints the statement, starting on a line indented to `indent`.
+    fn statement(&self, stmt: &Statement, indent: usize) -> String {
+        let line = Cursor::line(indent);
+        let lowest = Precedence::Lowest.value();
+        // Prints a statement made of `header` followed by `value`
+        let with_value = |header: String, value: &Expression| {
+            let value = self.expression(value, lowest, self.advance(line, &header));
+            format!("{header}{value};")
+        };
+
+        match stmt {
+            Statement::Let(s) => {
+                with_value(format!("{} {} = ", s.keyword(), s.name.value), &s.value)
+            }
+            Statement::Return(s) => with_value("return ".to_string(), &s.value),
+            Statement::Throw(s) => with_value("throw ".to_string(), &s.value),
+            Statement::Defer(s) => with_value("defer ".to_string(), &s.value),
+            Statement::Switch(s) => {
+                let mut body = String::from("{\n");
+                for case in s.cases.iter() {
+                    let values: Vec<String> = case
+                        .values
+                        .iter()
+                        .map(|value| self.expression(value, lowest, line))
+                        .collect();
+                    body.push_str(&format!("{}case {}:\n", self.indent(1), values.join(", ")));
+                    self.push_block_body(&mut body, &case.body, 2, indent);
+                }
+                if let Some(default) = &s.default {
+                    body.push_str(&format!("{}default:\n", self.indent(1)));
+                    self.push_block_body(&mut body, default, 2, indent);
+                }
+                body.push('}');
+
+                let subject = self.expression(&s.subject, lowest, self.advance(line, "switch ("));
+                self.attach(&format!("switch ({subject})"), &body)
+            }
+            Statement::DoWhile(s) => {
+                let mut out = self.attach("do", &self.block(&s.body, indent));
+                out.push_str(&self.after_block("while"));
+                out.push_str(" (");
+                let condition = self.expression(&s.condition, lowest, self.advance(line, &out));
+                format!("{out}{condition});")
+            }
+            Statement::ForIn(s) => {
+                let bindings: Vec<&str> = s.bindings.iter().map(|b| b.value.as_str()).collect();
+                let header = format!("for ({} in ", bindings.join(", "));
+                let iterable = self.expression(&s.iterable, lowest, self.advance(line, &header));
+                self.attach(
+                    &format!("{header}{iterable})"),
+                    &self.block(&s.body, indent),
+                )
+            }
+            // Enums have no expressions, so there is nothing to simplify
+            Statement::Enum(_) => stmt.to_source(),
+            Statement::Expression(s) => {
+                format!("{};", self.expression(&s.expression, lowest, line))
+            }
+        }
+    }
+
+    /// Prints the expression starting at `cursor`, wrapping it in
+    /// parentheses if its precedence is lower than `min_precedence`.
+    fn expression(&self, expression: &Expression, min_precedence: usize, cursor: Cursor) -> String {
+        let parenthesized = expression.precedence() < min_precedence;
+        let start = match parenthesized {
+            true => self.advance(cursor, "("),
+            false => cursor,
+        };
+        let lowest = Precedence::Lowest.value();
+        // Prints `operand` after `printed`, the start of the expression
+        let operand = |printed: &str, operand: &Expression, precedence: usize| {
+            self.expression(operand, precedence, self.advance(start, printed))
+        };
+
+        let printed = match expression {
+            Expression::Ident(e) => e.value.clone(),
+            Expression::Integer(e) => e.value.to_string(),
+            // Printed as written, e.g. `2.5e-3` rather than `0.0025`
+            Expression::Float(e) => e.token.literal.clone(),
+            Expression::Boolean(e) => e.value.to_string(),
+            Expression::Placeholder(_) => "_".to_string(),
+            Expression::If(e) => {
+                let condition = operand("if (", &e.condition, lowest);
+                let mut out = self.attach(
+                    &format!("if ({condition})"),
+                    &self.block(&e.consequence, start.indent),
+                );
+                if let Some(alternative) = &e.alternative {
+                    out.push_str(&self.after_block("else"));
+                    out = self.attach(&out, &self.block(alternative, start.indent));
+                }
+                out
+            }
+            Expression::Function(e) => {
+                let mut parameters: Vec<String> =
+                    e.parameters.iter().map(ToString::to_string).collect();
+                if let Some(rest) = &e.rest {
+                    parameters.push(format!("...{}", rest.value));
+                }
+                let parameters = self.list(
+                    "(",
+                    &parameters,
+                    ")",
+                    self.advance(start, "fn"),
+                    |_, parameter, _| parameter.clone(),
+                );
+                self.attach(
+                    &format!("fn{parameters}"),
+                    &self.block(&e.body, start.indent),
+                )
+            }
+            Expression::Member(e) => format!(
+                "{}{}{}",
+                operand("", &e.object, Precedence::Call.value()),
+                e.operator(),
+                e.property.value
+            ),
+            Expression::Set(e) => self.list("#{", &e.elements, "}", start, |printer, e, cursor| {
+                printer.expression(e, lowest, cursor)
+            }),
+            Expression::Array(e) => {
+                self.list("[", &e.elements, "]", start, |printer, e, cursor| {
+                    printer.expression(e, lowest, cursor)
+                })
+            }
+            Expression::Spread(e) => format!("...{}", operand("...", &e.value, lowest)),
+            Expression::Call(e) => {
+                let function = operand("", &e.function, Precedence::Call.value());
+                let items: Vec<(String, &Expression)> = e
+                    .arguments
+                    .iter()
+                    .map(|arg| (String::new(), arg))
+                    .chain(
+                        e.named_arguments
+                            .iter()
+                            .map(|arg| (format!("{}: ", arg.name.value), &arg.value)),
+                    )
+                    .collect();
+                let arguments = self.list(
+                    "(",
+                    &items,
+                    ")",
+                    self.advance(start, &function),
+                    |printer, (name, argument), cursor| {
+                        let argument =
+                            printer.expression(argument, lowest, printer.advance(cursor, name));
+                        format!("{name}{argument}")
+                    },
+                );
+                format!("{function}{arguments}")
+            }
+            Expression::Try(e) => {
+                let mut out = self.attach("try", &self.block(&e.body, start.indent));
+                out.push_str(&format!(
+                    "{} ({})",
+                    self.after_block("catch"),
+                    e.param.value
+                ));
+                self.attach(&out, &self.block(&e.handler, start.indent))
+            }
+            Expression::Prefix(e) => {
+                let right = operand(&e.operator, &e.right, Precedence::Prefix.value());
+                format!("{}{right}", e.operator)
+            }
+            Expression::Infix(e) => {
+                // Infix operators are left associative, so only the right
+                // operand needs parentheses at the same precedence level.
+                // Custom operators always wrap both operands.
+                let (left_precedence, right_precedence) =
+                    if e.token.token_type == TokenType::Operator {
+                        (Precedence::Call.value(), Precedence::Call.value())
+                    } else {
+                        (expression.precedence(), expression.precedence() + 1)
+                    };
+                let left = operand("", &e.left, left_precedence);
+                let printed = format!("{left} {} ", e.operator);
+                let right = operand(&printed, &e.right, right_precedence);
+                format!("{printed}{right}")
+            }
+            Expression::Pipe(e) => {
+                // A call on the right only keeps its parentheses when the
+                // piped value isn't one of its arguments, as in `x |> (g(1))`
+                let right_precedence = if e.parenthesizes_right() {
+                    Precedence::Call.value() + 1
+                } else {
+                    Precedence::Pipe.value() + 1
+                };
+                let left = operand("", &e.left, Precedence::Pipe.value());
+                let printed = format!("{left} |> ");
+                let right = operand(&printed, &e.right, right_precedence);
+                format!("{printed}{right}")
+            }
+        };
+
+        if parenthesized {
+            format!("({printed})")
+        } else {
+            printed
+        }
+    }
+
+    /// Prints `items` between `open` and `close`, each with `print` from
+    /// the position it starts at, such as the arguments of a call. The
+    /// items are put on their own lines if the first line of the list would
+    /// go past the maximum width.
+    fn list<T>(
+        &self,
+        open: &str,
+        items: &[T],
+        close: &str,
+        cursor: Cursor,
+        print: impl Fn(&Printer, &T, Cursor) -> String + Copy,
+    ) -> String {
+        let fits = self.measuring || {
+            let measuring = Printer {
+                measuring: true,
+                ..*self
+            };
+            let printed = measuring.list(open, items, close, cursor, print);
+            let first_line = printed.lines().next().unwrap_or_default();
+            cursor.column + self.width(first_line) <= self.config.max_width
+        };
+
+        if fits || items.is_empty() {
+            let mut out = String::from(open);
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&print(self, item, self.advance(cursor, &out)));
+            }
+            out.push_str(close);
+            return out;
+        }
+
+        let line = Cursor::line(cursor.indent + self.config.indent_width);
+        let items: Vec<String> = items.iter().map(|item| print(self, item, line)).collect();
+
+        let mut out = format!("{open}\n");
+        for line in items.join(",\n").lines() {
+            out.push_str(&self.indent(1));
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push_str(close);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BraceStyle, FormatConfig};
+    use crate::{
+        lexer::Lexer,
+        parser::{Associativity, InfixOperator, Parser, Precedence},
+    };
+
+    #[test]
+    fn test_to_pretty_string() {
+        let tests = vec![
+            ("a + b * c", "a + b * c;\n"),
+            ("(a + b) * c", "(a + b) * c;\n"),
+            ("a - (b - c)", "a - (b - c);\n"),
+            ("(a - b) - c", "a - b - c;\n"),
+            ("-(a + b)", "-(a + b);\n"),
+            ("-(-a)", "--a;\n"),
+            ("!(a == b) == c", "!(a == b) == c;\n"),
+            ("(5 > 4) == (3 < 4)", "5 > 4 == 3 < 4;\n"),
+            ("let x = (((1)))", "let x = 1;\n"),
+            ("return (a * b) / (c * d)", "return a * b / (c * d);\n"),
+            ("!(true == false)", "!(true == false);\n"),
+            ("if (a) {}", "if (a) {}\n"),
+            (
+                "if (a) { b; c } else { return d }",
+                "if (a) {\n    b;\n    c;\n} else {\n    return d;\n}\n",
+            ),
+            (
+                "if (a) { if (b) { c } }; -d",
+                "if (a) {\n    if (b) {\n        c;\n    }\n};\n-d;\n",
+            ),
+            ("let x = if (a) { b }", "let x = if (a) {\n    b;\n};\n"),
+            (
+                "switch (a) { case 1, 2: b; if (c) { d } default: }; -e",
+                "switch (a) {\n    case 1, 2:\n        b;\n        if (c) {\n            d;\n        }\n    default:\n}\n-e;\n",
+            ),
+            (
+                "let f = fn(a, ...rest) { return a; }",
+                "let f = fn(a, ...rest) {\n    return a;\n};\n",
+            ),
+            ("fn() {}", "fn() {}\n"),
+            ("(a + b)(c * (d), e: -f)", "(a + b)(c * d, e: -f);\n"),
+            ("-f(x)(y)", "-f(x)(y);\n"),
+            ("f(_, (2), x: _)", "f(_, 2, x: _);\n"),
+            ("#{(1), a + b} == #{}", "#{1, a + b} == #{};\n"),
+            ("(a.b).c(d)", "a.b.c(d);\n"),
+            ("(a + b).c", "(a + b).c;\n"),
+            ("[(1), ...(a + b)]", "[1, ...a + b];\n"),
+            ("f(...(a), ...[b])", "f(...a, ...[b]);\n"),
+            ("-(a.b)", "-a.b;\n"),
+            ("(-a).b", "(-a).b;\n"),
+            ("(a?.b)?.c(d)", "a?.b?.c(d);\n"),
+            ("if (a) { b }; (c)", "if (a) {\n    b;\n}\nc;\n"),
+            (
+                "if (a) { b }; (c + d)(e)",
+                "if (a) {\n    b;\n};\n(c + d)(e);\n",
+            ),
+            (
+                "for (x in (a + b)) { x }",
+                "for (x in a + b) {\n    x;\n}\n",
+            ),
+            (
+                "do { a } while (a < 10)",
+                "do {\n    a;\n} while (a < 10);\n",
+            ),
+            (
+                "try { throw a + b } catch (e) {}",
+                "try {\n    throw a + b;\n} catch (e) {}\n",
+            ),
+            // Floats are printed as written
+            ("f(2.5e-3, 1E9, 0.10)", "f(2.5e-3, 1E9, 0.10);\n"),
+            // Pipes are printed as written, keeping the parentheses around
+            // a call the value isn't piped into
+            ("(a + b) |> f |> g(1)", "a + b |> f |> g(1);\n"),
+            ("x |> (g(1)) |> (h)(2)", "x |> (g(1)) |> h(2);\n"),
+            ("x |> (f |> g)", "x |> (f |> g);\n"),
+            ("(x ?? y) |> f", "(x ?? y) |> f;\n"),
+        ];
+
+        for (input, expected) in tests {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.to_pretty_string(), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_to_pretty_string_with() {
+        let next_line = FormatConfig {
+            indent_width: 2,
+            brace_style: BraceStyle::NextLine,
+            ..FormatConfig::default()
+        };
+        let tabs = FormatConfig {
+            use_tabs: true,
+            max_width: 20,
+            ..FormatConfig::default()
+        };
+
+        let tests = vec![
+            (
+                "if (a) { b } else { c }",
+                &next_line,
+                "if (a)\n{\n  b;\n}\nelse\n{\n  c;\n}\n",
+            ),
+            (
+                "do { a } while (b); try {} catch (e) { f(e) }",
+                &next_line,
+                "do\n{\n  a;\n}\nwhile (b);\ntry {}\ncatch (e)\n{\n  f(e);\n}\n",
+            ),
+            (
+                "switch (a) { case 1: b }",
+                &next_line,
+                "switch (a)\n{\n  case 1:\n    b;\n}\n",
+            ),
+            ("fn() { a }", &tabs, "fn() {\n\ta;\n}\n"),
+            ("f(a, b, c)", &tabs, "f(a, b, c);\n"),
+            // The list would end at column 21
+            (
+                "let x = [1, 2, 3, 45]",
+                &tabs,
+                "let x = [\n\t1,\n\t2,\n\t3,\n\t45\n];\n",
+            ),
+            // Only lists going past the maximum width are broken, and items
+            // are indented relative to the line the list starts on
+            (
+                "if (a) { f(alpha, g(b), name: [c]) }",
+                &tabs,
+                "if (a) {\n\tf(\n\t\talpha,\n\t\tg(b),\n\t\tname: [c]\n\t);\n}\n",
+            ),
+            // Parameters are broken like arguments
+            (
+                "let f = fn(alpha, beta, gamma, delta) {}",
+                &tabs,
+                "let f = fn(\n\talpha,\n\tbeta,\n\tgamma,\n\tdelta\n) {};\n",
+            ),
+            (
+                "let f = fn(a, ...b) { a }",
+                &tabs,
+                "let f = fn(a, ...b) {\n\ta;\n};\n",
+            ),
+            // Lists ending in a block only need their first line to fit
+            (
+                "map(a, fn(x) { x * x })",
+                &tabs,
+                "map(a, fn(x) {\n\tx * x;\n});\n",
+            ),
+            // Infix operators are never broken
+            (
+                "let z = alpha + beta + gamma;",
+                &tabs,
+                "let z = alpha + beta + gamma;\n",
+            ),
+        ];
+
+        for (input, config, expected) in tests {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(
+                program.to_pretty_string_with(config),
+                expected,
+                "input: {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_pretty_string_comments() {
+        let tests = vec![
+            (
+                "// a\nlet x = 1; // b\n// c",
+                "// a\nlet x = 1; // b\n// c\n",
+            ),
+            (
+                "if (a) { // b\n c } else {\n// d\n}",
+                "if (a) {\n    // b\n    c;\n} else {\n    // d\n}\n",
+            ),
+            (
+                "switch (a) { case 1: // b\n default: c // d\n }",
+                "switch (a) {\n    case 1:\n        // b\n    default:\n        c; // d\n}\n",
+            ),
+            // Comments inside expressions are moved before their statement
+            ("f(1, // a\n 2)", "// a\nf(1, 2);\n"),
+            (
+                "let x = [\n    1, // a\n    // b\n    2\n];",
+                "// a\n// b\nlet x = [1, 2];\n",
+            ),
+            // Blank lines between statements are kept, but only one
+            (
+                "\n\nlet a = 1;\n\n\n\nlet b = 2; // c\n\n// d\nlet e = 3;\nf",
+                "let a = 1;\n\nlet b = 2; // c\n\n// d\nlet e = 3;\nf;\n",
+            ),
+            (
+                "if (a) {\n\n  b;\n\n  c\n}",
+                "if (a) {\n    b;\n\n    c;\n}\n",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            parser.keep_comments();
+            let program = parser.parse_program();
+            assert!(parser.errors().is_empty(), "input: {input}");
+            assert_eq!(program.to_pretty_string(), expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_to_pretty_string_custom_operators() {
+        let mut parser = Parser::new(Lexer::new("a + b <> -c; a <> b + c"));
+        parser
+            .register_infix_operator(InfixOperator {
+                symbol: "<>".to_string(),
+                precedence: Precedence::Sum,
+                associativity: Associativity::Left,
+            })
+            .unwrap();
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty());
+
+        assert_eq!(
+            program.to_pretty_string(),
+            "(a + b) <> (-c);\n(a <> b) + c;\n"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::ast::{patterns::Pattern, statements::BlockStatement, Expression, Program, Statement};
+
+impl Program {
+    /// Returns the program as S-expressions, with one top-level statement
+    /// per line, e.g. `let x = 1 + 2;` becomes `(let x (+ 1 2))`.
+    pub fn to_sexpr(&self) -> String {
+        let mut out = String::new();
+        for stmt in self.statements.iter() {
+            out.push_str(&statement_sexpr(stmt));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn statement_sexpr(stmt: &Statement) -> String {
+    match stmt {
+        Statement::Let(s) => format!(
+            "({} {} {})",
+            s.keyword(),
+            s.name.value,
+            expression_sexpr(&s.value)
+        ),
+        Statement::Return(s) => format!("(return {})", expression_sexpr(&s.value)),
+        Statement::Throw(s) => format!("(throw {})", expression_sexpr(&s.value)),
+        Statement::Defer(s) => format!("(defer {})", expression_sexpr(&s.value)),
+        Statement::Switch(s) => {
+            let mut out = format!("(switch {}", expression_sexpr(&s.subject));
+            for case in s.cases.iter() {
+                let values: Vec<String> = case.values.iter().map(expression_sexpr).collect();
+                out.push_str(&format!(
+                    " (case ({}) {})",
+                    values.join(" "),
+                    block_sexpr(&case.body)
+                ));
+            }
+            if let Some(default) = &s.default {
+                out.push_str(&format!(" (default {})", block_sexpr(default)));
+            }
+            out.push(')');
+            out
+        }
+        Statement::DoWhile(s) => format!(
+            "(do-while {} {})",
+            block_sexpr(&s.body),
+            expression_sexpr(&s.condition)
+        ),
+        Statement::ForIn(s) => {
+            let bindings: Vec<&str> = s.bindings.iter().map(|b| b.value.as_str()).collect();
+            format!(
+                "(for ({}) {} {})",
+                bindings.join(" "),
+                expression_sexpr(&s.iterable),
+                block_sexpr(&s.body)
+            )
+        }
+        Statement::Enum(s) => {
+            let mut out = format!("(enum {}", s.name.value);
+            for variant in s.variants.iter() {
+                if variant.fields.is_empty() {
+                    out.push_str(&format!(" {}", variant.name.value));
+                } else {
+                    let fields: Vec<&str> =
+                        variant.fields.iter().map(|f| f.value.as_str()).collect();
+                    out.push_str(&format!(" ({} {})", variant.name.value, fields.join(" ")));
+                }
+            }
+            out.push(')');
+            out
+        }
+        Statement::Expression(s) => expression_sexpr(&s.expression),
+    }
+}
+
+fn expression_sexpr(expression: &Expression) -> String {
+    match expression {
+        Expression::Ident(e) => e.value.clone(),
+        Expression::Integer(e) => e.value.to_string(),
+        Expression::Float(e) => e.to_string(),
+        Expression::Boolean(e) => e.value.to_string(),
+        Expression::Placeholder(_) => "_".to_string(),
+        Expression::Prefix(e) => format!("({} {})", e.operator, expression_sexpr(&e.right)),
+        Expression::Infix(e) => format!(
+            "({} {} {})",
+            e.operator,
+            expression_sexpr(&e.left),
+            expression_sexpr(&e.right)
+        ),
+        Expression::Pipe(e) => format!(
+            "(|> {} {})",
+            expression_sexpr(&e.left),
+            expression_sexpr(&e.right)
+        ),
+        Expression::If(e) => {
+            let mut out = format!(
+                "(if {} {}",
+                expression_sexpr(&e.condition),
+                block_sexpr(&e.consequence)
+            );
+            if let Some(alternative) = &e.alternative {
+                out.push(' ');
+                out.push_str(&block_sexpr(alternative));
+            }
+            out.push(')');
+            out
+        }
+        Expression::Member(e) => {
+            format!(
+                "({} {} {})",
+                e.operator(),
+                expression_sexpr(&e.object),
+                e.property.value
+            )
+        }
+        Expression::Set(e) => {
+            let mut out = String::from("(set");
+            for element in e.elements.iter() {
+                out.push(' ');
+                out.push_str(&expression_sexpr(element));
+            }
+            out.push(')');
+            out
+        }
+        Expression::Array(e) => {
+            let mut out = String::from("(array");
+            for element in e.elements.iter() {
+                out.push(' ');
+                out.push_str(&expression_sexpr(element));
+            }
+            out.push(')');
+            out
+        }
+        Expression::Spread(e) => format!("(... {})", expression_sexpr(&e.value)),
+        Expression::Call(e) => {
+            let mut out = format!("(call {}", expression_sexpr(&e.function));
+            for argument in e.arguments.iter() {
+                out.push(' ');
+                out.push_str(&expression_sexpr(argument));
+            }
+            for argument in e.named_arguments.iter() {
+                out.push_str(&format!(
+                    " (named {} {})",
+                    argument.name.value,
+                    expression_sexpr(&argument.value)
+                ));
+            }
+            out.push(')');
+            out
+        }
+        Expression::Function(e) => {
+            let mut parameters: Vec<String> = e.parameters.iter().map(pattern_sexpr).collect();
+            if let Some(rest) = &e.rest {
+                parameters.push(format!("...{}", rest.value));
+            }
+            format!("(fn ({}) {})", parameters.join(" "), block_sexpr(&e.body))
+        }
+        Expression::Try(e) => format!(
+            "(try {} (catch {} {}))",
+            block_sexpr(&e.body),
+            e.param.value,
+            block_sexpr(&e.handler)
+        ),
+    }
+}
+
+fn pattern_sexpr(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Ident(p) => p.value.clone(),
+        Pattern::Array(p) => {
+            let elements: Vec<String> = p.elements.iter().map(pattern_sexpr).collect();
+            format!("[{}]", elements.join(" "))
+        }
+        Pattern::Hash(p) => {
+            let keys: Vec<&str> = p.keys.iter().map(|k| k.value.as_str()).collect();
+            format!("{{{}}}", keys.join(" "))
+        }
+    }
+}
+
+fn block_sexpr(block: &BlockStatement) -> String {
+    let mut out = String::from("(block");
+    for stmt in block.statements.iter() {
+        out.push(' ');
+        out.push_str(&statement_sexpr(stmt));
+    }
+    out.push(')');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{lexer::Lexer, parser::Parser};
+
+    #[test]
+    fn test_to_sexpr() {
+        let tests = vec![
+            ("a + b * c", "(+ a (* b c))\n"),
+            ("-a * b", "(* (- a) b)\n"),
+            ("!-a; 5", "(! (- a))\n5\n"),
+            ("!true", "(! true)\n"),
+            (
+                "if (a) { return b; } else { c; d }",
+                "(if a (block (return b)) (block c d))\n",
+            ),
+            ("if (a) {}", "(if a (block))\n"),
+            (
+                "switch (x) { case 1, 2: a; b default: c }",
+                "(switch x (case (1 2) (block a b)) (default (block c)))\n",
+            ),
+            ("do { a } while (b)", "(do-while (block a) b)\n"),
+            ("for (k, v in h) { k }", "(for (k v) h (block k))\n"),
+            ("fn(a, ...b) { a }", "(fn (a ...b) (block a))\n"),
+            ("fn([a, b], {x, y}) {}", "(fn ([a b] {x y}) (block))\n"),
+            ("f(a + b, c: d)", "(call f (+ a b) (named c d))\n"),
+            ("f()()", "(call (call f))\n"),
+            ("f(_, x: _)", "(call f _ (named x _))\n"),
+            ("#{a, -b}", "(set a (- b))\n"),
+            ("a.b(c)", "(call (. a b) c)\n"),
+            ("a?.b.c", "(. (?. a b) c)\n"),
+            ("1e9 * 2.5e-3", "(* 1000000000.0 0.0025)\n"),
+            ("f(...[1, ...a])", "(call f (... (array 1 (... a))))\n"),
+            (
+                "enum Shape { Circle(r), Rect(w, h), Point }",
+                "(enum Shape (Circle r) (Rect w h) Point)\n",
+            ),
+            (
+                "try { throw a; } catch (e) { e }",
+                "(try (block (throw a)) (catch e (block e)))\n",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+            assert!(parser.errors().is_empty());
+            assert_eq!(program.to_sexpr(), expected);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::ast::{statements::BlockStatement, Expression, Program, Statement};
+
+impl Program {
+    /// Prints the program as Monkey source code, one statement per line.
+    ///
+    /// Unlike `to_string()`, the output always parses back into an equal
+    /// program, since every statement is terminated and every operation
+    /// is parenthesized.
+    pub fn to_source(&self) -> String {
+        self.statements
+            .iter()
+            .map(|stmt| format!("{}\n", stmt.to_source()))
+            .collect()
+    }
+}
+
+impl Statement {
+    /// Prints the statement as Monkey source code, see [`Program::to_source`].
+    pub fn to_source(&self) -> String {
+        match self {
+            Statement::Let(s) => format!(
+                "{} {} = {};",
+                s.keyword(),
+                s.name.value,
+                s.value.to_source()
+            ),
+            Statement::Return(s) => format!("return {};", s.value.to_source()),
+            Statement::Throw(s) => format!("throw {};", s.value.to_source()),
+            Statement::Defer(s) => format!("defer {};", s.value.to_source()),
+            Statement::Switch(s) => {
+                let mut out = format!("switch ({}) {{", s.subject.to_source());
+                for case in s.cases.iter() {
+                    let values: Vec<String> =
+                        case.values.iter().map(Expression::to_source).collect();
+                    out.push_str(&format!(" case {}:", values.join(", ")));
+                    for stmt in case.body.statements.iter() {
+                        out.push(' ');
+                        out.push_str(&stmt.to_source());
+                    }
+                }
+                if let Some(default) = &s.default {
+                    out.push_str(" default:");
+                    for stmt in default.statements.iter() {
+                        out.push(' ');
+                        out.push_str(&stmt.to_source());
+                    }
+                }
+                out.push_str(" }");
+                out
+            }
+            Statement::DoWhile(s) => format!(
+                "do {} while ({});",
+                s.body.to_source(),
+                s.condition.to_source()
+            ),
+            Statement::ForIn(s) => {
+                let bindings: Vec<&str> = s.bindings.iter().map(|b| b.value.as_str()).collect();
+                format!(
+                    "for ({} in {}) {}",
+                    bindings.join(", "),
+                    s.iterable.to_source(),
+                    s.body.to_source()
+                )
+            }
+            Statement::Enum(s) if s.variants.is_empty() => format!("enum {} {{}}", s.name.value),
+            Statement::Enum(s) => {
+                let variants: Vec<String> = s.variants.iter().map(ToString::to_string).collect();
+                format!("enum {} {{ {} }}", s.name.value, variants.join(", "))
+            }
+            Statement::Expression(s) => format!("{};", s.expression.to_source()),
+        }
+    }
+}
+
+impl Expression {
+    /// Prints the expression as Monkey source code, see [`Program::to_source`].
+    ///
+    /// Number literals are expected to be non-negative, as produced by the
+    /// parser: negative numbers are represented by a prefix expression.
+    pub fn to_source(&self) -> String {
+        match self {
+            Expression::Ident(e) => e.value.clone(),
+            Expression::Integer(e) => e.value.to_string(),
+            Expression::Float(e) => e.to_string(),
+            Expression::Boolean(e) => e.value.to_string(),
+            Expression::Placeholder(_) => "_".to_string(),
+            Expression::Prefix(e) => format!("({}{})", e.operator, e.right.to_source()),
+            Expression::Infix(e) => format!(
+                "({} {} {})",
+                e.left.to_source(),
+                e.operator,
+                e.right.to_source()
+            ),
+            Expression::Pipe(e) if e.parenthesizes_right() => {
+                format!("({} |> ({}))", e.left.to_source(), e.right.to_source())
+            }
+            Expression::Pipe(e) => format!("({} |> {})", e.left.to_source(), e.right.to_source()),
+            Expression::If(e) => {
+                let mut out = format!(
+                    "if ({}) {}",
+                    e.condition.to_source(),
+                    e.consequence.to_source()
+                );
+                if let Some(alternative) = &e.alternative {
+                    out.push_str(" else ");
+                    out.push_str(&alternative.to_source());
+                }
+                out
+            }
+            Expression::Member(e) => format!(
+                "{}{}{}",
+                e.object.to_source(),
+                e.operator(),
+                e.property.value
+            ),
+            Expression::Set(e) => {
+                let elements: Vec<String> = e.elements.iter().map(Expression::to_source).collect();
+                format!("#{{{}}}", elements.join(", "))
+            }
+            Expression::Array(e) => {
+                let elements: Vec<String> = e.elements.iter().map(Expression::to_source).collect();
+                format!("[{}]", elements.join(", "))
+            }
+            Expression::Spread(e) => format!("...{}", e.value.to_source()),
+            Expression::Call(e) => format!(
+                "{}({})",
+                e.function.to_source(),
+                e.argument_list(Expression::to_source)
+            ),
+            Expression::Function(e) => {
+                format!("fn({}) {}", e.parameter_list(), e.body.to_source())
+            }
+            Expression::Try(e) => format!(
+                "try {} catch ({}) {}",
+                e.body.to_source(),
+                e.param.value,
+                e.handler.to_source()
+            ),
+        }
+    }
+}
+
+impl BlockStatement {
+    /// Prints the block on a single line, e.g. `{ a; b; }`.
+    pub fn to_source(&self) -> String {
+        if self.statements.is_empty() {
+            return "{}".to_string();
+        }
+
+        let statements: Vec<String> = self.statements.iter().map(Statement::to_source).collect();
+        format!("{{ {} }}", statements.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_to_source() {
+        let tests = vec![
+            ("let x = 5", "let x = 5;\n"),
+            ("return -a * b", "return ((-a) * b);\n"),
+            ("a b; c", "a;\nb;\nc;\n"),
+            ("let y = x return y", "let y = x;\nreturn y;\n"),
+            ("!true == false", "((!true) == false);\n"),
+            (
+                "if (a < b) { a } else { return b; c }",
+                "if ((a < b)) { a; } else { return b; c; };\n",
+            ),
+            ("if (a) {}", "if (a) {};\n"),
+            ("enum E {}; E.A", "enum E {}\nE.A;\n"),
+            ("-1e20 + 2.50", "((-1e20) + 2.5);\n"),
+            ("fn([a, [b]], {x}) {}", "fn([a, [b]], {x}) {};\n"),
+            ("f(...a, [1, ...b + c])", "f(...a, [1, ...(b + c)]);\n"),
+            (
+                "enum Shape { Circle(r), Rect(w, h) }",
+                "enum Shape { Circle(r), Rect(w, h) }\n",
+            ),
+            (
+                "switch (a) { case 1, b: c default: }",
+                "switch (a) { case 1, b: c; default: }\n",
+            ),
+            ("do {} while (!a)", "do {} while ((!a));\n"),
+            ("for (k, v in h) { k }", "for (k, v in h) { k; }\n"),
+            ("fn(a, ...b) { a }", "fn(a, ...b) { a; };\n"),
+            ("-f(a + b, c: -d)", "(-f((a + b), c: (-d)));\n"),
+            ("#{1, a + b}", "#{1, (a + b)};\n"),
+            ("(-a).b(c)", "(-a).b(c);\n"),
+            ("a + b |> f(1)", "((a + b) |> f(1));\n"),
+            ("x |> (g(1))", "(x |> (g(1)));\n"),
+            (
+                "try { throw -a } catch (e) { e }",
+                "try { throw (-a); } catch (e) { e; };\n",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.to_source(), expected);
+        }
+    }
+
+    #[test]
+    fn test_to_source_round_trip() {
+        let input = "let a = 1 + 2 * 3; return !-a; a == b != c > d; if (a) { if (b) { c } } - d; x |> f |> (g(1))";
+
+        let program = Parser::parse(input).unwrap();
+        let source = program.to_source();
+        let reparsed = Parser::parse(&source).unwrap();
+
+        assert_eq!(reparsed, program);
+        assert_eq!(reparsed.to_source(), source);
+    }
+}
@@ -0,0 +1,365 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::ast::{
+    expressions::IdentExpression, patterns::Pattern, statements::BlockStatement, Expression,
+    Program, Statement,
+};
+
+/// A node of the tree view, before it's indented.
+enum Tree {
+    /// A node without children, printed on a single line
+    Leaf(String),
+    /// A node named by its label, followed by its fields
+    Node(String, Vec<(&'static str, Tree)>),
+    List(Vec<Tree>),
+    /// A missing optional field, left out of the output
+    Empty,
+}
+
+impl Tree {
+    fn node(label: impl Into<String>, fields: Vec<(&'static str, Tree)>) -> Tree {
+        Tree::Node(label.into(), fields)
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Tree::List(items) => items.is_empty(),
+            Tree::Empty => true,
+            _ => false,
+        }
+    }
+
+    /// Writes the tree indented by `indent` levels of two spaces, starting
+    /// with `field`, the name of the field holding it, if any.
+    fn write(&self, out: &mut String, indent: usize, field: Option<&str>) {
+        for _ in 0..indent {
+            out.push_str("  ");
+        }
+        if let Some(field) = field {
+            out.push_str(field);
+            out.push(':');
+        }
+
+        match self {
+            Tree::Leaf(label) | Tree::Node(label, _) => {
+                if field.is_some() {
+                    out.push(' ');
+                }
+                out.push_str(label);
+            }
+            Tree::List(_) | Tree::Empty => {}
+        }
+        out.push('\n');
+
+        match self {
+            Tree::Node(_, fields) => {
+                for (name, child) in fields.iter().filter(|(_, child)| !child.is_empty()) {
+                    child.write(out, indent + 1, Some(name));
+                }
+            }
+            Tree::List(items) => {
+                for item in items.iter() {
+                    item.write(out, indent + 1, None);
+                }
+            }
+            Tree::Leaf(_) | Tree::Empty => {}
+        }
+    }
+}
+
+impl Program {
+    /// Returns the program as an indented tree, with a line per node and
+    /// its children below it, e.g. `let x = 1 + 2;` becomes:
+    ///
+    /// ```text
+    /// LetStatement
+    ///   name: Ident(x)
+    ///   value: InfixExpression(+)
+    ///     left: Integer(1)
+    ///     right: Integer(2)
+    /// ```
+    pub fn to_tree_string(&self) -> String {
+        let mut out = String::new();
+        for stmt in self.statements.iter() {
+            statement_tree(stmt).write(&mut out, 0, None);
+        }
+        out
+    }
+}
+
+fn statement_tree(stmt: &Statement) -> Tree {
+    match stmt {
+        Statement::Let(s) => {
+            let label = match s.is_const() {
+                true => "LetStatement(const)",
+                false => "LetStatement",
+            };
+            Tree::node(
+                label,
+                vec![
+                    ("name", ident_tree(&s.name)),
+                    ("value", expression_tree(&s.value)),
+                ],
+            )
+        }
+        Statement::Return(s) => Tree::node(
+            "ReturnStatement",
+            vec![("value", expression_tree(&s.value))],
+        ),
+        Statement::Throw(s) => {
+            Tree::node("ThrowStatement", vec![("value", expression_tree(&s.value))])
+        }
+        Statement::Defer(s) => {
+            Tree::node("DeferStatement", vec![("value", expression_tree(&s.value))])
+        }
+        Statement::Switch(s) => {
+            let cases = s
+                .cases
+                .iter()
+                .map(|case| {
+                    Tree::node(
+                        "SwitchCase",
+                        vec![
+                            (
+                                "values",
+                                Tree::List(case.values.iter().map(expression_tree).collect()),
+                            ),
+                            ("body", block_tree(&case.body)),
+                        ],
+                    )
+                })
+                .collect();
+            Tree::node(
+                "SwitchStatement",
+                vec![
+                    ("subject", expression_tree(&s.subject)),
+                    ("cases", Tree::List(cases)),
+                    (
+                        "default",
+                        s.default.as_ref().map_or(Tree::Empty, block_tree),
+                    ),
+                ],
+            )
+        }
+        Statement::DoWhile(s) => Tree::node(
+            "DoWhileStatement",
+            vec![
+                ("body", block_tree(&s.body)),
+                ("condition", expression_tree(&s.condition)),
+            ],
+        ),
+        Statement::ForIn(s) => Tree::node(
+            "ForInStatement",
+            vec![
+                (
+                    "bindings",
+                    Tree::List(s.bindings.iter().map(ident_tree).collect()),
+                ),
+                ("iterable", expression_tree(&s.iterable)),
+                ("body", block_tree(&s.body)),
+            ],
+        ),
+        Statement::Enum(s) => {
+            let variants = s
+                .variants
+                .iter()
+                .map(|variant| {
+                    Tree::node(
+                        format!("EnumVariant({})", variant.name.value),
+                        vec![(
+                            "fields",
+                            Tree::List(variant.fields.iter().map(ident_tree).collect()),
+                        )],
+                    )
+                })
+                .collect();
+            Tree::node(
+                "EnumStatement",
+                vec![
+                    ("name", ident_tree(&s.name)),
+                    ("variants", Tree::List(variants)),
+                ],
+            )
+        }
+        Statement::Expression(s) => Tree::node(
+            "ExpressionStatement",
+            vec![("expression", expression_tree(&s.expression))],
+        ),
+    }
+}
+
+fn expression_tree(expression: &Expression) -> Tree {
+    match expression {
+        Expression::Ident(e) => ident_tree(e),
+        Expression::Integer(e) => Tree::Leaf(format!("Integer({})", e.value)),
+        Expression::Float(e) => Tree::Leaf(format!("Float({e})")),
+        Expression::Boolean(e) => Tree::Leaf(format!("Boolean({})", e.value)),
+        Expression::Placeholder(_) => Tree::Leaf("Placeholder".to_string()),
+        Expression::Prefix(e) => Tree::node(
+            format!("PrefixExpression({})", e.operator),
+            vec![("right", expression_tree(&e.right))],
+        ),
+        Expression::Infix(e) => Tree::node(
+            format!("InfixExpression({})", e.operator),
+            vec![
+                ("left", expression_tree(&e.left)),
+                ("right", expression_tree(&e.right)),
+            ],
+        ),
+        Expression::Pipe(e) => Tree::node(
+            if e.splices {
+                "PipeExpression(splices)"
+            } else {
+                "PipeExpression"
+            },
+            vec![
+                ("left", expression_tree(&e.left)),
+                ("right", expression_tree(&e.right)),
+            ],
+        ),
+        Expression::If(e) => Tree::node(
+            "IfExpression",
+            vec![
+                ("condition", expression_tree(&e.condition)),
+                ("consequence", block_tree(&e.consequence)),
+                (
+                    "alternative",
+                    e.alternative.as_ref().map_or(Tree::Empty, block_tree),
+                ),
+            ],
+        ),
+        Expression::Member(e) => Tree::node(
+            format!("MemberExpression({})", e.operator()),
+            vec![
+                ("object", expression_tree(&e.object)),
+                ("property", ident_tree(&e.property)),
+            ],
+        ),
+        Expression::Set(e) => Tree::node(
+            "SetLiteral",
+            vec![(
+                "elements",
+                Tree::List(e.elements.iter().map(expression_tree).collect()),
+            )],
+        ),
+        Expression::Array(e) => Tree::node(
+            "ArrayLiteral",
+            vec![(
+                "elements",
+                Tree::List(e.elements.iter().map(expression_tree).collect()),
+            )],
+        ),
+        Expression::Spread(e) => Tree::node(
+            "SpreadExpression",
+            vec![("value", expression_tree(&e.value))],
+        ),
+        Expression::Call(e) => {
+            let named_arguments = e
+                .named_arguments
+                .iter()
+                .map(|arg| {
+                    Tree::node(
+                        format!("NamedArgument({})", arg.name.value),
+                        vec![("value", expression_tree(&arg.value))],
+                    )
+                })
+                .collect();
+            Tree::node(
+                "CallExpression",
+                vec![
+                    ("function", expression_tree(&e.function)),
+                    (
+                        "arguments",
+                        Tree::List(e.arguments.iter().map(expression_tree).collect()),
+                    ),
+                    ("named_arguments", Tree::List(named_arguments)),
+                ],
+            )
+        }
+        Expression::Function(e) => Tree::node(
+            "FunctionLiteral",
+            vec![
+                (
+                    "parameters",
+                    Tree::List(e.parameters.iter().map(pattern_tree).collect()),
+                ),
+                ("rest", e.rest.as_ref().map_or(Tree::Empty, ident_tree)),
+                ("body", block_tree(&e.body)),
+            ],
+        ),
+        Expression::Try(e) => Tree::node(
+            "TryExpression",
+            vec![
+                ("body", block_tree(&e.body)),
+                ("param", ident_tree(&e.param)),
+                ("handler", block_tree(&e.handler)),
+            ],
+        ),
+    }
+}
+
+fn block_tree(block: &BlockStatement) -> Tree {
+    let statements = block.statements.iter().map(statement_tree).collect();
+    Tree::node(
+        "BlockStatement",
+        vec![("statements", Tree::List(statements))],
+    )
+}
+
+fn pattern_tree(pattern: &Pattern) -> Tree {
+    match pattern {
+        Pattern::Ident(p) => ident_tree(p),
+        Pattern::Array(p) => Tree::node(
+            "ArrayPattern",
+            vec![(
+                "elements",
+                Tree::List(p.elements.iter().map(pattern_tree).collect()),
+            )],
+        ),
+        Pattern::Hash(p) => Tree::node(
+            "HashPattern",
+            vec![("keys", Tree::List(p.keys.iter().map(ident_tree).collect()))],
+        ),
+    }
+}
+
+fn ident_tree(ident: &IdentExpression) -> Tree {
+    Tree::Leaf(format!("Ident({})", ident.value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_to_tree_string() {
+        let program = Parser::parse("let x = f(1 + 2, a.b); if (x) { -x }").unwrap();
+
+        let expected = "LetStatement
+  name: Ident(x)
+  value: CallExpression
+    function: Ident(f)
+    arguments:
+      InfixExpression(+)
+        left: Integer(1)
+        right: Integer(2)
+      MemberExpression(.)
+        object: Ident(a)
+        property: Ident(b)
+ExpressionStatement
+  expression: IfExpression
+    condition: Ident(x)
+    consequence: BlockStatement
+      statements:
+        ExpressionStatement
+          expression: PrefixExpression(-)
+            right: Ident(x)
+";
+        assert_eq!(program.to_tree_string(), expected);
+    }
+}
@@ -0,0 +1,45 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::{
+    ast::{NodeTrait, Statement, StatementTrait},
+    token::{Span, Token},
+};
+
+/// A list of statements wrapped in braces, e.g. the branches of an `if`,
+/// or the body of a `case`.
+#[derive(Debug)]
+pub struct BlockStatement {
+    /// The `{` token, or the `:` of a `case`
+    pub token: Token,
+    pub statements: Vec<Statement>,
+    /// The span from the opening to the closing brace. The body of a
+    /// `case` spans from its `:` to the end of its last statement.
+    pub span: Span,
+}
+
+impl Display for BlockStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for stmt in self.statements.iter() {
+            write!(f, "{stmt}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for BlockStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.statements == other.statements
+    }
+}
+
+impl NodeTrait for BlockStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for BlockStatement {
+    fn statement_node(&self) {}
+}
@@ -0,0 +1,35 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{Expression, NodeTrait, StatementTrait},
+    token::Token,
+};
+
+#[derive(Debug)]
+pub struct DeferStatement {
+    pub token: Token,
+    pub value: Expression,
+}
+
+impl Display for DeferStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {};", self.token_literal(), self.value)
+    }
+}
+
+impl PartialEq for DeferStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl NodeTrait for DeferStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for DeferStatement {
+    fn statement_node(&self) {}
+}
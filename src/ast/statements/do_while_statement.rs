@@ -0,0 +1,40 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{statements::BlockStatement, Expression, NodeTrait, StatementTrait},
+    token::{Span, Token},
+};
+
+/// `do { <body> } while (<condition>);`, which runs `body` once before
+/// checking `condition`.
+#[derive(Debug)]
+pub struct DoWhileStatement {
+    pub token: Token,
+    pub body: BlockStatement,
+    pub condition: Expression,
+    /// The span from the `do` keyword to the closing parenthesis
+    pub span: Span,
+}
+
+impl Display for DoWhileStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "do {}while{};", self.body, self.condition)
+    }
+}
+
+impl PartialEq for DoWhileStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.body == other.body && self.condition == other.condition
+    }
+}
+
+impl NodeTrait for DoWhileStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for DoWhileStatement {
+    fn statement_node(&self) {}
+}
@@ -0,0 +1,76 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{expressions::IdentExpression, NodeTrait, StatementTrait},
+    token::{Span, Token},
+};
+
+/// `enum <name> { <variant>, <variant>(<fields>) }`. Variants are
+/// constructed through the enum, e.g. `Shape.Rect(1, 2)`.
+#[derive(Debug)]
+pub struct EnumStatement {
+    pub token: Token,
+    pub name: IdentExpression,
+    pub variants: Vec<EnumVariant>,
+    /// The span from the `enum` keyword to the closing brace
+    pub span: Span,
+}
+
+/// A variant of an enum, with the names of the values it carries.
+#[derive(Debug)]
+pub struct EnumVariant {
+    pub name: IdentExpression,
+    pub fields: Vec<IdentExpression>,
+}
+
+impl Display for EnumStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let variants: Vec<String> = self.variants.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "{} {} {{{}}}",
+            self.token_literal(),
+            self.name,
+            variants.join(", ")
+        )
+    }
+}
+
+impl Display for EnumVariant {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.fields.is_empty() {
+            return write!(f, "{}", self.name);
+        }
+
+        let fields: Vec<String> = self.fields.iter().map(ToString::to_string).collect();
+        write!(f, "{}({})", self.name, fields.join(", "))
+    }
+}
+
+impl PartialEq for EnumStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.variants == other.variants
+    }
+}
+
+impl PartialEq for EnumVariant {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.fields == other.fields
+    }
+}
+
+impl NodeTrait for EnumStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for EnumStatement {
+    fn statement_node(&self) {}
+}
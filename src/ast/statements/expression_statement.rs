@@ -1,21 +1,29 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use crate::{
     ast::{Expression, NodeTrait, StatementTrait},
     token::Token,
 };
 
+#[derive(Debug)]
 pub struct ExpressionStatement {
     pub token: Token,
     pub expression: Expression,
 }
 
 impl Display for ExpressionStatement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.expression)
     }
 }
 
+impl PartialEq for ExpressionStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.expression == other.expression
+    }
+}
+
 impl NodeTrait for ExpressionStatement {
     fn token_literal(&self) -> &str {
         &self.token.literal
@@ -0,0 +1,55 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{
+        expressions::IdentExpression, statements::BlockStatement, Expression, NodeTrait,
+        StatementTrait,
+    },
+    token::Token,
+};
+
+/// `for (<bindings> in <iterable>) { <body> }`, where `bindings` is either
+/// a single name, e.g. the elements of an array, or a `key, value` pair.
+#[derive(Debug)]
+pub struct ForInStatement {
+    pub token: Token,
+    pub bindings: Vec<IdentExpression>,
+    pub iterable: Expression,
+    pub body: BlockStatement,
+}
+
+impl Display for ForInStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let bindings: Vec<String> = self.bindings.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "for({} in {}) {}",
+            bindings.join(", "),
+            self.iterable,
+            self.body
+        )
+    }
+}
+
+impl PartialEq for ForInStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.bindings == other.bindings
+            && self.iterable == other.iterable
+            && self.body == other.body
+    }
+}
+
+impl NodeTrait for ForInStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for ForInStatement {
+    fn statement_node(&self) {}
+}
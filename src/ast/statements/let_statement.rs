@@ -1,18 +1,36 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use crate::{
     ast::{expressions::IdentExpression, Expression, NodeTrait, StatementTrait},
-    token::Token,
+    token::{Token, TokenType},
 };
 
+/// A `let` or `const` binding, told apart by their token.
+#[derive(Debug)]
 pub struct LetStatement {
     pub token: Token,
     pub name: IdentExpression,
     pub value: Expression,
 }
 
+impl LetStatement {
+    /// Checks if the binding was declared with `const`
+    pub fn is_const(&self) -> bool {
+        self.token.token_type == TokenType::Const
+    }
+
+    /// Returns the keyword that declares this kind of binding
+    pub fn keyword(&self) -> &str {
+        if self.is_const() {
+            "const"
+        } else {
+            "let"
+        }
+    }
+}
+
 impl Display for LetStatement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "{} {} = {};",
@@ -23,6 +41,13 @@ impl Display for LetStatement {
     }
 }
 
+impl PartialEq for LetStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.is_const() == other.is_const() && self.name == other.name && self.value == other.value
+    }
+}
+
 impl NodeTrait for LetStatement {
     fn token_literal(&self) -> &str {
         &self.token.literal
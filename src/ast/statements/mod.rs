@@ -1,7 +1,21 @@
+mod block_statement;
+mod defer_statement;
+mod do_while_statement;
+mod enum_statement;
 mod expression_statement;
+mod for_in_statement;
 mod let_statement;
 mod return_statement;
+mod switch_statement;
+mod throw_statement;
 
+pub use block_statement::BlockStatement;
+pub use defer_statement::DeferStatement;
+pub use do_while_statement::DoWhileStatement;
+pub use enum_statement::{EnumStatement, EnumVariant};
 pub use expression_statement::ExpressionStatement;
+pub use for_in_statement::ForInStatement;
 pub use let_statement::LetStatement;
 pub use return_statement::ReturnStatement;
+pub use switch_statement::{SwitchCase, SwitchStatement};
+pub use throw_statement::ThrowStatement;
@@ -1,21 +1,29 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use crate::{
     ast::{Expression, NodeTrait, StatementTrait},
     token::Token,
 };
 
+#[derive(Debug)]
 pub struct ReturnStatement {
     pub token: Token,
     pub value: Expression,
 }
 
 impl Display for ReturnStatement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{} {};", self.token_literal(), self.value)
     }
 }
 
+impl PartialEq for ReturnStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
 impl NodeTrait for ReturnStatement {
     fn token_literal(&self) -> &str {
         &self.token.literal
@@ -23,7 +31,5 @@ impl NodeTrait for ReturnStatement {
 }
 
 impl StatementTrait for ReturnStatement {
-    fn statement_node(&self) {
-        todo!()
-    }
+    fn statement_node(&self) {}
 }
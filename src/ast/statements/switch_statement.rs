@@ -0,0 +1,76 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{
+    ast::{statements::BlockStatement, Expression, NodeTrait, StatementTrait},
+    token::{Span, Token},
+};
+
+/// `switch (<subject>) { case <values>: ... default: ... }`. Cases don't
+/// fall through, so only the body of the first matching case runs.
+#[derive(Debug)]
+pub struct SwitchStatement {
+    pub token: Token,
+    pub subject: Expression,
+    pub cases: Vec<SwitchCase>,
+    pub default: Option<BlockStatement>,
+    /// The span from the `switch` keyword to the closing brace
+    pub span: Span,
+}
+
+/// A `case <value>, <value>: <statements>` arm of a switch.
+#[derive(Debug)]
+pub struct SwitchCase {
+    /// The `case` token
+    pub token: Token,
+    pub values: Vec<Expression>,
+    /// The statements after the `:`, up to the next arm
+    pub body: BlockStatement,
+}
+
+impl Display for SwitchStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "switch{} {{", self.subject)?;
+        for case in self.cases.iter() {
+            write!(f, "{case}")?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, "default: {default}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for SwitchCase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let values: Vec<String> = self.values.iter().map(ToString::to_string).collect();
+        write!(f, "case {}: {}", values.join(", "), self.body)
+    }
+}
+
+impl PartialEq for SwitchStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.subject == other.subject && self.cases == other.cases && self.default == other.default
+    }
+}
+
+impl PartialEq for SwitchCase {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values && self.body == other.body
+    }
+}
+
+impl NodeTrait for SwitchStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for SwitchStatement {
+    fn statement_node(&self) {}
+}
@@ -0,0 +1,35 @@
+use core::fmt::Display;
+
+use crate::{
+    ast::{Expression, NodeTrait, StatementTrait},
+    token::Token,
+};
+
+#[derive(Debug)]
+pub struct ThrowStatement {
+    pub token: Token,
+    pub value: Expression,
+}
+
+impl Display for ThrowStatement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {};", self.token_literal(), self.value)
+    }
+}
+
+impl PartialEq for ThrowStatement {
+    /// Compares the structure of the nodes, ignoring their tokens.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl NodeTrait for ThrowStatement {
+    fn token_literal(&self) -> &str {
+        &self.token.literal
+    }
+}
+
+impl StatementTrait for ThrowStatement {
+    fn statement_node(&self) {}
+}
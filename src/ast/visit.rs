@@ -0,0 +1,158 @@
+//! Traversal of the AST.
+//!
+//! Implementors of [`Visitor`] override the methods for the nodes they're
+//! interested in, and call the matching `walk_*` function to keep visiting
+//! the children of the node.
+
+use crate::ast::{statements::BlockStatement, Expression, Program, Statement};
+
+pub trait Visitor {
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_block(&mut self, block: &BlockStatement) {
+        walk_block(self, block);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Program) {
+    for stmt in program.statements.iter() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, stmt: &Statement) {
+    match stmt {
+        Statement::Let(s) => visitor.visit_expression(&s.value),
+        Statement::Return(s) => visitor.visit_expression(&s.value),
+        Statement::Throw(s) => visitor.visit_expression(&s.value),
+        Statement::Defer(s) => visitor.visit_expression(&s.value),
+        Statement::Switch(s) => {
+            visitor.visit_expression(&s.subject);
+            for case in s.cases.iter() {
+                for value in case.values.iter() {
+                    visitor.visit_expression(value);
+                }
+                visitor.visit_block(&case.body);
+            }
+            if let Some(default) = &s.default {
+                visitor.visit_block(default);
+            }
+        }
+        Statement::DoWhile(s) => {
+            visitor.visit_block(&s.body);
+            visitor.visit_expression(&s.condition);
+        }
+        Statement::ForIn(s) => {
+            visitor.visit_expression(&s.iterable);
+            visitor.visit_block(&s.body);
+        }
+        Statement::Enum(_) => {}
+        Statement::Expression(s) => visitor.visit_expression(&s.expression),
+    }
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &BlockStatement) {
+    for stmt in block.statements.iter() {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Ident(_)
+        | Expression::Integer(_)
+        | Expression::Float(_)
+        | Expression::Boolean(_)
+        | Expression::Placeholder(_) => {}
+        Expression::Prefix(e) => visitor.visit_expression(&e.right),
+        Expression::Infix(e) => {
+            visitor.visit_expression(&e.left);
+            visitor.visit_expression(&e.right);
+        }
+        Expression::Pipe(e) => {
+            visitor.visit_expression(&e.left);
+            visitor.visit_expression(&e.right);
+        }
+        Expression::If(e) => {
+            visitor.visit_expression(&e.condition);
+            visitor.visit_block(&e.consequence);
+            if let Some(alternative) = &e.alternative {
+                visitor.visit_block(alternative);
+            }
+        }
+        Expression::Try(e) => {
+            visitor.visit_block(&e.body);
+            visitor.visit_block(&e.handler);
+        }
+        Expression::Function(e) => visitor.visit_block(&e.body),
+        Expression::Member(e) => visitor.visit_expression(&e.object),
+        Expression::Set(e) => {
+            for element in e.elements.iter() {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::Array(e) => {
+            for element in e.elements.iter() {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::Spread(e) => visitor.visit_expression(&e.value),
+        Expression::Call(e) => {
+            visitor.visit_expression(&e.function);
+            for argument in e.arguments.iter() {
+                visitor.visit_expression(argument);
+            }
+            for argument in e.named_arguments.iter() {
+                visitor.visit_expression(&argument.value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    /// Counts the identifiers and blocks of a program
+    #[derive(Default)]
+    struct Counter {
+        idents: usize,
+        blocks: usize,
+    }
+
+    impl Visitor for Counter {
+        fn visit_block(&mut self, block: &BlockStatement) {
+            self.blocks += 1;
+            walk_block(self, block);
+        }
+
+        fn visit_expression(&mut self, expression: &Expression) {
+            if let Expression::Ident(_) = expression {
+                self.idents += 1;
+            }
+            walk_expression(self, expression);
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        let program = Parser::parse("let a = b; if (c) { -d } else { return e + f; }").unwrap();
+
+        let mut counter = Counter::default();
+        counter.visit_program(&program);
+
+        assert_eq!(counter.idents, 5);
+        assert_eq!(counter.blocks, 2);
+    }
+}
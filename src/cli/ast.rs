@@ -0,0 +1,114 @@
+use std::{fs, io};
+
+use writing_an_interpreter_book::{
+    analyzer::CallGraph, ast::Program, events::JsonLinesSubscriber, lexer::Lexer, parser::Parser,
+};
+
+/// The representations `monkey ast` can print.
+enum Format {
+    Pretty,
+    Sexpr,
+    Json,
+    Dot,
+    Tree,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "pretty" => Some(Format::Pretty),
+            "sexpr" => Some(Format::Sexpr),
+            "json" => Some(Format::Json),
+            "dot" => Some(Format::Dot),
+            "tree" => Some(Format::Tree),
+            _ => None,
+        }
+    }
+
+    fn render(&self, program: &Program) -> String {
+        match self {
+            Format::Pretty => program.to_pretty_string(),
+            Format::Sexpr => program.to_sexpr(),
+            Format::Json => program.to_json(),
+            Format::Dot => program.to_dot(),
+            Format::Tree => program.to_tree_string(),
+        }
+    }
+}
+
+/// `monkey ast FILE [--format pretty|sexpr|json|dot|tree] [--callgraph]
+/// [--events jsonl]`
+///
+/// With `--callgraph`, the call graph of the functions of FILE is printed
+/// in the Graphviz format instead of the tree. With `--events jsonl`, the
+/// events of parsing FILE are written to stderr as JSON lines.
+pub fn run(args: &[String]) -> i32 {
+    let callgraph = args.iter().any(|arg| arg == "--callgraph");
+    let args = args.iter().filter(|arg| *arg != "--callgraph");
+    let parsed = super::split_option(args, "format").and_then(|(format, args)| {
+        let (events, positional) = super::split_option(args, "events")?;
+        Ok((format, events, positional))
+    });
+    let (format, events, positional) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 2;
+        }
+    };
+
+    match events {
+        Some("jsonl") | None => {}
+        Some(name) => {
+            eprintln!("Error: unknown events format \"{name}\"");
+            return 2;
+        }
+    }
+
+    let format = match format {
+        Some(name) if callgraph && name != "dot" => {
+            eprintln!("Error: the call graph can only be printed as dot, not {name}");
+            return 2;
+        }
+        Some(name) => match Format::from_name(name) {
+            Some(format) => format,
+            None => {
+                eprintln!("Error: unknown format \"{name}\"");
+                return 2;
+            }
+        },
+        None => Format::Pretty,
+    };
+
+    let [path] = positional[..] else {
+        eprintln!("Error: expected exactly one FILE argument");
+        return 2;
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let mut parser = Parser::new(Lexer::new(&source));
+    if events.is_some() {
+        parser.set_subscriber(JsonLinesSubscriber::new(io::stderr()));
+    }
+    let program = parser.parse_program();
+    if !parser.errors().is_empty() {
+        for error in parser.errors().iter() {
+            eprintln!("{path}: parser error: {error}");
+        }
+        return 1;
+    }
+
+    if callgraph {
+        print!("{}", CallGraph::new(&program).to_dot());
+    } else {
+        print!("{}", format.render(&program));
+    }
+    0
+}
@@ -0,0 +1,92 @@
+use std::fs;
+
+use writing_an_interpreter_book::{
+    ast::Program,
+    codegen::{self, CodegenError, SourceMap},
+    parser::Parser,
+};
+
+/// The languages `monkey build` can compile to.
+enum Target {
+    Wat,
+}
+
+impl Target {
+    fn from_name(name: &str) -> Option<Target> {
+        match name {
+            "wat" => Some(Target::Wat),
+            _ => None,
+        }
+    }
+
+    fn compile(&self, program: &Program) -> Result<(String, SourceMap), CodegenError> {
+        match self {
+            Target::Wat => codegen::wat::compile_with_source_map(program),
+        }
+    }
+}
+
+/// `monkey build FILE [--emit wat] [--source-map PATH]`
+pub fn run(args: &[String]) -> i32 {
+    let parsed = super::split_option(args, "source-map").and_then(|(map_path, args)| {
+        let (target, positional) = super::split_option(args, "emit")?;
+        Ok((map_path, target, positional))
+    });
+    let (map_path, target, positional) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 2;
+        }
+    };
+
+    let target = match target.map(Target::from_name).unwrap_or(Some(Target::Wat)) {
+        Some(target) => target,
+        None => {
+            eprintln!("Error: unknown target \"{}\"", target.unwrap_or_default());
+            return 2;
+        }
+    };
+
+    let [path] = positional[..] else {
+        eprintln!("Error: expected exactly one FILE argument");
+        return 2;
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let program = match Parser::parse(&source) {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in errors.iter() {
+                eprintln!("{path}: parser error: {error}");
+            }
+            return 1;
+        }
+    };
+
+    match target.compile(&program) {
+        Ok((output, source_map)) => {
+            if let Some(map_path) = map_path {
+                let json = source_map.to_json(&output, path, &source);
+                if let Err(e) = fs::write(map_path, json + "\n") {
+                    eprintln!("Error: could not write {map_path}: {e}");
+                    return 2;
+                }
+            }
+            print!("{output}");
+            0
+        }
+        Err(error) => {
+            let (line, col) = error.span.location(&source);
+            eprintln!("{path}:{line}:{col}: error: {error}");
+            1
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::fs;
+
+use writing_an_interpreter_book::{
+    analyzer::{self, Diagnostic, SeverityConfig},
+    parser::Parser,
+};
+
+/// How `monkey check` reports diagnostics.
+enum ErrorFormat {
+    /// `path:line:col: severity[code]: message` lines for humans
+    Text,
+    /// One JSON object per line, for editors and CI
+    Json,
+}
+
+impl ErrorFormat {
+    fn from_name(name: &str) -> Option<ErrorFormat> {
+        match name {
+            "text" => Some(ErrorFormat::Text),
+            "json" => Some(ErrorFormat::Json),
+            _ => None,
+        }
+    }
+
+    fn render(&self, diagnostic: &Diagnostic, path: &str, source: &str) -> String {
+        match self {
+            ErrorFormat::Text => {
+                let (line, col) = diagnostic.span.location(source);
+                format!(
+                    "{path}:{line}:{col}: {}[{}]: {}",
+                    diagnostic.severity.name(),
+                    diagnostic.code,
+                    diagnostic.message
+                )
+            }
+            ErrorFormat::Json => diagnostic.to_json(path, source),
+        }
+    }
+}
+
+/// Reads the `--deny` and `--allow` options, each a comma separated list
+/// of codes, returning the remaining arguments.
+fn parse_severities(args: &[String]) -> Result<(SeverityConfig, Vec<&str>), String> {
+    let (deny, args) = super::split_option(args, "deny")?;
+    let (allow, args) = super::split_option(args, "allow")?;
+
+    let mut config = SeverityConfig::default();
+    for code in deny.into_iter().flat_map(|codes| codes.split(',')) {
+        config.deny(code.trim())?;
+    }
+    for code in allow.into_iter().flat_map(|codes| codes.split(',')) {
+        config.allow(code.trim())?;
+    }
+
+    Ok((config, args))
+}
+
+/// `monkey check FILE [--error-format text|json] [--deny CODES] [--allow CODES]`
+pub fn run(args: &[String]) -> i32 {
+    let parsed = parse_severities(args).and_then(|(config, args)| {
+        let (format, positional) = super::split_option(args, "error-format")?;
+        Ok((config, format, positional))
+    });
+    let (config, format, positional) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 2;
+        }
+    };
+
+    let format = match format
+        .map(ErrorFormat::from_name)
+        .unwrap_or(Some(ErrorFormat::Text))
+    {
+        Some(format) => format,
+        None => {
+            eprintln!(
+                "Error: unknown error format \"{}\"",
+                format.unwrap_or_default()
+            );
+            return 2;
+        }
+    };
+
+    let [path] = positional[..] else {
+        eprintln!("Error: expected exactly one FILE argument");
+        return 2;
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let program = match Parser::parse(&source) {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in errors.iter() {
+                let rendered = format.render(&Diagnostic::from(error), path, &source);
+                // Structured records all go to stdout, so they can be piped
+                match format {
+                    ErrorFormat::Text => eprintln!("{rendered}"),
+                    ErrorFormat::Json => println!("{rendered}"),
+                }
+            }
+            return 1;
+        }
+    };
+
+    let diagnostics = analyzer::analyze_with(&program, &config);
+    for diagnostic in diagnostics.iter() {
+        println!("{}", format.render(diagnostic, path, &source));
+    }
+
+    let has_errors = diagnostics
+        .iter()
+        .any(|d| d.severity == analyzer::Severity::Error);
+    i32::from(has_errors)
+}
@@ -0,0 +1,79 @@
+use std::{fs, path::Path};
+
+use writing_an_interpreter_book::{
+    doc::{self, ModuleDoc},
+    lexer::Lexer,
+    parser::Parser,
+};
+
+/// `monkey doc PATH... [--format markdown|html]`
+///
+/// Prints the documentation of the functions bound at the top level of the
+/// given files, or of the `.monkey` files under the given directories.
+pub fn run(args: &[String]) -> i32 {
+    let (format, paths) = match super::split_option(args, "format") {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 2;
+        }
+    };
+
+    let render = match format.unwrap_or("markdown") {
+        "markdown" => doc::to_markdown,
+        "html" => doc::to_html,
+        other => {
+            eprintln!("Error: unknown format \"{other}\", expected markdown or html");
+            return 2;
+        }
+    };
+
+    if paths.is_empty() {
+        eprintln!("Error: expected at least one file or directory");
+        return 2;
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        if let Err(e) = super::collect_files(Path::new(path), &mut files) {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    }
+
+    let mut modules = Vec::new();
+    let mut failed = false;
+    for file in files.iter() {
+        let name = file.display().to_string();
+        let source = match fs::read_to_string(file) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Error: could not read {name}: {e}");
+                return 2;
+            }
+        };
+
+        let mut parser = Parser::new(Lexer::new(&source));
+        parser.keep_comments();
+        let program = parser.parse_program();
+        if !parser.errors().is_empty() {
+            for error in parser.errors().iter() {
+                eprintln!("{name}: parser error: {error}");
+            }
+            failed = true;
+            continue;
+        }
+
+        modules.push(ModuleDoc {
+            functions: doc::extract(&program, &source),
+            name,
+        });
+    }
+
+    if failed {
+        return 1;
+    }
+
+    print!("{}", render(&modules));
+    0
+}
@@ -0,0 +1,68 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use writing_an_interpreter_book::{config::Config, lexer::Lexer, parser::Parser};
+
+/// `monkey fmt FILE [--config PATH]`
+///
+/// Prints FILE pretty-printed in the style of the `[format]` table of the
+/// given settings file, or of the `monkey.toml` closest to FILE, keeping
+/// its comments.
+pub fn run(args: &[String]) -> i32 {
+    let (config_path, positional) = match super::split_option(args, "config") {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 2;
+        }
+    };
+
+    let [path] = positional[..] else {
+        eprintln!("Error: expected exactly one FILE argument");
+        return 2;
+    };
+
+    let config_path = config_path.map(PathBuf::from).or_else(|| {
+        let file = Path::new(path).canonicalize().ok()?;
+        Config::find(file.parent()?)
+    });
+    let config = match config_path {
+        Some(config_path) => match fs::read_to_string(&config_path) {
+            Ok(contents) => match Config::parse(&contents) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("{}:{}: error: {}", config_path.display(), e.line, e.message);
+                    return 2;
+                }
+            },
+            Err(e) => {
+                eprintln!("Error: could not read {}: {e}", config_path.display());
+                return 2;
+            }
+        },
+        None => Config::default(),
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let mut parser = Parser::new(Lexer::new(&source));
+    parser.keep_comments();
+    let program = parser.parse_program();
+    if !parser.errors().is_empty() {
+        for error in parser.errors().iter() {
+            eprintln!("{path}: parser error: {error}");
+        }
+        return 1;
+    }
+
+    print!("{}", program.to_pretty_string_with(&config.format));
+    0
+}
@@ -0,0 +1,128 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+};
+
+use writing_an_interpreter_book::{
+    learn::{self, Check, Progress, CHAPTERS},
+    parser::Parser,
+    spec::Outcome,
+};
+
+/// Where progress is saved when no `--state` is given.
+const DEFAULT_STATE_PATH: &str = ".monkey-learn";
+
+/// `monkey learn [--state PATH]`
+///
+/// Walks through the lessons not completed yet, saving progress to the
+/// state file after each one.
+pub fn run(args: &[String]) -> i32 {
+    let (path, positional) = match super::split_option(args, "state") {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 2;
+        }
+    };
+    if !positional.is_empty() {
+        eprintln!("Error: unexpected argument \"{}\"", positional[0]);
+        return 2;
+    }
+    let path = path.unwrap_or(DEFAULT_STATE_PATH);
+
+    let mut progress = match fs::read_to_string(path) {
+        Ok(state) => Progress::from_state(&state),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Progress::default(),
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let stdin = io::stdin();
+    match learn(&mut progress, path, &mut stdin.lock(), &mut io::stdout()) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    }
+}
+
+fn learn(
+    progress: &mut Progress,
+    path: &str,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "Type your answer after the prompt, :skip to skip a lesson or :quit to stop.\n\
+         Progress is saved to {path}."
+    )?;
+
+    for chapter in CHAPTERS {
+        for (i, lesson) in chapter.lessons.iter().enumerate() {
+            if progress.is_completed(chapter, lesson) {
+                continue;
+            }
+            writeln!(
+                output,
+                "\n== {} ({}/{}) ==\n{}",
+                chapter.title,
+                i + 1,
+                chapter.lessons.len(),
+                lesson.prompt
+            )?;
+
+            loop {
+                write!(output, ">> ")?;
+                output.flush()?;
+                let mut answer = String::new();
+                if input.read_line(&mut answer)? == 0 {
+                    return Ok(());
+                }
+                let answer = answer.trim();
+                match answer {
+                    "" => continue,
+                    ":quit" => return Ok(()),
+                    ":skip" => break,
+                    _ => {}
+                }
+
+                show_stage(&lesson.check, answer, output)?;
+                match lesson.check(answer) {
+                    Outcome::Passed => {
+                        writeln!(output, "Correct!")?;
+                        progress.complete(chapter, lesson);
+                        fs::write(path, progress.to_state())?;
+                        break;
+                    }
+                    Outcome::Failed(reason) => writeln!(output, "Not quite: {reason}")?,
+                    Outcome::Skipped(reason) => {
+                        writeln!(output, "This lesson can't be checked yet ({reason})")?;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    writeln!(output, "\nThat's all the lessons for now.")
+}
+
+/// Prints what the stage checked by the lesson makes of the answer, so
+/// mistakes can be spotted.
+fn show_stage(check: &Check, answer: &str, output: &mut impl Write) -> io::Result<()> {
+    match check {
+        Check::Tokens(_) => {
+            let tokens = learn::tokens(answer);
+            let literals: Vec<String> = tokens.iter().map(|t| format!("`{}`", t.literal)).collect();
+            writeln!(output, "tokens: {}", literals.join(" "))
+        }
+        Check::Sexpr(_) => match Parser::parse(answer) {
+            Ok(program) => write!(output, "tree: {}", program.to_sexpr()),
+            Err(_) => Ok(()),
+        },
+    }
+}
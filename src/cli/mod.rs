@@ -0,0 +1,145 @@
+//! Command line interface of the `monkey` binary.
+
+mod ast;
+mod build;
+mod check;
+mod doc;
+mod fmt;
+mod learn;
+mod rename;
+mod spec;
+mod stats;
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::repl;
+
+const USAGE: &str = "Usage: monkey [COMMAND]
+
+Commands:
+  (none)                 Start the REPL
+  ast FILE [--format F]  Print the AST of FILE, where F is one of
+                         pretty (default), sexpr, json, dot or tree
+  ast FILE --callgraph   Print the calls between the functions of FILE
+                         as a Graphviz graph, with recursive functions
+                         in bold and unreachable ones dashed
+  ast FILE --events jsonl
+                         Also write the events of parsing FILE to
+                         stderr, one JSON object per line
+  build FILE [--emit wat] [--source-map PATH]
+                         Compile the integer and boolean program in
+                         FILE to the WebAssembly text format, writing
+                         a source map of the output to PATH
+  check FILE [--error-format F] [--deny CODES] [--allow CODES]
+                         Report likely mistakes in FILE, such as
+                         unreachable code, where F is text (default)
+                         or json. CODES is a comma separated list of
+                         diagnostic codes to report as errors or to
+                         silence; --deny warnings denies them all
+  doc PATH... [--format F]
+                         Print the documentation of the functions of
+                         the given files or directories, from their
+                         /// comments, where F is markdown (default)
+                         or html
+  fmt FILE [--config PATH]
+                         Print FILE formatted in the style set by the
+                         settings file PATH, or by the monkey.toml in
+                         the directory of FILE or its ancestors
+  learn [--state PATH]   Start a guided tutorial of the lexer and parser,
+                         saving progress to PATH
+                         (.monkey-learn by default)
+  rename FILE LINE:COLUMN NEW_NAME
+                         Print FILE with the name at LINE:COLUMN and
+                         its uses renamed to NEW_NAME, unless that
+                         would change what a name refers to
+  spec PATH...           Run the spec cases in the given files or
+                         directories
+  stats FILE             Print the statement counts, function
+                         complexities, nesting depth and longest
+                         call chain of FILE
+  help                   Print this message";
+
+/// Runs the command given by `args` (excluding the program name) and
+/// returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        None => {
+            repl::start();
+            0
+        }
+        Some("ast") => ast::run(&args[1..]),
+        Some("build") => build::run(&args[1..]),
+        Some("check") => check::run(&args[1..]),
+        Some("doc") => doc::run(&args[1..]),
+        Some("fmt") => fmt::run(&args[1..]),
+        Some("learn") => learn::run(&args[1..]),
+        Some("rename") => rename::run(&args[1..]),
+        Some("spec") => spec::run(&args[1..]),
+        Some("stats") => stats::run(&args[1..]),
+        Some("help" | "-h" | "--help") => {
+            println!("{USAGE}");
+            0
+        }
+        Some(command) => {
+            eprintln!("Unknown command \"{command}\"\n\n{USAGE}");
+            2
+        }
+    }
+}
+
+/// Returns the value of a `--name value` or `--name=value` option, along
+/// with the remaining positional arguments.
+fn split_option<'a, S: AsRef<str> + ?Sized + 'a>(
+    args: impl IntoIterator<Item = &'a S>,
+    name: &str,
+) -> Result<(Option<&'a str>, Vec<&'a str>), String> {
+    let flag = format!("--{name}");
+    let prefix = format!("--{name}=");
+    let mut value = None;
+    let mut positional = Vec::new();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let arg = arg.as_ref();
+        if arg == flag {
+            match iter.next() {
+                Some(v) => value = Some(v.as_ref()),
+                None => return Err(format!("missing value for {flag}")),
+            }
+        } else if let Some(v) = arg.strip_prefix(&prefix) {
+            value = Some(v);
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    Ok((value, positional))
+}
+
+/// Adds `path` to `files` if it's a file, or every `.monkey` file under it,
+/// in sorted order, if it's a directory.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !path.is_dir() {
+        fs::metadata(path)?;
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, files)?;
+        } else if entry.extension().is_some_and(|ext| ext == "monkey") {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::fs;
+
+use writing_an_interpreter_book::{parser::Parser, refactor};
+
+/// `monkey rename FILE LINE:COLUMN NEW_NAME`
+///
+/// Prints FILE with the name at LINE:COLUMN, where it's bound and every use
+/// of it, renamed to NEW_NAME.
+pub fn run(args: &[String]) -> i32 {
+    let [path, position, new_name] = args else {
+        eprintln!("Error: expected FILE LINE:COLUMN NEW_NAME");
+        return 2;
+    };
+
+    let Some((line, column)) = position
+        .split_once(':')
+        .and_then(|(line, column)| Some((line.parse().ok()?, column.parse().ok()?)))
+    else {
+        eprintln!("Error: expected a position like 3:14, got \"{position}\"");
+        return 2;
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let Some(offset) = offset_at(&source, line, column) else {
+        eprintln!("Error: {path} has no position {line}:{column}");
+        return 2;
+    };
+
+    let program = match Parser::parse(&source) {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in errors.iter() {
+                eprintln!("{path}: parser error: {error}");
+            }
+            return 1;
+        }
+    };
+
+    match refactor::rename(&program, offset, new_name) {
+        Ok(edits) => {
+            print!("{}", refactor::apply(&source, &edits));
+            0
+        }
+        Err(e) => {
+            let (line, column) = e.span.location(&source);
+            eprintln!("{path}:{line}:{column}: error: {e}");
+            1
+        }
+    }
+}
+
+/// Returns the offset of the 1-based `line` and `column` in `source`, with
+/// columns counted in characters like [`Span::location`]. The end of a
+/// line is a position too.
+///
+/// [`Span::location`]: writing_an_interpreter_book::token::Span::location
+fn offset_at(source: &str, line: usize, column: usize) -> Option<usize> {
+    let mut line_start = 0;
+    for (i, text) in source.split('\n').enumerate() {
+        if i + 1 == line {
+            return text
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([text.len()])
+                .nth(column.checked_sub(1)?)
+                .map(|i| line_start + i);
+        }
+        line_start += text.len() + 1;
+    }
+    None
+}
@@ -0,0 +1,61 @@
+use std::{fs, path::Path};
+
+use writing_an_interpreter_book::spec::{parse_spec, Outcome};
+
+/// `monkey spec PATH...`
+///
+/// Runs every spec case found in the given files, or in the `.monkey`
+/// files under the given directories.
+pub fn run(args: &[String]) -> i32 {
+    if args.is_empty() {
+        eprintln!("Error: expected at least one spec file or directory");
+        return 2;
+    }
+
+    let mut files = Vec::new();
+    for arg in args {
+        if let Err(e) = super::collect_files(Path::new(arg), &mut files) {
+            eprintln!("Error: could not read {arg}: {e}");
+            return 2;
+        }
+    }
+
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+
+    for file in files.iter() {
+        let contents = match fs::read_to_string(file) {
+            Ok(contents) => contents,
+            Err(e) => {
+                eprintln!("Error: could not read {}: {e}", file.display());
+                return 2;
+            }
+        };
+
+        let file_name = file.display().to_string();
+        for case in parse_spec(&file_name, &contents) {
+            let location = format!("{}:{}: {}", file_name, case.line, case.name);
+            match case.run() {
+                Outcome::Passed => {
+                    passed += 1;
+                    println!("{location} ... ok");
+                }
+                Outcome::Failed(reason) => {
+                    failed += 1;
+                    println!("{location} ... FAILED\n    {reason}");
+                }
+                Outcome::Skipped(reason) => {
+                    skipped += 1;
+                    println!("{location} ... skipped ({reason})");
+                }
+            }
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {skipped} skipped");
+
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs;
+
+use writing_an_interpreter_book::{analyzer::Metrics, parser::Parser};
+
+/// `monkey stats FILE`
+///
+/// Prints the number of statements of each kind in FILE, the complexity of
+/// each of its functions, its deepest nesting and its longest chain of
+/// calls and member accesses.
+pub fn run(args: &[String]) -> i32 {
+    let [path] = args else {
+        eprintln!("Error: expected exactly one FILE argument");
+        return 2;
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error: could not read {path}: {e}");
+            return 2;
+        }
+    };
+
+    let program = match Parser::parse(&source) {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in errors.iter() {
+                eprintln!("{path}: parser error: {error}");
+            }
+            return 1;
+        }
+    };
+
+    let metrics = Metrics::new(&program);
+    println!("statements: {}", metrics.statement_count());
+    for (kind, count) in metrics.statements.iter() {
+        println!("  {kind}: {count}");
+    }
+    println!("functions: {}", metrics.functions.len());
+    for function in metrics.functions.iter() {
+        let (line, column) = function.span.location(&source);
+        println!(
+            "  {} ({path}:{line}:{column}): complexity {}",
+            function.name.as_deref().unwrap_or("(anonymous)"),
+            function.complexity
+        );
+    }
+    println!("max nesting depth: {}", metrics.max_depth);
+    println!("longest chain: {}", metrics.longest_chain);
+    0
+}
@@ -0,0 +1,36 @@
+//! Backends compiling parsed programs to other languages.
+
+mod source_map;
+pub mod wat;
+
+pub use source_map::SourceMap;
+
+use std::{error::Error, fmt::Display};
+
+use crate::token::Span;
+
+/// A program a backend can't compile: it uses a construct the backend
+/// doesn't support, or has a type error found before running it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenError {
+    pub message: String,
+    /// The span of the node the error was found at
+    pub span: Span,
+}
+
+impl CodegenError {
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for CodegenError {}
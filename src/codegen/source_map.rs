@@ -0,0 +1,122 @@
+use crate::{ast::printers::json::write_string, token::Span};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Maps each line of a backend's output to the span of the node it was
+/// compiled from, so errors in the generated code can be reported
+/// against the Monkey source.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SourceMap {
+    lines: Vec<Option<Span>>,
+}
+
+impl SourceMap {
+    /// Returns the span the output line `line`, starting at 1, was
+    /// compiled from. Lines that weren't compiled from a node, such as
+    /// declarations, have no span.
+    pub fn span(&self, line: usize) -> Option<Span> {
+        self.lines.get(line.checked_sub(1)?).copied().flatten()
+    }
+
+    /// Serializes the map in the [Source Map v3] format, mapping the start
+    /// of each line of `output` to the start of its span in `source`, the
+    /// contents of `file`.
+    ///
+    /// [Source Map v3]: https://sourcemaps.info/spec.html
+    pub fn to_json(&self, output: &str, file: &str, source: &str) -> String {
+        let mut mappings = String::new();
+        // Apart from the output column, fields are relative to the
+        // previous segment in the whole map
+        let (mut prev_line, mut prev_column) = (0, 0);
+
+        for (i, (span, text)) in self.lines.iter().zip(output.lines()).enumerate() {
+            if i > 0 {
+                mappings.push(';');
+            }
+            let Some(span) = span else {
+                continue;
+            };
+
+            let (line, column) = span.location(source);
+            let (line, column) = (line as i64 - 1, column as i64 - 1);
+            let indent = text.len() - text.trim_start().len();
+
+            write_vlq(&mut mappings, indent as i64);
+            // Index of the only source
+            write_vlq(&mut mappings, 0);
+            write_vlq(&mut mappings, line - prev_line);
+            write_vlq(&mut mappings, column - prev_column);
+            (prev_line, prev_column) = (line, column);
+        }
+
+        let mut out = String::from("{\"version\": 3, \"sources\": [");
+        write_string(&mut out, file);
+        out.push_str("], \"names\": [], \"mappings\": ");
+        write_string(&mut out, &mappings);
+        out.push('}');
+        out
+    }
+}
+
+impl FromIterator<Option<Span>> for SourceMap {
+    fn from_iter<T: IntoIterator<Item = Option<Span>>>(iter: T) -> Self {
+        Self {
+            lines: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Writes `value` as a base 64 variable length quantity, in groups of 5
+/// bits starting with the lowest ones, with the sign as the lowest bit.
+fn write_vlq(out: &mut String, value: i64) {
+    let mut vlq = (value.unsigned_abs() << 1) | u64::from(value < 0);
+    loop {
+        let digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+        let continuation = if vlq > 0 { 0b100000 } else { 0 };
+        out.push(BASE64[digit | continuation] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_vlq() {
+        let tests = [
+            (0, "A"),
+            (1, "C"),
+            (-1, "D"),
+            (15, "e"),
+            (16, "gB"),
+            (-100, "pG"),
+        ];
+
+        for (value, expected) in tests {
+            let mut out = String::new();
+            write_vlq(&mut out, value);
+            assert_eq!(out, expected, "encoding {value}");
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        let source = "let a = 1;\nlet b = a;";
+        let output = "(module\n  global.get $a\n  global.set $b\n)\n";
+        let map: SourceMap = [None, Some(Span::new(19, 20)), Some(Span::new(11, 21)), None]
+            .into_iter()
+            .collect();
+
+        assert_eq!(map.span(2), Some(Span::new(19, 20)));
+        assert_eq!(map.span(4), None);
+        assert_eq!(map.span(0), None);
+        assert_eq!(
+            map.to_json(output, "main.monkey", source),
+            r#"{"version": 3, "sources": ["main.monkey"], "names": [], "mappings": ";EACQ;EAAR;"}"#
+        );
+    }
+}
@@ -0,0 +1,1009 @@
+//! An experimental backend compiling integer and boolean programs to the
+//! WebAssembly text format.
+//!
+//! Integers compile to `i64` values and booleans to `i32` ones. Functions
+//! bound by top level `let`s become module functions, exported under
+//! their name, and the other top level statements become an exported
+//! `main` function, returning the value of the last one. Top level
+//! bindings become globals, and bindings inside functions locals.
+//!
+//! Since the values have no runtime tags, types are checked while
+//! compiling, reporting the errors the evaluator would give. Parameters
+//! are always integers, and the result type of each function is inferred
+//! from its body. Division by zero traps.
+
+use crate::{
+    ast::{
+        expressions::{FunctionLiteral, IdentExpression, IfExpression, NamedArgument},
+        patterns::Pattern,
+        statements::{BlockStatement, LetStatement},
+        Expression, Program, Statement,
+    },
+    codegen::{CodegenError, SourceMap},
+    token::Span,
+};
+
+/// Compiles `program` to a WebAssembly text module.
+pub fn compile(program: &Program) -> Result<String, CodegenError> {
+    compile_with_source_map(program).map(|(module, _)| module)
+}
+
+/// Compiles `program` to a WebAssembly text module, along with a map from
+/// each instruction to the node it was compiled from.
+pub fn compile_with_source_map(program: &Program) -> Result<(String, SourceMap), CodegenError> {
+    let mut compiler = Compiler::new(program)?;
+
+    // Each pass can infer the types depending on those inferred by the
+    // previous one, until nothing changes
+    let lines = loop {
+        let (lines, changed) = compiler.compile_pass()?;
+        if !changed {
+            compiler.check_inferred()?;
+            break lines;
+        }
+    };
+
+    let module = lines.iter().map(|(line, _)| format!("{line}\n")).collect();
+    let source_map = lines.into_iter().map(|(_, span)| span).collect();
+    Ok((module, source_map))
+}
+
+/// Lines of the module, with the span of the node each was compiled from
+type Lines = Vec<(String, Option<Span>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Type {
+    Int,
+    Bool,
+}
+
+impl Type {
+    fn wasm(self) -> &'static str {
+        match self {
+            Type::Int => "i64",
+            Type::Bool => "i32",
+        }
+    }
+
+    /// The name the evaluator uses for the type in error messages
+    fn name(self) -> &'static str {
+        match self {
+            Type::Int => "INTEGER",
+            Type::Bool => "BOOLEAN",
+        }
+    }
+}
+
+/// What is known about the value of a compiled expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Known(Type),
+    /// Depends on the result of a function not inferred yet
+    Unknown,
+    /// The expression always returns before producing a value
+    Never,
+}
+
+impl Value {
+    /// Combines the values of two branches, or returns `None` if they
+    /// have different types.
+    fn unify(self, other: Value) -> Option<Value> {
+        match (self, other) {
+            (Value::Never, v) | (v, Value::Never) => Some(v),
+            (Value::Unknown, v) | (v, Value::Unknown) => Some(v),
+            (Value::Known(a), Value::Known(b)) => (a == b).then_some(self),
+        }
+    }
+
+    /// The type to declare the value with. Unknown values only remain in
+    /// passes whose output is discarded, so any type will do.
+    fn wasm(self) -> &'static str {
+        match self {
+            Value::Known(t) => t.wasm(),
+            Value::Unknown | Value::Never => Type::Int.wasm(),
+        }
+    }
+}
+
+/// A function declared with a top level `let`.
+struct Function<'a> {
+    name: &'a IdentExpression,
+    literal: &'a FunctionLiteral,
+    /// The result type inferred so far
+    result: Value,
+}
+
+struct Compiler<'a> {
+    functions: Vec<Function<'a>>,
+    /// The top level statements, other than function declarations
+    main: Vec<&'a Statement>,
+    /// The globals bound by `main` in the last pass, in order
+    globals: Vec<(&'a str, Value)>,
+}
+
+impl<'a> Compiler<'a> {
+    fn new(program: &'a Program) -> Result<Self, CodegenError> {
+        let mut functions: Vec<Function> = Vec::new();
+        let mut main = Vec::new();
+
+        for stmt in program.statements.iter() {
+            let Statement::Let(LetStatement {
+                name,
+                value: Expression::Function(literal),
+                ..
+            }) = stmt
+            else {
+                main.push(stmt);
+                continue;
+            };
+
+            check_identifier(name)?;
+            if name.value == "main" {
+                return Err(CodegenError::new(
+                    "main is reserved for the top level statements",
+                    name.token.span,
+                ));
+            }
+            if functions.iter().any(|f| f.name.value == name.value) {
+                return Err(CodegenError::new(
+                    format!("function {} is declared twice", name.value),
+                    name.token.span,
+                ));
+            }
+
+            functions.push(Function {
+                name,
+                literal,
+                result: Value::Unknown,
+            });
+        }
+
+        Ok(Self {
+            functions,
+            main,
+            globals: Vec::new(),
+        })
+    }
+
+    fn function(&self, name: &str) -> Option<&Function<'a>> {
+        self.functions.iter().find(|f| f.name.value == name)
+    }
+
+    /// Compiles the module with the types inferred so far, returning it
+    /// along with whether some types were inferred by this pass.
+    fn compile_pass(&mut self) -> Result<(Lines, bool), CodegenError> {
+        // `main` goes first, so the functions see the globals it binds
+        let mut main = FunctionCompiler::new(self, true);
+        let main_result = main.compile_main(&self.main)?;
+        let main_body = main.finish("main", None, &[], main_result);
+        let globals = main.globals;
+
+        let mut changed = globals != self.globals;
+        self.globals = globals;
+
+        let mut functions = Vec::new();
+        let mut results = Vec::new();
+        for function in self.functions.iter() {
+            let mut compiler = FunctionCompiler::new(self, false);
+            let params = compiler.declare_parameters(function.literal)?;
+            let body: Vec<_> = function.literal.body.statements.iter().collect();
+            let tail = compiler.compile_block(&body, function.literal.body.span)?;
+            let result = compiler.result_with(tail, function.literal.body.span)?;
+
+            functions.push(compiler.finish(
+                &function.name.value,
+                Some(function.name.token.span),
+                &params,
+                Some(result),
+            ));
+            results.push(result);
+        }
+        for (function, result) in self.functions.iter_mut().zip(results) {
+            changed |= function.result != result;
+            function.result = result;
+        }
+
+        let mut module = vec![("(module".to_string(), None)];
+        for (name, value) in self.globals.iter() {
+            let global = format!("  (global ${name} (mut {0}) ({0}.const 0))", value.wasm());
+            module.push((global, None));
+        }
+        module.extend(functions.into_iter().chain([main_body]).flatten());
+        module.push((")".to_string(), None));
+
+        Ok((module, changed))
+    }
+
+    /// Reports the functions whose result type couldn't be inferred, such
+    /// as those that only call themselves.
+    fn check_inferred(&self) -> Result<(), CodegenError> {
+        match self.functions.iter().find(|f| f.result == Value::Unknown) {
+            Some(function) => Err(CodegenError::new(
+                format!("can't infer the result type of {}", function.name.value),
+                function.name.token.span,
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Compiles the body of a single function into a list of instructions.
+struct FunctionCompiler<'c, 'a> {
+    compiler: &'c Compiler<'a>,
+    /// Whether this is `main`, whose bindings are globals
+    is_main: bool,
+    /// The parameters followed by the other locals
+    locals: Vec<(&'a str, Value)>,
+    /// The globals bound so far, when compiling `main`
+    globals: Vec<(&'a str, Value)>,
+    /// The combined type of the values returned so far
+    returned: Value,
+    /// The instructions, along with the span of the node each was
+    /// compiled from
+    lines: Vec<(String, Span)>,
+    depth: usize,
+}
+
+impl<'c, 'a> FunctionCompiler<'c, 'a> {
+    fn new(compiler: &'c Compiler<'a>, is_main: bool) -> Self {
+        Self {
+            compiler,
+            is_main,
+            locals: Vec::new(),
+            globals: Vec::new(),
+            returned: Value::Never,
+            lines: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// Adds an instruction compiled from the node at `span`.
+    fn emit(&mut self, instruction: impl AsRef<str>, span: Span) {
+        let indent = "  ".repeat(self.depth);
+        self.lines
+            .push((format!("{indent}{}", instruction.as_ref()), span));
+    }
+
+    /// Declares the parameters of `literal` as locals, returning their
+    /// names.
+    fn declare_parameters(
+        &mut self,
+        literal: &'a FunctionLiteral,
+    ) -> Result<Vec<&'a str>, CodegenError> {
+        if let Some(rest) = &literal.rest {
+            return Err(unsupported("rest parameters", rest.token.span));
+        }
+
+        let mut params = Vec::new();
+        for param in literal.parameters.iter() {
+            let Pattern::Ident(ident) = param else {
+                return Err(unsupported("destructuring parameters", param.span()));
+            };
+            check_identifier(ident)?;
+            if params.contains(&ident.value.as_str()) {
+                return Err(CodegenError::new(
+                    format!("parameter {} is declared twice", ident.value),
+                    ident.token.span,
+                ));
+            }
+            self.locals.push((&ident.value, Value::Known(Type::Int)));
+            params.push(ident.value.as_str());
+        }
+
+        Ok(params)
+    }
+
+    /// Compiles the top level statements, returning the type of the
+    /// program's value, or `None` if it doesn't end with an expression.
+    fn compile_main(
+        &mut self,
+        statements: &[&'a Statement],
+    ) -> Result<Option<Value>, CodegenError> {
+        match statements.last() {
+            Some(Statement::Expression(_)) => {
+                let span = statements[0]
+                    .span()
+                    .to(statements[statements.len() - 1].span());
+                let tail = self.compile_block(statements, span)?;
+                self.result_with(tail, span).map(Some)
+            }
+            Some(last) => {
+                for stmt in statements.iter() {
+                    self.compile_statement(stmt)?;
+                }
+                if self.returned != Value::Never {
+                    return Err(CodegenError::new(
+                        "the program must end with an expression to return a value",
+                        last.span(),
+                    ));
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Combines the value of the last statement of a function with the
+    /// values it returns.
+    fn result_with(&self, tail: Value, span: Span) -> Result<Value, CodegenError> {
+        tail.unify(self.returned)
+            .ok_or_else(|| mismatched("the returned values", tail, self.returned, span))
+    }
+
+    /// Assembles the compiled function, exported as `name`. Its header
+    /// maps to `span`, if any.
+    fn finish(
+        &self,
+        name: &str,
+        span: Option<Span>,
+        params: &[&str],
+        result: Option<Value>,
+    ) -> Lines {
+        let mut header = format!("  (func ${name} (export \"{name}\")");
+        for param in params.iter() {
+            header.push_str(&format!(" (param ${param} i64)"));
+        }
+        if let Some(result) = result {
+            header.push_str(&format!(" (result {})", result.wasm()));
+        }
+
+        let mut out = vec![(header, span)];
+        for (name, value) in self.locals.iter().skip(params.len()) {
+            out.push((format!("    (local ${name} {})", value.wasm()), None));
+        }
+        for (line, span) in self.lines.iter() {
+            out.push((format!("    {line}"), Some(*span)));
+        }
+        out.push(("  )".to_string(), None));
+        out
+    }
+
+    /// Compiles `statements`, leaving the value of the last one on the
+    /// stack.
+    fn compile_block(
+        &mut self,
+        statements: &[&'a Statement],
+        span: Span,
+    ) -> Result<Value, CodegenError> {
+        let Some((last, rest)) = statements.split_last() else {
+            return Err(CodegenError::new("an empty block has no value", span));
+        };
+
+        for stmt in rest.iter() {
+            self.compile_statement(stmt)?;
+        }
+
+        match last {
+            Statement::Expression(s) => self.compile_expression(&s.expression),
+            Statement::Return(_) => {
+                self.compile_statement(last)?;
+                Ok(Value::Never)
+            }
+            _ => Err(CodegenError::new(
+                "the block must end with an expression to have a value",
+                last.span(),
+            )),
+        }
+    }
+
+    /// Compiles a statement whose value, if any, is discarded.
+    fn compile_statement(&mut self, stmt: &'a Statement) -> Result<(), CodegenError> {
+        match stmt {
+            Statement::Let(s) => {
+                if let Expression::Function(literal) = &s.value {
+                    return Err(nested_function(literal));
+                }
+                check_identifier(&s.name)?;
+                let value = self.compile_expression(&s.value)?;
+                self.bind(&s.name, value, stmt.span())
+            }
+            Statement::Return(s) => {
+                let value = self.compile_expression(&s.value)?;
+                self.returned = self.returned.unify(value).ok_or_else(|| {
+                    mismatched("the returned values", self.returned, value, stmt.span())
+                })?;
+                self.emit("return", stmt.span());
+                Ok(())
+            }
+            Statement::Expression(s) => {
+                if let Expression::If(e) = &s.expression {
+                    return self.compile_if(e, s.expression.span(), false).map(|_| ());
+                }
+                if self.compile_expression(&s.expression)? != Value::Never {
+                    self.emit("drop", s.expression.span());
+                }
+                Ok(())
+            }
+            Statement::Throw(_) => Err(unsupported("throw statements", stmt.span())),
+            Statement::Defer(_) => Err(unsupported("defer statements", stmt.span())),
+            Statement::Switch(_) => Err(unsupported("switch statements", stmt.span())),
+            Statement::DoWhile(_) => Err(unsupported("do-while loops", stmt.span())),
+            Statement::ForIn(_) => Err(unsupported("for-in loops", stmt.span())),
+            Statement::Enum(_) => Err(unsupported("enums", stmt.span())),
+        }
+    }
+
+    /// Stores the value on the stack in the binding `name`, declaring it
+    /// on its first use. A binding keeps the type it was declared with.
+    fn bind(
+        &mut self,
+        name: &'a IdentExpression,
+        value: Value,
+        span: Span,
+    ) -> Result<(), CodegenError> {
+        if self.compiler.function(&name.value).is_some() {
+            return Err(CodegenError::new(
+                format!("{} is already declared as a function", name.value),
+                name.token.span,
+            ));
+        }
+
+        let (bindings, set) = if self.is_main {
+            (&mut self.globals, "global.set")
+        } else {
+            (&mut self.locals, "local.set")
+        };
+
+        match bindings.iter_mut().find(|(n, _)| *n == name.value) {
+            Some((_, declared)) => {
+                *declared = declared.unify(value).ok_or_else(|| {
+                    mismatched(
+                        &format!("the values bound to {}", name.value),
+                        *declared,
+                        value,
+                        name.token.span,
+                    )
+                })?;
+            }
+            None => bindings.push((&name.value, value)),
+        }
+
+        self.emit(format!("{set} ${}", name.value), span);
+        Ok(())
+    }
+
+    fn compile_expression(&mut self, expression: &'a Expression) -> Result<Value, CodegenError> {
+        match expression {
+            Expression::Integer(e) => {
+                self.emit(format!("i64.const {}", e.value), e.token.span);
+                Ok(Value::Known(Type::Int))
+            }
+            Expression::Boolean(e) => {
+                self.emit(format!("i32.const {}", i32::from(e.value)), e.token.span);
+                Ok(Value::Known(Type::Bool))
+            }
+            Expression::Ident(e) => self.compile_identifier(e),
+            Expression::Prefix(e) => {
+                let span = expression.span();
+                match e.operator.as_str() {
+                    "-" => {
+                        self.emit("i64.const 0", span);
+                        let right = self.compile_expression(&e.right)?;
+                        if let Value::Known(Type::Bool) = right {
+                            return Err(unknown_operator(&format!("-{}", Type::Bool.name()), span));
+                        }
+                        self.emit("i64.sub", span);
+                        Ok(Value::Known(Type::Int))
+                    }
+                    // Unary plus leaves integers as they are
+                    "+" => {
+                        let right = self.compile_expression(&e.right)?;
+                        if let Value::Known(Type::Bool) = right {
+                            return Err(unknown_operator(&format!("+{}", Type::Bool.name()), span));
+                        }
+                        Ok(Value::Known(Type::Int))
+                    }
+                    "!" => {
+                        if let Value::Known(Type::Int) = self.compile_expression(&e.right)? {
+                            // Integers are always truthy
+                            self.emit("drop", span);
+                            self.emit("i32.const 0", span);
+                        } else {
+                            self.emit("i32.eqz", span);
+                        }
+                        Ok(Value::Known(Type::Bool))
+                    }
+                    operator => Err(unknown_operator(operator, span)),
+                }
+            }
+            Expression::Infix(e) => {
+                let left = self.compile_expression(&e.left)?;
+                let right = self.compile_expression(&e.right)?;
+                let span = expression.span();
+
+                let operands = match left.unify(right) {
+                    Some(Value::Known(t)) => t,
+                    Some(_) => Type::Int,
+                    None => {
+                        return Err(CodegenError::new(
+                            format!(
+                                "type mismatch: {} {} {}",
+                                type_name(left),
+                                e.operator,
+                                type_name(right)
+                            ),
+                            span,
+                        ))
+                    }
+                };
+
+                let (instruction, result) = match (operands, e.operator.as_str()) {
+                    (Type::Int, "+") => ("i64.add", Type::Int),
+                    (Type::Int, "-") => ("i64.sub", Type::Int),
+                    (Type::Int, "*") => ("i64.mul", Type::Int),
+                    (Type::Int, "/") => ("i64.div_s", Type::Int),
+                    (Type::Int, "<") => ("i64.lt_s", Type::Bool),
+                    (Type::Int, ">") => ("i64.gt_s", Type::Bool),
+                    (Type::Int, "==") => ("i64.eq", Type::Bool),
+                    (Type::Int, "!=") => ("i64.ne", Type::Bool),
+                    (Type::Bool, "==") => ("i32.eq", Type::Bool),
+                    (Type::Bool, "!=") => ("i32.ne", Type::Bool),
+                    (t, operator) => {
+                        return Err(unknown_operator(
+                            &format!("{} {operator} {}", t.name(), t.name()),
+                            span,
+                        ))
+                    }
+                };
+
+                self.emit(instruction, span);
+                Ok(Value::Known(result))
+            }
+            Expression::If(e) => self.compile_if(e, expression.span(), true),
+            Expression::Call(e) => {
+                let arguments: Vec<_> = e.arguments.iter().collect();
+                self.compile_call(&e.function, &arguments, &e.named_arguments, e.span)
+            }
+            // Pipes compile to the call they stand for, e.g. `x |> f(1)`
+            // to `f(x, 1)`
+            Expression::Pipe(e) => {
+                let call = e.call();
+                let span = expression.span();
+                self.compile_call(call.function, &call.arguments, call.named_arguments, span)
+            }
+            Expression::Function(e) => Err(nested_function(e)),
+            Expression::Float(_) => Err(unsupported("float literals", expression.span())),
+            Expression::Try(_) => Err(unsupported("try expressions", expression.span())),
+            Expression::Placeholder(_) => Err(unsupported("placeholders", expression.span())),
+            Expression::Set(_) => Err(unsupported("sets", expression.span())),
+            Expression::Member(_) => Err(unsupported("member expressions", expression.span())),
+            Expression::Array(_) => Err(unsupported("arrays", expression.span())),
+            Expression::Spread(_) => Err(unsupported("spread arguments", expression.span())),
+        }
+    }
+
+    fn compile_identifier(&mut self, ident: &'a IdentExpression) -> Result<Value, CodegenError> {
+        let name = ident.value.as_str();
+        let local = self.locals.iter().find(|(n, _)| *n == name);
+        let global = match self.is_main {
+            true => self.globals.iter().find(|(n, _)| *n == name),
+            false => self.compiler.globals.iter().find(|(n, _)| *n == name),
+        };
+
+        let (get, value) = match (local, global) {
+            (Some((_, value)), _) => ("local.get", *value),
+            (None, Some((_, value))) => ("global.get", *value),
+            (None, None) if self.compiler.function(name).is_some() => {
+                return Err(CodegenError::new(
+                    format!("functions can only be called, {name} can't be used as a value"),
+                    ident.token.span,
+                ))
+            }
+            (None, None) => {
+                return Err(CodegenError::new(
+                    format!("identifier not found: {name}"),
+                    ident.token.span,
+                ))
+            }
+        };
+
+        self.emit(format!("{get} ${name}"), ident.token.span);
+        Ok(value)
+    }
+
+    /// Compiles an `if`, leaving the value of the branch taken on the stack
+    /// when `has_value`.
+    fn compile_if(
+        &mut self,
+        e: &'a IfExpression,
+        span: Span,
+        has_value: bool,
+    ) -> Result<Value, CodegenError> {
+        if has_value && e.alternative.is_none() {
+            return Err(CodegenError::new(
+                "an if without an else has no value when the condition is false",
+                span,
+            ));
+        }
+
+        if let Value::Known(Type::Int) = self.compile_expression(&e.condition)? {
+            // Integers are always truthy
+            self.emit("drop", e.condition.span());
+            self.emit("i32.const 1", e.condition.span());
+        }
+
+        // The result type is only known once the branches are compiled
+        let header = self.lines.len();
+        self.emit("if", span);
+
+        let consequence = self.compile_branch(&e.consequence, has_value)?;
+        let alternative = match &e.alternative {
+            Some(alternative) => {
+                self.emit("else", span);
+                self.compile_branch(alternative, has_value)?
+            }
+            None => Value::Never,
+        };
+        self.emit("end", span);
+
+        if !has_value {
+            return Ok(Value::Never);
+        }
+
+        let value = consequence
+            .unify(alternative)
+            .ok_or_else(|| mismatched("the branches", consequence, alternative, span))?;
+        match value {
+            Value::Never => self.emit("unreachable", span),
+            _ => self.lines[header]
+                .0
+                .push_str(&format!(" (result {})", value.wasm())),
+        }
+        Ok(value)
+    }
+
+    fn compile_branch(
+        &mut self,
+        block: &'a BlockStatement,
+        has_value: bool,
+    ) -> Result<Value, CodegenError> {
+        self.depth += 1;
+        let statements: Vec<_> = block.statements.iter().collect();
+        let value = if has_value {
+            self.compile_block(&statements, block.span)
+        } else {
+            statements
+                .iter()
+                .try_for_each(|stmt| self.compile_statement(stmt))
+                .map(|_| Value::Never)
+        };
+        self.depth -= 1;
+        value
+    }
+
+    fn compile_call(
+        &mut self,
+        function: &'a Expression,
+        arguments: &[&'a Expression],
+        named_arguments: &'a [NamedArgument],
+        span: Span,
+    ) -> Result<Value, CodegenError> {
+        let callee = function;
+        let function = match callee {
+            Expression::Ident(ident) if !self.locals.iter().any(|(n, _)| *n == ident.value) => {
+                self.compiler.function(&ident.value)
+            }
+            _ => None,
+        };
+        let Some(function) = function else {
+            return Err(CodegenError::new(
+                "only functions declared with a top level let can be called",
+                callee.span(),
+            ));
+        };
+
+        if let Some(arg) = named_arguments.first() {
+            return Err(unsupported("named arguments", arg.name.token.span));
+        }
+
+        let name = &function.name.value;
+        let want = function.literal.parameters.len();
+        if arguments.len() != want {
+            return Err(CodegenError::new(
+                format!(
+                    "wrong number of arguments to {name}: want={want}, got={}",
+                    arguments.len()
+                ),
+                span,
+            ));
+        }
+
+        for (i, arg) in arguments.iter().enumerate() {
+            if let Value::Known(Type::Bool) = self.compile_expression(arg)? {
+                return Err(CodegenError::new(
+                    format!(
+                        "argument {} to {name} must be an INTEGER, got BOOLEAN",
+                        i + 1
+                    ),
+                    arg.span(),
+                ));
+            }
+        }
+
+        self.emit(format!("call ${name}"), span);
+        Ok(function.result)
+    }
+}
+
+/// WebAssembly identifiers are limited to ASCII, unlike Monkey ones.
+fn check_identifier(ident: &IdentExpression) -> Result<(), CodegenError> {
+    if ident.value.is_ascii() {
+        Ok(())
+    } else {
+        Err(CodegenError::new(
+            format!(
+                "identifier {} must be ASCII to be used in WebAssembly",
+                ident.value
+            ),
+            ident.token.span,
+        ))
+    }
+}
+
+fn type_name(value: Value) -> &'static str {
+    match value {
+        Value::Known(t) => t.name(),
+        Value::Unknown | Value::Never => Type::Int.name(),
+    }
+}
+
+fn unsupported(what: &str, span: Span) -> CodegenError {
+    CodegenError::new(format!("{what} aren't supported by the WAT backend"), span)
+}
+
+fn nested_function(literal: &FunctionLiteral) -> CodegenError {
+    CodegenError::new(
+        "functions can only be declared with a top level let",
+        literal.token.span,
+    )
+}
+
+fn unknown_operator(operation: &str, span: Span) -> CodegenError {
+    CodegenError::new(format!("unknown operator: {operation}"), span)
+}
+
+fn mismatched(what: &str, a: Value, b: Value, span: Span) -> CodegenError {
+    CodegenError::new(
+        format!(
+            "{what} have different types: {} and {}",
+            type_name(a),
+            type_name(b)
+        ),
+        span,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn compile_source(source: &str) -> Result<String, CodegenError> {
+        compile(&Parser::parse(source).unwrap())
+    }
+
+    #[test]
+    fn test_compile() {
+        let source = "
+let fib = fn(n) {
+  if (n < 2) { return n; }
+  fib(n - 1) + fib(n - 2)
+};
+let limit = 10;
+fib(limit) == 55";
+
+        let expected = r#"(module
+  (global $limit (mut i64) (i64.const 0))
+  (func $fib (export "fib") (param $n i64) (result i64)
+    local.get $n
+    i64.const 2
+    i64.lt_s
+    if
+      local.get $n
+      return
+    end
+    local.get $n
+    i64.const 1
+    i64.sub
+    call $fib
+    local.get $n
+    i64.const 2
+    i64.sub
+    call $fib
+    i64.add
+  )
+  (func $main (export "main") (result i32)
+    i64.const 10
+    global.set $limit
+    global.get $limit
+    call $fib
+    i64.const 55
+    i64.eq
+  )
+)
+"#;
+
+        assert_eq!(compile_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_infer_mutually_recursive_results() {
+        let source = "
+let isEven = fn(n) { if (n == 0) { true } else { isOdd(n - 1) } };
+let isOdd = fn(n) { if (n == 0) { false } else { isEven(n - 1) } };
+let x = -3;
+if (isEven(4)) { x } else { !5; 0 }";
+
+        let expected = r#"(module
+  (global $x (mut i64) (i64.const 0))
+  (func $isEven (export "isEven") (param $n i64) (result i32)
+    local.get $n
+    i64.const 0
+    i64.eq
+    if (result i32)
+      i32.const 1
+    else
+      local.get $n
+      i64.const 1
+      i64.sub
+      call $isOdd
+    end
+  )
+  (func $isOdd (export "isOdd") (param $n i64) (result i32)
+    local.get $n
+    i64.const 0
+    i64.eq
+    if (result i32)
+      i32.const 0
+    else
+      local.get $n
+      i64.const 1
+      i64.sub
+      call $isEven
+    end
+  )
+  (func $main (export "main") (result i64)
+    i64.const 0
+    i64.const 3
+    i64.sub
+    global.set $x
+    i64.const 4
+    call $isEven
+    if (result i64)
+      global.get $x
+    else
+      i64.const 5
+      drop
+      i32.const 0
+      drop
+      i64.const 0
+    end
+  )
+)
+"#;
+
+        assert_eq!(compile_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_compile_locals() {
+        let source = "let f = fn(a) { let b = a * 2; let ok = b > 3; ok }; let g = 1;";
+
+        let module = compile_source(source).unwrap();
+        assert!(module.contains(
+            "(func $f (export \"f\") (param $a i64) (result i32)\n    (local $b i64)\n    (local $ok i32)\n"
+        ));
+        assert!(module.contains("(func $main (export \"main\")\n"));
+    }
+
+    #[test]
+    fn test_compile_pipes() {
+        let functions = "let add = fn(a, b) { a + b }; let inc = fn(a) { a + 1 };";
+
+        assert_eq!(
+            compile_source(&format!("{functions} 1 |> inc |> add(2)")).unwrap(),
+            compile_source(&format!("{functions} add(inc(1), 2)")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_source_map() {
+        let source = "let f = fn(a) { a };\nf(1 + 2)";
+        let (module, source_map) =
+            compile_with_source_map(&Parser::parse(source).unwrap()).unwrap();
+
+        let spans: Vec<_> = module
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (line.trim(), source_map.span(i + 1)))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("(module", None),
+                (
+                    "(func $f (export \"f\") (param $a i64) (result i64)",
+                    Some(Span::new(4, 5))
+                ),
+                ("local.get $a", Some(Span::new(16, 17))),
+                (")", None),
+                ("(func $main (export \"main\") (result i64)", None),
+                ("i64.const 1", Some(Span::new(23, 24))),
+                ("i64.const 2", Some(Span::new(27, 28))),
+                ("i64.add", Some(Span::new(23, 28))),
+                ("call $f", Some(Span::new(21, 29))),
+                (")", None),
+                (")", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let tests = [
+            ("1 + true", "type mismatch: INTEGER + BOOLEAN"),
+            ("true + false", "unknown operator: BOOLEAN + BOOLEAN"),
+            ("-true", "unknown operator: -BOOLEAN"),
+            ("+true", "unknown operator: +BOOLEAN"),
+            ("x", "identifier not found: x"),
+            (
+                "let x = 1; let x = true;",
+                "the values bound to x have different types: INTEGER and BOOLEAN",
+            ),
+            (
+                "if (true) { 1 } else { false }",
+                "the branches have different types: INTEGER and BOOLEAN",
+            ),
+            (
+                "if (true) { 1 }",
+                "an if without an else has no value when the condition is false",
+            ),
+            (
+                "let f = fn(a) { a }; f(1, 2)",
+                "wrong number of arguments to f: want=1, got=2",
+            ),
+            (
+                "let f = fn(a) { a }; 1 |> f(2)",
+                "wrong number of arguments to f: want=1, got=2",
+            ),
+            (
+                "let f = fn(a) { a }; f(true)",
+                "argument 1 to f must be an INTEGER, got BOOLEAN",
+            ),
+            (
+                "let f = fn(a) { a }; f",
+                "functions can only be called, f can't be used as a value",
+            ),
+            (
+                "let f = fn() { fn() { 1 } };",
+                "functions can only be declared with a top level let",
+            ),
+            (
+                "let f = fn() { f() }; f()",
+                "can't infer the result type of f",
+            ),
+            (
+                "let main = fn() { 1 };",
+                "main is reserved for the top level statements",
+            ),
+            (
+                "let f = fn(a, a) { a }; f(1, 2);",
+                "parameter a is declared twice",
+            ),
+            (
+                "let f = fn([a]) { a };",
+                "destructuring parameters aren't supported by the WAT backend",
+            ),
+            ("1.5", "float literals aren't supported by the WAT backend"),
+            (
+                "return 1; let a = 2;",
+                "the program must end with an expression to return a value",
+            ),
+        ];
+
+        for (source, expected) in tests {
+            let error = compile_source(source).unwrap_err();
+            assert_eq!(error.message, expected, "compiling {source:?}");
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Per-project settings, read from a `monkey.toml` file.
+//!
+//! ```toml
+//! [format]
+//! indent_width = 2
+//! use_tabs = false
+//! brace_style = "next_line"  # or "same_line"
+//! max_width = 80
+//! ```
+//!
+//! Only the subset of TOML needed by the settings is supported: `[table]`
+//! headers, and `key = value` lines with integer, boolean or string values.
+//! Tables other than the ones above are skipped, and may use any syntax.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::ast::{BraceStyle, FormatConfig};
+
+/// Name of the settings file.
+pub const FILE_NAME: &str = "monkey.toml";
+
+/// The settings of a project.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Config {
+    /// The `[format]` table, used by `monkey fmt`
+    pub format: FormatConfig,
+}
+
+/// A mistake in a settings file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    /// Line of the file (1-based) where the mistake is
+    pub line: usize,
+    pub message: String,
+}
+
+impl ConfigError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+enum Value {
+    Integer(usize),
+    Boolean(bool),
+    String(String),
+}
+
+impl Config {
+    /// Parses the contents of a settings file. Settings it doesn't list
+    /// keep their default value.
+    pub fn parse(contents: &str) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+        let mut table = String::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line_number = i + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[') {
+                let Some(name) = name.strip_suffix(']') else {
+                    return Err(ConfigError::new(line_number, "expected `]`"));
+                };
+                table = name.trim().to_string();
+                continue;
+            }
+            if table != "format" {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(ConfigError::new(line_number, "expected `key = value`"));
+            };
+            let value = parse_value(value.trim(), line_number)?;
+            config.format.set(key.trim(), value, line_number)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the path of the settings file in `dir` or the closest of its
+    /// ancestors, if any.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+    }
+}
+
+impl FormatConfig {
+    fn set(&mut self, key: &str, value: Value, line: usize) -> Result<(), ConfigError> {
+        let expected = |kind: &str| ConfigError::new(line, format!("expected {key} to be {kind}"));
+
+        match (key, value) {
+            ("indent_width", Value::Integer(width)) if width > 0 => self.indent_width = width,
+            ("indent_width", _) => return Err(expected("a positive integer")),
+            ("use_tabs", Value::Boolean(use_tabs)) => self.use_tabs = use_tabs,
+            ("use_tabs", _) => return Err(expected("a boolean")),
+            ("brace_style", Value::String(name)) => match BraceStyle::from_name(&name) {
+                Some(style) => self.brace_style = style,
+                None => return Err(expected("\"same_line\" or \"next_line\"")),
+            },
+            ("brace_style", _) => return Err(expected("\"same_line\" or \"next_line\"")),
+            ("max_width", Value::Integer(width)) => self.max_width = width,
+            ("max_width", _) => return Err(expected("an integer")),
+            _ => {
+                return Err(ConfigError::new(
+                    line,
+                    format!("unknown format setting \"{key}\""),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Removes the `#` comment at the end of `line`, if any, ignoring the `#`
+/// characters inside strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(value: &str, line: usize) -> Result<Value, ConfigError> {
+    match value {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => {}
+    }
+
+    if let Some(string) = value.strip_prefix('"') {
+        return match string.strip_suffix('"') {
+            Some(string) if !string.contains(['"', '\\']) => Ok(Value::String(string.to_string())),
+            _ => Err(ConfigError::new(
+                line,
+                "expected a string without quotes or escapes",
+            )),
+        };
+    }
+
+    value
+        .replace('_', "")
+        .parse()
+        .map(Value::Integer)
+        .map_err(|_| ConfigError::new(line, format!("invalid value `{value}`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let contents = r#"
+# Settings of the project
+[package]
+authors = [
+    "someone",
+]
+
+[format]
+indent_width = 2   # columns
+use_tabs = true
+brace_style = "next_line"
+max_width = 1_000
+"#;
+
+        let config = Config::parse(contents).unwrap();
+        assert_eq!(
+            config.format,
+            FormatConfig {
+                indent_width: 2,
+                use_tabs: true,
+                brace_style: BraceStyle::NextLine,
+                max_width: 1000,
+            }
+        );
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let tests = vec![
+            ("[format", "line 1: expected `]`"),
+            ("[format]\nindent_width", "line 2: expected `key = value`"),
+            (
+                "[format]\nindent_width = 0",
+                "line 2: expected indent_width to be a positive integer",
+            ),
+            (
+                "[format]\nuse_tabs = 1",
+                "line 2: expected use_tabs to be a boolean",
+            ),
+            (
+                "[format]\nbrace_style = \"k&r\"",
+                "line 2: expected brace_style to be \"same_line\" or \"next_line\"",
+            ),
+            (
+                "[format]\n\nindent = 4",
+                "line 3: unknown format setting \"indent\"",
+            ),
+            ("[format]\nmax_width = 8O", "line 2: invalid value `8O`"),
+        ];
+
+        for (contents, expected) in tests {
+            let error = Config::parse(contents).unwrap_err();
+            assert_eq!(error.to_string(), expected, "contents: {contents}");
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! API documentation generated by `monkey doc`.
+//!
+//! Every top-level `let` or `const` binding of a function literal is
+//! documented, along with the `///` comments on the lines right before it:
+//!
+//! ```monkey
+//! /// Adds two numbers.
+//! let add = fn(a, b) { a + b };
+//! ```
+//!
+//! Comments are only available when the program is parsed with
+//! [`Parser::keep_comments`](crate::parser::Parser::keep_comments).
+
+use std::fmt::Display;
+
+use crate::ast::{expressions::FunctionLiteral, Expression, Program, Statement};
+
+/// The documentation of a function bound at the top level of a program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionDoc {
+    pub name: String,
+    /// The parameters as written in the source, e.g. `a, [b, c], ...rest`
+    pub parameters: String,
+    pub arity: Arity,
+    /// The text of the doc comments, without the `///` and the space after
+    /// it, one item per line
+    pub docs: Vec<String>,
+    /// Line (1-based) of the binding in the source
+    pub line: usize,
+}
+
+/// The number of arguments a function takes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    Exact(usize),
+    /// The function has a rest parameter, collecting the arguments after
+    /// the first ones
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn of(function: &FunctionLiteral) -> Arity {
+        match function.rest {
+            Some(_) => Arity::AtLeast(function.parameters.len()),
+            None => Arity::Exact(function.parameters.len()),
+        }
+    }
+}
+
+impl Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arity::Exact(count) => write!(f, "{count}"),
+            Arity::AtLeast(count) => write!(f, "{count} or more"),
+        }
+    }
+}
+
+/// The documented functions of one source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleDoc {
+    /// Name of the file, used as the title of its section
+    pub name: String,
+    pub functions: Vec<FunctionDoc>,
+}
+
+/// Returns the documentation of the functions bound at the top level of
+/// `program`, in source order. `source` is the code `program` was parsed
+/// from.
+pub fn extract(program: &Program, source: &str) -> Vec<FunctionDoc> {
+    program
+        .statements
+        .iter()
+        .filter_map(|stmt| {
+            let Statement::Let(binding) = stmt else {
+                return None;
+            };
+            let Expression::Function(function) = &binding.value else {
+                return None;
+            };
+
+            // Only the doc comments right before the binding belong to it
+            let leading = program.comments.leading(stmt);
+            let first_doc = leading
+                .iter()
+                .rposition(|comment| !is_doc_comment(&comment.text))
+                .map_or(0, |i| i + 1);
+            let docs = leading[first_doc..]
+                .iter()
+                .map(|comment| {
+                    let text = &comment.text[3..];
+                    text.strip_prefix(' ').unwrap_or(text).to_string()
+                })
+                .collect();
+
+            Some(FunctionDoc {
+                name: binding.name.value.clone(),
+                parameters: function.parameter_list(),
+                arity: Arity::of(function),
+                docs,
+                line: stmt.span().location(source).0,
+            })
+        })
+        .collect()
+}
+
+/// Checks if `comment` is a `///` doc comment. Comments starting with
+/// more slashes, such as `////`, are ordinary comments.
+fn is_doc_comment(comment: &str) -> bool {
+    comment.starts_with("///") && !comment.starts_with("////")
+}
+
+/// Returns the documentation of the modules as a Markdown document, with a
+/// section per module. Doc comments are copied as they are, so they may
+/// use Markdown themselves.
+pub fn to_markdown(modules: &[ModuleDoc]) -> String {
+    let mut out = String::from("# API documentation\n");
+
+    for module in modules {
+        out.push_str(&format!("\n## {}\n", module.name));
+        if module.functions.is_empty() {
+            out.push_str("\nNo functions.\n");
+        }
+
+        for function in module.functions.iter() {
+            out.push_str(&format!(
+                "\n### `{}({})`\n\nArity: {}. Defined on line {}.\n",
+                function.name, function.parameters, function.arity, function.line
+            ));
+            if !function.docs.is_empty() {
+                out.push('\n');
+                for line in function.docs.iter() {
+                    out.push_str(line.trim_end());
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Returns the documentation of the modules as a standalone HTML page.
+/// Doc comments are split into paragraphs at blank lines, and escaped.
+pub fn to_html(modules: &[ModuleDoc]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>API documentation</title>\n</head>\n<body>\n\
+         <h1>API documentation</h1>\n",
+    );
+
+    for module in modules {
+        out.push_str(&format!("<h2>{}</h2>\n", escape_html(&module.name)));
+        if module.functions.is_empty() {
+            out.push_str("<p>No functions.</p>\n");
+        }
+
+        for function in module.functions.iter() {
+            out.push_str(&format!(
+                "<h3><code>{}({})</code></h3>\n\
+                 <p>Arity: {}. Defined on line {}.</p>\n",
+                escape_html(&function.name),
+                escape_html(&function.parameters),
+                function.arity,
+                function.line
+            ));
+            for paragraph in function.docs.split(|line| line.trim().is_empty()) {
+                if paragraph.is_empty() {
+                    continue;
+                }
+                let text: Vec<String> = paragraph
+                    .iter()
+                    .map(|line| escape_html(line.trim()))
+                    .collect();
+                out.push_str(&format!("<p>{}</p>\n", text.join("\n")));
+            }
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer::Lexer, parser::Parser};
+
+    const SOURCE: &str = "// Helpers
+//// Not documentation
+
+/// Adds numbers.
+///
+/// Returns <a + b>.
+let add = fn(a, b) { a + b };
+let x = 1;
+const log = fn(format, ...args) {
+    /// Not at the top level
+    let inner = fn() {};
+};
+";
+
+    fn parse(source: &str) -> Program {
+        let mut parser = Parser::new(Lexer::new(source));
+        parser.keep_comments();
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty());
+        program
+    }
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(&parse(SOURCE), SOURCE),
+            vec![
+                FunctionDoc {
+                    name: "add".to_string(),
+                    parameters: "a, b".to_string(),
+                    arity: Arity::Exact(2),
+                    docs: vec![
+                        "Adds numbers.".to_string(),
+                        "".to_string(),
+                        "Returns <a + b>.".to_string(),
+                    ],
+                    line: 7,
+                },
+                FunctionDoc {
+                    name: "log".to_string(),
+                    parameters: "format, ...args".to_string(),
+                    arity: Arity::AtLeast(1),
+                    docs: vec![],
+                    line: 9,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let modules = vec![
+            ModuleDoc {
+                name: "lib.monkey".to_string(),
+                functions: extract(&parse(SOURCE), SOURCE),
+            },
+            ModuleDoc {
+                name: "empty.monkey".to_string(),
+                functions: vec![],
+            },
+        ];
+
+        assert_eq!(
+            to_markdown(&modules),
+            "# API documentation
+
+## lib.monkey
+
+### `add(a, b)`
+
+Arity: 2. Defined on line 7.
+
+Adds numbers.
+
+Returns <a + b>.
+
+### `log(format, ...args)`
+
+Arity: 1 or more. Defined on line 9.
+
+## empty.monkey
+
+No functions.
+"
+        );
+    }
+
+    #[test]
+    fn test_to_html() {
+        let modules = vec![ModuleDoc {
+            name: "lib.monkey".to_string(),
+            functions: extract(&parse(SOURCE), SOURCE),
+        }];
+
+        let html = to_html(&modules);
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains(
+            "<h3><code>add(a, b)</code></h3>\n\
+             <p>Arity: 2. Defined on line 7.</p>\n\
+             <p>Adds numbers.</p>\n\
+             <p>Returns &lt;a + b&gt;.</p>\n"
+        ));
+        assert!(html.ends_with("<p>Arity: 1 or more. Defined on line 9.</p>\n</body>\n</html>\n"));
+    }
+}
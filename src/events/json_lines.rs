@@ -0,0 +1,104 @@
+use std::{io::Write, time::Instant};
+
+use crate::{
+    ast::printers::json::write_string,
+    events::{Event, Subscriber},
+    token::Span,
+};
+
+/// Writes each event as a single line JSON object, along with the
+/// microseconds elapsed since the subscriber was created.
+///
+/// Events that fail to be written are dropped, since tooling shouldn't
+/// make processing the program fail.
+pub struct JsonLinesSubscriber<W: Write> {
+    out: W,
+    start: Instant,
+}
+
+impl<W: Write> JsonLinesSubscriber<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Subscriber for JsonLinesSubscriber<W> {
+    fn on_event(&mut self, event: &Event) {
+        let elapsed = self.start.elapsed().as_micros();
+        let _ = writeln!(self.out, "{}", to_json(event, elapsed));
+    }
+}
+
+fn to_json(event: &Event, elapsed: u128) -> String {
+    let mut out = format!("{{\"time_us\": {elapsed}, \"event\": \"{}\"", event.name());
+
+    match event {
+        Event::ParseStarted => {}
+        Event::StatementParsed { span } => write_span(&mut out, span),
+        Event::ErrorRaised {
+            code,
+            message,
+            span,
+        } => {
+            out.push_str(&format!(", \"code\": \"{code}\", \"message\": "));
+            write_string(&mut out, message);
+            write_span(&mut out, span);
+        }
+        Event::ParseFinished { statements, errors } => {
+            out.push_str(&format!(
+                ", \"statements\": {statements}, \"errors\": {errors}"
+            ));
+        }
+    }
+
+    out.push('}');
+    out
+}
+
+fn write_span(out: &mut String, span: &Span) {
+    out.push_str(&format!(
+        ", \"span\": {{\"start\": {}, \"end\": {}}}",
+        span.start, span.end
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines_subscriber() {
+        let mut subscriber = JsonLinesSubscriber::new(Vec::new());
+        subscriber.on_event(&Event::ErrorRaised {
+            code: "P0001",
+            message: "expected \"=\"",
+            span: Span::new(4, 5),
+        });
+        subscriber.on_event(&Event::ParseFinished {
+            statements: 0,
+            errors: 1,
+        });
+
+        let out = String::from_utf8(subscriber.into_inner()).unwrap();
+        let lines: Vec<_> = out
+            .lines()
+            // The times vary between runs
+            .map(|line| line.split_once(", ").unwrap().1)
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                r#""event": "error_raised", "code": "P0001", "message": "expected \"=\"", "span": {"start": 4, "end": 5}}"#,
+                r#""event": "parse_finished", "statements": 0, "errors": 1}"#,
+            ]
+        );
+    }
+}
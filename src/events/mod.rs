@@ -0,0 +1,63 @@
+//! Structured events reported while processing a program, for tools that
+//! build timelines or profiles.
+//!
+//! The parser reports its events to the [`Subscriber`] set with
+//! [`Parser::set_subscriber`](crate::parser::Parser::set_subscriber).
+
+#[cfg(feature = "std")]
+mod json_lines;
+
+#[cfg(feature = "std")]
+pub use json_lines::JsonLinesSubscriber;
+
+use crate::token::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'e> {
+    ParseStarted,
+    /// A statement was parsed successfully
+    StatementParsed {
+        span: Span,
+    },
+    /// An error was found in the source
+    ErrorRaised {
+        code: &'static str,
+        message: &'e str,
+        span: Span,
+    },
+    ParseFinished {
+        statements: usize,
+        errors: usize,
+    },
+}
+
+impl Event<'_> {
+    /// The name of the event in `snake_case`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::ParseStarted => "parse_started",
+            Event::StatementParsed { .. } => "statement_parsed",
+            Event::ErrorRaised { .. } => "error_raised",
+            Event::ParseFinished { .. } => "parse_finished",
+        }
+    }
+}
+
+/// Receives the events, in the order they happen.
+pub trait Subscriber {
+    fn on_event(&mut self, event: &Event);
+}
+
+impl<S: Subscriber + ?Sized> Subscriber for &mut S {
+    fn on_event(&mut self, event: &Event) {
+        (**self).on_event(event)
+    }
+}
+
+/// Ignores every event. This is the default subscriber.
+#[derive(Debug, Default)]
+pub struct NoopSubscriber;
+
+impl Subscriber for NoopSubscriber {
+    fn on_event(&mut self, _event: &Event) {}
+}
@@ -0,0 +1,274 @@
+//! Lessons of the guided tutorial run by `monkey learn`.
+//!
+//! The tutorial follows the stages of the interpreter: each chapter
+//! teaches one of them, and each lesson asks for some Monkey code which is
+//! checked by running it through that stage, e.g. the tokens of the answer
+//! are compared to the tokens of the expected code.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    lexer::Lexer,
+    parser::Parser,
+    spec::Outcome,
+    token::{Token, TokenType},
+};
+
+/// A group of lessons about one stage of the interpreter.
+pub struct Chapter {
+    /// Short name, used in lesson ids
+    pub name: &'static str,
+    pub title: &'static str,
+    pub lessons: &'static [Lesson],
+}
+
+/// A single question of the tutorial.
+pub struct Lesson {
+    /// Short name, unique within its chapter
+    pub name: &'static str,
+    /// Explanation of the concept, ending with what to type
+    pub prompt: &'static str,
+    pub check: Check,
+}
+
+/// How the answer to a lesson is checked.
+pub enum Check {
+    /// The answer must lex to the same tokens as this code, ignoring
+    /// whitespace and semicolons
+    Tokens(&'static str),
+    /// The answer must parse to these S-expressions, see
+    /// [`Program::to_sexpr`](crate::ast::Program::to_sexpr)
+    Sexpr(&'static str),
+}
+
+pub const CHAPTERS: &[Chapter] = &[
+    Chapter {
+        name: "tokens",
+        title: "Tokens",
+        lessons: &[
+            Lesson {
+                name: "let",
+                prompt: "The lexer splits source code into tokens, such as keywords, \
+                         identifiers and operators, skipping the whitespace between them.\n\
+                         Type a let statement binding the name `five` to the number 5.",
+                check: Check::Tokens("let five = 5;"),
+            },
+            Lesson {
+                name: "operators",
+                prompt: "Some operators, such as `==` and `!=`, are made of two characters \
+                         but still form a single token.\n\
+                         Type an expression checking that 10 is not equal to 9.",
+                check: Check::Tokens("10 != 9"),
+            },
+            Lesson {
+                name: "functions",
+                prompt: "`fn` is a keyword, while the names of parameters are identifiers, \
+                         separated by comma tokens.\n\
+                         Type a function literal with the parameters x and y, returning x + y.",
+                check: Check::Tokens("fn(x, y) { x + y }"),
+            },
+        ],
+    },
+    Chapter {
+        name: "parsing",
+        title: "Parsing",
+        lessons: &[
+            Lesson {
+                name: "precedence",
+                prompt: "The parser groups tokens into a tree, following the precedence of \
+                         operators: `*` binds tighter than `+`.\n\
+                         Type an expression adding 1 to the product of 2 and 3.",
+                check: Check::Sexpr("(+ 1 (* 2 3))"),
+            },
+            Lesson {
+                name: "grouping",
+                prompt: "Parentheses override precedence, but leave no node of their own \
+                         in the tree.\n\
+                         Type an expression multiplying the sum of 1 and 2 by 3.",
+                check: Check::Sexpr("(* (+ 1 2) 3)"),
+            },
+            Lesson {
+                name: "calls",
+                prompt: "A call is parsed as an operator too, binding tighter than any \
+                         other.\n\
+                         Type a call of `add` with the arguments 1 and the negation of x.",
+                check: Check::Sexpr("(call add 1 (- x))"),
+            },
+        ],
+    },
+];
+
+impl Lesson {
+    /// Checks `answer` against the expected result of the lesson.
+    pub fn check(&self, answer: &str) -> Outcome {
+        match self.check {
+            Check::Tokens(expected) => compare_tokens(&tokens(expected), &tokens(answer)),
+            Check::Sexpr(expected) => match Parser::parse(answer) {
+                Ok(program) => {
+                    let actual = program.to_sexpr();
+                    if actual.trim_end() == expected {
+                        Outcome::Passed
+                    } else {
+                        Outcome::Failed(format!(
+                            "expected the tree {expected}, got {}",
+                            actual.trim_end()
+                        ))
+                    }
+                }
+                Err(errors) => Outcome::Failed(errors[0].to_string()),
+            },
+        }
+    }
+}
+
+/// Returns the tokens of `source`, without semicolons and the final
+/// end of input.
+pub fn tokens(source: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token();
+        match token.token_type {
+            TokenType::Eof => return tokens,
+            TokenType::Semicolon => {}
+            _ => tokens.push(token),
+        }
+    }
+}
+
+fn compare_tokens(expected: &[Token], actual: &[Token]) -> Outcome {
+    for (i, expected) in expected.iter().enumerate() {
+        match actual.get(i) {
+            Some(actual)
+                if actual.token_type == expected.token_type
+                    && actual.literal == expected.literal => {}
+            Some(actual) => {
+                return Outcome::Failed(format!(
+                    "expected token {} to be `{}`, got `{}`",
+                    i + 1,
+                    expected.literal,
+                    actual.literal
+                ));
+            }
+            None => {
+                return Outcome::Failed(format!(
+                    "expected token {} to be `{}`, got none",
+                    i + 1,
+                    expected.literal
+                ));
+            }
+        }
+    }
+
+    match actual.get(expected.len()) {
+        Some(unexpected) => Outcome::Failed(format!("unexpected token `{}`", unexpected.literal)),
+        None => Outcome::Passed,
+    }
+}
+
+/// The lessons completed so far, by id (`chapter/lesson`).
+#[derive(Debug, Default, PartialEq)]
+pub struct Progress {
+    completed: BTreeSet<String>,
+}
+
+impl Progress {
+    /// Reads the progress saved by [`Progress::to_state`], one lesson id
+    /// per line.
+    pub fn from_state(state: &str) -> Self {
+        Self {
+            completed: state
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    pub fn to_state(&self) -> String {
+        self.completed.iter().map(|id| format!("{id}\n")).collect()
+    }
+
+    pub fn is_completed(&self, chapter: &Chapter, lesson: &Lesson) -> bool {
+        self.completed.contains(&lesson_id(chapter, lesson))
+    }
+
+    pub fn complete(&mut self, chapter: &Chapter, lesson: &Lesson) {
+        self.completed.insert(lesson_id(chapter, lesson));
+    }
+}
+
+fn lesson_id(chapter: &Chapter, lesson: &Lesson) -> String {
+    format!("{}/{}", chapter.name, lesson.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lesson(chapter: &str, name: &str) -> &'static Lesson {
+        let chapter = CHAPTERS.iter().find(|c| c.name == chapter).unwrap();
+        chapter.lessons.iter().find(|l| l.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let tests = vec![
+            ("tokens", "let", "let   five=5", Outcome::Passed),
+            (
+                "tokens",
+                "let",
+                "let five == 5;",
+                Outcome::Failed("expected token 3 to be `=`, got `==`".to_string()),
+            ),
+            (
+                "tokens",
+                "operators",
+                "10 != 9 + 1",
+                Outcome::Failed("unexpected token `+`".to_string()),
+            ),
+            (
+                "tokens",
+                "functions",
+                "fn(x, y) {",
+                Outcome::Failed("expected token 8 to be `x`, got none".to_string()),
+            ),
+            ("parsing", "precedence", "1 + (2 * 3);", Outcome::Passed),
+            (
+                "parsing",
+                "grouping",
+                "1 + 2 * 3",
+                Outcome::Failed("expected the tree (* (+ 1 2) 3), got (+ 1 (* 2 3))".to_string()),
+            ),
+            (
+                "parsing",
+                "calls",
+                "add(1, ",
+                Outcome::Failed("no prefix parse function for \"end of input\" found".to_string()),
+            ),
+        ];
+
+        for (chapter, name, answer, expected) in tests {
+            assert_eq!(
+                lesson(chapter, name).check(answer),
+                expected,
+                "answer: {answer}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::from_state("parsing/calls\n\ntokens/let\n");
+        let tokens = &CHAPTERS[0];
+        assert!(progress.is_completed(tokens, &tokens.lessons[0]));
+        assert!(!progress.is_completed(tokens, &tokens.lessons[1]));
+
+        progress.complete(tokens, &tokens.lessons[1]);
+        assert_eq!(
+            progress.to_state(),
+            "parsing/calls\ntokens/let\ntokens/operators\n"
+        );
+    }
+}
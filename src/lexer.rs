@@ -1,55 +1,247 @@
-use std::collections::HashMap;
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 
-use once_cell::sync::Lazy;
+use once_cell::race::OnceBox;
 
-use crate::token::{Token, TokenType};
+use crate::token::{Comment, Span, Token, TokenType};
 
-static KEYWORDS: Lazy<HashMap<&str, TokenType>> = Lazy::new(|| {
-    let mut keywords = HashMap::new();
-    keywords.insert("fn", TokenType::Function);
-    keywords.insert("let", TokenType::Let);
-    keywords.insert("true", TokenType::True);
-    keywords.insert("false", TokenType::False);
-    keywords.insert("if", TokenType::If);
-    keywords.insert("else", TokenType::Else);
-    keywords.insert("return", TokenType::Return);
+static KEYWORDS: OnceBox<Keywords> = OnceBox::new();
 
-    keywords
-});
+/// The words the lexer reads as keywords instead of identifiers.
+///
+/// The default set is the one of the Monkey language, and can be extended
+/// or replaced to build dialects, e.g. with localized keywords:
+///
+/// ```
+/// use writing_an_interpreter_book::{
+///     lexer::{Keywords, Lexer},
+///     token::TokenType,
+/// };
+///
+/// let mut keywords = Keywords::default();
+/// keywords.insert("sea", TokenType::Let);
+///
+/// let mut lexer = Lexer::with_keywords("sea x = 5;", &keywords);
+/// assert_eq!(lexer.next_token().token_type, TokenType::Let);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keywords {
+    words: BTreeMap<String, TokenType>,
+}
+
+impl Keywords {
+    /// Returns a set without any keywords.
+    pub fn empty() -> Self {
+        Self {
+            words: BTreeMap::new(),
+        }
+    }
+
+    /// Makes `word` a keyword producing tokens of `token_type`.
+    pub fn insert(&mut self, word: &str, token_type: TokenType) {
+        self.words.insert(word.to_string(), token_type);
+    }
+
+    /// Makes `word` an identifier again.
+    pub fn remove(&mut self, word: &str) {
+        self.words.remove(word);
+    }
+
+    pub fn get(&self, word: &str) -> Option<&TokenType> {
+        self.words.get(word)
+    }
+}
+
+impl Default for Keywords {
+    fn default() -> Self {
+        let mut keywords = Keywords::empty();
+        keywords.insert("fn", TokenType::Function);
+        keywords.insert("let", TokenType::Let);
+        keywords.insert("const", TokenType::Const);
+        keywords.insert("true", TokenType::True);
+        keywords.insert("false", TokenType::False);
+        keywords.insert("if", TokenType::If);
+        keywords.insert("else", TokenType::Else);
+        keywords.insert("return", TokenType::Return);
+        keywords.insert("try", TokenType::Try);
+        keywords.insert("catch", TokenType::Catch);
+        keywords.insert("throw", TokenType::Throw);
+        keywords.insert("defer", TokenType::Defer);
+        keywords.insert("switch", TokenType::Switch);
+        keywords.insert("case", TokenType::Case);
+        keywords.insert("default", TokenType::Default);
+        keywords.insert("do", TokenType::Do);
+        keywords.insert("while", TokenType::While);
+        keywords.insert("for", TokenType::For);
+        keywords.insert("in", TokenType::In);
+        keywords.insert("enum", TokenType::Enum);
+
+        keywords
+    }
+}
+
+/// Symbols of the built-in operators and punctuation, which custom
+/// operators can't reuse.
+const BUILTIN_OPERATORS: [&str; 17] = [
+    "=", "+", "-", "!", "*", "/", "<", ">", "==", "!=", ":", "...", "|>", "#", ".", "??", "?.",
+];
 
 pub struct Lexer<'a> {
     pub input: &'a str,
     pub position: usize,
     pub read_position: usize,
     pub ch: Option<char>,
+    /// Symbols of custom operators, longest first so the longest match wins
+    operators: Vec<String>,
+    keywords: &'a Keywords,
+    /// The comments skipped so far, if they're kept
+    comments: Option<Vec<Comment>>,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
+        Self::with_keywords(
+            input,
+            KEYWORDS.get_or_init(|| Box::new(Keywords::default())),
+        )
+    }
+
+    /// Creates a lexer reading `keywords` instead of the default keywords.
+    pub fn with_keywords(input: &'a str, keywords: &'a Keywords) -> Self {
         let mut lexer = Self {
             input,
             position: 0,
             read_position: 0,
             ch: None,
+            operators: Vec::new(),
+            keywords,
+            comments: None,
         };
 
         lexer.read_char();
         lexer
     }
 
-    pub fn read_char(&mut self) {
-        if self.read_position >= self.input.len() {
-            self.ch = None;
-        } else {
-            self.ch = self.input.chars().nth(self.read_position);
+    /// Makes the lexer produce a `TokenType::Operator` token for `symbol`.
+    ///
+    /// The symbol must be made of punctuation other than delimiters, and
+    /// can't be a built-in operator or start a comment. Custom operators
+    /// are matched before built-in ones, so it also can't start a longer
+    /// built-in token, like `|` does `|>`, or be read as operators that
+    /// can already follow each other, like `!!` in `!!x`.
+    pub fn add_operator(&mut self, symbol: &str) -> Result<(), String> {
+        let valid_char = |ch: char| ch.is_ascii_punctuation() && !"(){}[],;_".contains(ch);
+
+        if symbol.is_empty() || !symbol.chars().all(valid_char) {
+            return Err(format!(
+                "invalid operator \"{symbol}\": operators must be made of punctuation"
+            ));
+        }
+
+        if BUILTIN_OPERATORS.contains(&symbol) || symbol.starts_with("//") {
+            return Err(format!("invalid operator \"{symbol}\": symbol is reserved"));
         }
+
+        if let Some(builtin) = BUILTIN_OPERATORS.iter().find(|op| op.starts_with(symbol)) {
+            return Err(format!(
+                "invalid operator \"{symbol}\": symbol would hide the `{builtin}` operator"
+            ));
+        }
+
+        // Prefix operators can follow any operator, as in `a * -b`
+        let mut lexer = Lexer::new(symbol);
+        let mut tokens = Vec::new();
+        loop {
+            match lexer.next_token().token_type {
+                TokenType::Eof => break,
+                token_type => tokens.push(token_type),
+            }
+        }
+        if let [first, rest @ ..] = tokens.as_slice() {
+            let prefix =
+                |t: &TokenType| matches!(t, TokenType::Bang | TokenType::Minus | TokenType::Plus);
+            if *first != TokenType::Illegal && !rest.is_empty() && rest.iter().all(prefix) {
+                return Err(format!(
+                    "invalid operator \"{symbol}\": symbol already reads as a sequence of operators"
+                ));
+            }
+        }
+
+        if !self.operators.iter().any(|op| op == symbol) {
+            self.operators.push(symbol.to_string());
+            self.operators
+                .sort_by_key(|op| core::cmp::Reverse(op.len()));
+        }
+
+        Ok(())
+    }
+
+    /// Makes the lexer keep the comments it skips, to be collected with
+    /// [`Lexer::take_comments`].
+    pub fn keep_comments(&mut self) {
+        self.comments.get_or_insert_with(Vec::new);
+    }
+
+    /// Whether the lexer keeps the comments it skips.
+    pub fn keeps_comments(&self) -> bool {
+        self.comments.is_some()
+    }
+
+    /// Returns the comments skipped since the last call, in source order.
+    /// Comments are only kept after calling [`Lexer::keep_comments`].
+    pub fn take_comments(&mut self) -> Vec<Comment> {
+        self.comments
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Moves back to the beginning of the input.
+    pub fn reset(&mut self) {
+        if let Some(comments) = self.comments.as_mut() {
+            comments.clear();
+        }
+        self.position = 0;
+        self.read_position = 0;
+        self.read_char();
+    }
+
+    /// Advances to the next character of the input. Positions are byte
+    /// offsets, so they can be used to slice `self.input` directly.
+    pub fn read_char(&mut self) {
+        self.ch = self
+            .input
+            .get(self.read_position..)
+            .and_then(|rest| rest.chars().next());
         self.position = self.read_position;
-        self.read_position += 1;
+        self.read_position += self.ch.map_or(1, char::len_utf8);
     }
 
     pub fn next_token(&mut self) -> Token {
         self.skip_whitespace();
 
+        let start = self.position.min(self.input.len());
+        let mut token = self.read_token();
+        token.span = Span::new(start, self.position.min(self.input.len()));
+        token
+    }
+
+    /// Reads the token starting at the current character
+    fn read_token(&mut self) -> Token {
+        if let Some(symbol) = self.match_operator() {
+            let end = self.position + symbol.len();
+            while self.position < end {
+                self.read_char();
+            }
+            return Token::new(TokenType::Operator, symbol);
+        }
+
         let token: Token = match self.ch {
             Some('=') => {
                 if matches!(self.peek_char(), Some('=')) {
@@ -69,38 +261,100 @@ impl<'a> Lexer<'a> {
                     Token::new(TokenType::Bang, "!".to_string())
                 }
             }
+            Some('|') if matches!(self.peek_char(), Some('>')) => {
+                self.read_char();
+                Token::new(TokenType::Pipe, "|>".to_string())
+            }
+            Some('?') if matches!(self.peek_char(), Some('?')) => {
+                self.read_char();
+                Token::new(TokenType::Coalesce, "??".to_string())
+            }
+            Some('?') if matches!(self.peek_char(), Some('.')) => {
+                self.read_char();
+                Token::new(TokenType::QuestionDot, "?.".to_string())
+            }
             Some('/') => Token::new(TokenType::Slash, "/".to_string()),
             Some('*') => Token::new(TokenType::Asterisk, "*".to_string()),
             Some('<') => Token::new(TokenType::LessThan, "<".to_string()),
             Some('>') => Token::new(TokenType::GreaterThan, ">".to_string()),
             Some(',') => Token::new(TokenType::Comma, ",".to_string()),
             Some(';') => Token::new(TokenType::Semicolon, ";".to_string()),
+            Some(':') => Token::new(TokenType::Colon, ":".to_string()),
+            Some('.') if self.input[self.position..].starts_with("...") => {
+                self.read_char();
+                self.read_char();
+                Token::new(TokenType::Ellipsis, "...".to_string())
+            }
+            Some('.') => Token::new(TokenType::Dot, ".".to_string()),
             Some('(') => Token::new(TokenType::LeftParen, "(".to_string()),
             Some(')') => Token::new(TokenType::RightParen, ")".to_string()),
             Some('{') => Token::new(TokenType::LeftBrace, "{".to_string()),
             Some('}') => Token::new(TokenType::RightBrace, "}".to_string()),
+            Some('[') => Token::new(TokenType::LeftBracket, "[".to_string()),
+            Some(']') => Token::new(TokenType::RightBracket, "]".to_string()),
+            Some('#') if matches!(self.peek_char(), Some('{')) => {
+                self.read_char();
+                Token::new(TokenType::SetBrace, "#{".to_string())
+            }
             Some(ch) => {
                 if Self::is_letter(&ch) {
                     let literal = self.read_identifier();
-                    return Token::new(Self::lookup_ident(literal), literal.to_string());
+                    let token_type = self.lookup_ident(literal);
+                    return Token::new(token_type, literal.to_string());
                 } else if Self::is_digit(&ch) {
-                    let literal = self.read_number();
-                    return Token::new(TokenType::Int, literal.to_string());
+                    let (token_type, literal) = self.read_number();
+                    return Token::new(token_type, literal.to_string());
                 } else {
-                    Token::new(TokenType::Illegal, ch.to_string())
+                    // Report a run of invalid characters as a single token
+                    let start = self.position;
+                    while self.peek_is_illegal() {
+                        self.read_char();
+                    }
+                    let literal = &self.input[start..self.read_position.min(self.input.len())];
+                    Token::new(TokenType::Illegal, literal.to_string())
                 }
             }
-            _ => Token {
-                token_type: TokenType::Eof,
-                literal: "".to_string(),
-            },
+            _ => Token::new(TokenType::Eof, "".to_string()),
         };
 
         self.read_char();
         token
     }
 
-    fn read_identifier(&mut self) -> &str {
+    /// Whether the next character can't start a token either, so it
+    /// belongs to the same `Illegal` token as the current one.
+    fn peek_is_illegal(&self) -> bool {
+        let Some(rest) = self.input.get(self.read_position..) else {
+            return false;
+        };
+        let Some(ch) = rest.chars().next() else {
+            return false;
+        };
+
+        let starts_token = ch.is_whitespace()
+            || Self::is_letter(&ch)
+            || Self::is_digit(&ch)
+            || "=+-!/*<>,;:.(){}[]".contains(ch)
+            || ["|>", "??", "?.", "#{"]
+                .iter()
+                .any(|symbol| rest.starts_with(symbol))
+            || self
+                .operators
+                .iter()
+                .any(|op| rest.starts_with(op.as_str()));
+        !starts_token
+    }
+
+    /// Returns the custom operator starting at the current position, if any
+    fn match_operator(&self) -> Option<String> {
+        let rest = self.input.get(self.position..)?;
+        self.operators
+            .iter()
+            .find(|op| rest.starts_with(op.as_str()))
+            .cloned()
+    }
+
+    fn read_identifier(&mut self) -> &'a str {
         let position = self.position;
         while let Some(ch) = self.ch {
             if Self::is_letter(&ch) {
@@ -110,20 +364,43 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        self.input[position..self.position].as_ref()
+        &self.input[position..self.position]
     }
 
-    fn read_number(&mut self) -> &str {
+    /// Reads an integer like `42`, or a float like `2.5`, `1e9` or `2.5e-3`.
+    ///
+    /// An `e` right after the digits always starts an exponent, so a
+    /// malformed one like `1e+` is still read as a float, for the parser to
+    /// report.
+    fn read_number(&mut self) -> (TokenType, &'a str) {
         let position = self.position;
-        while let Some(ch) = self.ch {
-            if Self::is_digit(&ch) {
+        let mut token_type = TokenType::Int;
+
+        self.skip_digits();
+
+        // A `.` not followed by a digit is a member access, as in `1.max`
+        if self.ch == Some('.') && self.peek_char().is_some_and(|ch| Self::is_digit(&ch)) {
+            token_type = TokenType::Float;
+            self.read_char();
+            self.skip_digits();
+        }
+
+        if matches!(self.ch, Some('e' | 'E')) {
+            token_type = TokenType::Float;
+            self.read_char();
+            if matches!(self.ch, Some('+' | '-')) {
                 self.read_char();
-            } else {
-                break;
             }
+            self.skip_digits();
         }
 
-        self.input[position..self.position].as_ref()
+        (token_type, &self.input[position..self.position])
+    }
+
+    fn skip_digits(&mut self) {
+        while self.ch.is_some_and(|ch| Self::is_digit(&ch)) {
+            self.read_char();
+        }
     }
 
     fn is_letter(ch: &char) -> bool {
@@ -134,30 +411,69 @@ impl<'a> Lexer<'a> {
         ch.is_ascii_digit()
     }
 
-    fn lookup_ident(ident: &str) -> TokenType {
-        match KEYWORDS.get(ident) {
+    fn lookup_ident(&self, ident: &str) -> TokenType {
+        match self.keywords.get(ident) {
             Some(token_type) => token_type.to_owned(),
             None => TokenType::Ident,
         }
     }
 
+    /// Skips whitespace and `//` line comments
     fn skip_whitespace(&mut self) {
         while let Some(ch) = self.ch {
             if ch == ' ' || ch == '\t' || ch == '\n' || ch == '\r' {
                 self.read_char();
+            } else if ch == '/' && matches!(self.peek_char(), Some('/')) {
+                self.skip_comment();
             } else {
                 break;
             }
         }
     }
 
-    fn peek_char(&self) -> Option<char> {
-        if self.read_position >= self.input.len() {
-            None
-        } else {
-            self.input.chars().nth(self.read_position)
+    fn skip_comment(&mut self) {
+        let start = self.position;
+        while let Some(ch) = self.ch {
+            if ch == '\n' {
+                break;
+            }
+            self.read_char();
+        }
+
+        if let Some(comments) = self.comments.as_mut() {
+            let end = self.position.min(self.input.len());
+            comments.push(Comment {
+                text: self.input[start..end].trim_end().to_string(),
+                span: Span::new(start, end),
+            });
         }
     }
+
+    fn peek_char(&self) -> Option<char> {
+        self.input
+            .get(self.read_position..)
+            .and_then(|rest| rest.chars().next())
+    }
+}
+
+/// Describes the characters of an `Illegal` token, with a hint about what
+/// they may have been meant to be, e.g. "unexpected character '@';
+/// identifiers may contain letters and '_'".
+pub fn describe_illegal(literal: &str) -> String {
+    let hint = match literal.chars().next() {
+        Some('"' | '\'' | '`') => "strings aren't supported".to_string(),
+        Some('|') => "did you mean the pipe operator `|>`?".to_string(),
+        Some('?') => "`?` is only valid in `??` and `?.`".to_string(),
+        Some('#') => "`#` is only valid at the start of a set, as in `#{1, 2}`".to_string(),
+        Some(ch @ ('&' | '%' | '^' | '~')) => format!("there is no `{ch}` operator"),
+        _ => "identifiers may contain letters and '_'".to_string(),
+    };
+
+    if literal.chars().count() == 1 {
+        format!("unexpected character '{literal}'; {hint}")
+    } else {
+        format!("unexpected characters '{literal}'; {hint}")
+    }
 }
 
 #[cfg(test)]
@@ -187,6 +503,7 @@ mod tests {
 
           10 == 10;
           10 != 9;
+          try catch throw defer switch case default: do while for in enum ... |> _ _a #{ a.b [ ] ?? a?.b
         "#;
 
         let expected_values = vec![
@@ -263,10 +580,232 @@ mod tests {
             (TokenType::NotEqual, "!="),
             (TokenType::Int, "9"),
             (TokenType::Semicolon, ";"),
+            (TokenType::Try, "try"),
+            (TokenType::Catch, "catch"),
+            (TokenType::Throw, "throw"),
+            (TokenType::Defer, "defer"),
+            (TokenType::Switch, "switch"),
+            (TokenType::Case, "case"),
+            (TokenType::Default, "default"),
+            (TokenType::Colon, ":"),
+            (TokenType::Do, "do"),
+            (TokenType::While, "while"),
+            (TokenType::For, "for"),
+            (TokenType::In, "in"),
+            (TokenType::Enum, "enum"),
+            (TokenType::Ellipsis, "..."),
+            (TokenType::Pipe, "|>"),
+            (TokenType::Ident, "_"),
+            (TokenType::Ident, "_a"),
+            (TokenType::SetBrace, "#{"),
+            (TokenType::Ident, "a"),
+            (TokenType::Dot, "."),
+            (TokenType::Ident, "b"),
+            (TokenType::LeftBracket, "["),
+            (TokenType::RightBracket, "]"),
+            (TokenType::Coalesce, "??"),
+            (TokenType::Ident, "a"),
+            (TokenType::QuestionDot, "?."),
+            (TokenType::Ident, "b"),
+            (TokenType::Eof, ""),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for expected in expected_values.iter() {
+            let token = lexer.next_token();
+
+            assert_eq!(token.token_type, expected.0);
+            assert_eq!(token.literal, expected.1);
+        }
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = "// a comment\nlet x = 5; // trailing\n10 / 2 //";
+
+        let expected_values = vec![
+            (TokenType::Let, "let"),
+            (TokenType::Ident, "x"),
+            (TokenType::Assign, "="),
+            (TokenType::Int, "5"),
+            (TokenType::Semicolon, ";"),
+            (TokenType::Int, "10"),
+            (TokenType::Slash, "/"),
+            (TokenType::Int, "2"),
+            (TokenType::Eof, ""),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for expected in expected_values.iter() {
+            let token = lexer.next_token();
+
+            assert_eq!(token.token_type, expected.0);
+            assert_eq!(token.literal, expected.1);
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        let input = "5 2.5 1e9 2.5e-3 3E+2 1.max 1e 1e+ 4.";
+
+        let expected_values = vec![
+            (TokenType::Int, "5"),
+            (TokenType::Float, "2.5"),
+            (TokenType::Float, "1e9"),
+            (TokenType::Float, "2.5e-3"),
+            (TokenType::Float, "3E+2"),
+            (TokenType::Int, "1"),
+            (TokenType::Dot, "."),
+            (TokenType::Ident, "max"),
+            (TokenType::Float, "1e"),
+            (TokenType::Float, "1e+"),
+            (TokenType::Int, "4"),
+            (TokenType::Dot, "."),
+            (TokenType::Eof, ""),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for expected in expected_values.iter() {
+            let token = lexer.next_token();
+
+            assert_eq!(token.token_type, expected.0);
+            assert_eq!(token.literal, expected.1);
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_input() {
+        let input = "let héllo = 5; ü € 🐒";
+
+        let expected_values = vec![
+            (TokenType::Let, "let"),
+            (TokenType::Ident, "héllo"),
+            (TokenType::Assign, "="),
+            (TokenType::Int, "5"),
+            (TokenType::Semicolon, ";"),
+            (TokenType::Ident, "ü"),
+            (TokenType::Illegal, "€"),
+            (TokenType::Illegal, "🐒"),
+            (TokenType::Eof, ""),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for expected in expected_values.iter() {
+            let token = lexer.next_token();
+
+            assert_eq!(token.token_type, expected.0);
+            assert_eq!(token.literal, expected.1);
+        }
+    }
+
+    #[test]
+    fn test_illegal_characters() {
+        let input = "a @$ b|c ?? d ?e €5 &&";
+
+        let expected_values = vec![
+            (TokenType::Ident, "a"),
+            (TokenType::Illegal, "@$"),
+            (TokenType::Ident, "b"),
+            (TokenType::Illegal, "|"),
+            (TokenType::Ident, "c"),
+            (TokenType::Coalesce, "??"),
+            (TokenType::Ident, "d"),
+            (TokenType::Illegal, "?"),
+            (TokenType::Ident, "e"),
+            (TokenType::Illegal, "€"),
+            (TokenType::Int, "5"),
+            (TokenType::Illegal, "&&"),
+            (TokenType::Eof, ""),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for expected in expected_values.iter() {
+            let token = lexer.next_token();
+
+            assert_eq!(token.token_type, expected.0);
+            assert_eq!(token.literal, expected.1);
+            assert_eq!(&input[token.span.start..token.span.end], expected.1);
+        }
+    }
+
+    #[test]
+    fn test_describe_illegal() {
+        let tests = vec![
+            (
+                "@",
+                "unexpected character '@'; identifiers may contain letters and '_'",
+            ),
+            ("&&", "unexpected characters '&&'; there is no `&` operator"),
+            (
+                "|",
+                "unexpected character '|'; did you mean the pipe operator `|>`?",
+            ),
+            ("\"", "unexpected character '\"'; strings aren't supported"),
+        ];
+
+        for (literal, expected) in tests {
+            assert_eq!(describe_illegal(literal), expected);
+        }
+    }
+
+    #[test]
+    fn test_keep_comments() {
+        let input = "a // one  \n// two\nb";
+
+        let mut lexer = Lexer::new(input);
+        while lexer.next_token().token_type != TokenType::Eof {}
+        assert!(lexer.take_comments().is_empty());
+
+        let mut lexer = Lexer::new(input);
+        lexer.keep_comments();
+        assert_eq!(lexer.next_token().literal, "a");
+        assert_eq!(lexer.next_token().literal, "b");
+        assert_eq!(
+            lexer.take_comments(),
+            vec![
+                Comment {
+                    text: "// one".to_string(),
+                    span: Span::new(2, 10),
+                },
+                Comment {
+                    text: "// two".to_string(),
+                    span: Span::new(11, 17),
+                },
+            ]
+        );
+        assert!(lexer.take_comments().is_empty());
+    }
+
+    #[test]
+    fn test_custom_keywords() {
+        let mut keywords = Keywords::default();
+        keywords.insert("función", TokenType::Function);
+        keywords.insert("sea", TokenType::Let);
+        keywords.remove("fn");
+
+        let input = "sea f = función(x) { fn };";
+
+        let expected_values = vec![
+            (TokenType::Let, "sea"),
+            (TokenType::Ident, "f"),
+            (TokenType::Assign, "="),
+            (TokenType::Function, "función"),
+            (TokenType::LeftParen, "("),
+            (TokenType::Ident, "x"),
+            (TokenType::RightParen, ")"),
+            (TokenType::LeftBrace, "{"),
+            (TokenType::Ident, "fn"),
+            (TokenType::RightBrace, "}"),
+            (TokenType::Semicolon, ";"),
             (TokenType::Eof, ""),
         ];
 
-        let mut lexer = Lexer::new(&input);
+        let mut lexer = Lexer::with_keywords(input, &keywords);
 
         for expected in expected_values.iter() {
             let token = lexer.next_token();
@@ -0,0 +1,50 @@
+//! A Rust implementation of the Monkey language from the book "Writing An
+//! Interpreter In Go" by Thorsten Ball.
+//!
+//! Source code goes through the [`lexer`], producing [`token`]s, and the
+//! [`parser`], producing an [`ast`]. From there, the [`analyzer`] reports
+//! likely mistakes, [`codegen`] compiles programs to other languages and
+//! [`doc`] documents their functions.
+//! The `monkey` binary wraps these in a REPL and a command line interface.
+//!
+//! ```
+//! use writing_an_interpreter_book::parser::Parser;
+//!
+//! let program = Parser::parse("let x = 1; return x; x").unwrap();
+//! assert_eq!(program.to_sexpr(), "(let x 1)\n(return x)\nx\n");
+//!
+//! # #[cfg(feature = "std")]
+//! # {
+//! use writing_an_interpreter_book::analyzer;
+//!
+//! let diagnostics = analyzer::analyze(&program);
+//! assert_eq!(diagnostics[0].code, "A0001");
+//! # }
+//! ```
+//!
+//! The lexer, parser and AST only need an allocator. Disabling the default
+//! `std` feature builds just them, for `no_std` environments.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod analyzer;
+pub mod ast;
+#[cfg(feature = "std")]
+pub mod codegen;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod doc;
+pub mod events;
+#[cfg(feature = "std")]
+pub mod learn;
+pub mod lexer;
+pub mod parser;
+#[cfg(feature = "std")]
+pub mod refactor;
+#[cfg(feature = "std")]
+pub mod spec;
+pub mod token;
@@ -1,9 +1,9 @@
-mod ast;
-mod lexer;
-mod parser;
+mod cli;
 mod repl;
-mod token;
+
+use std::{env, process};
 
 fn main() {
-    repl::start();
+    let args: Vec<String> = env::args().skip(1).collect();
+    process::exit(cli::run(&args));
 }
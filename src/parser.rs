@@ -1,21 +1,44 @@
-use std::{borrow::BorrowMut, mem};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{borrow::BorrowMut, error::Error, fmt::Display, mem};
 
 use crate::{
     ast::{
         self,
-        expressions::{IdentExpression, InfixExpression, IntegerLiteral, PrefixExpression},
-        statements::{ExpressionStatement, LetStatement, ReturnStatement},
-        Expression,
+        expressions::{
+            ArrayLiteral, BooleanLiteral, CallExpression, FloatLiteral, FunctionLiteral,
+            IdentExpression, IfExpression, InfixExpression, IntegerLiteral, MemberExpression,
+            NamedArgument, PipeExpression, PlaceholderExpression, PrefixExpression, SetLiteral,
+            SpreadExpression, TryExpression,
+        },
+        patterns::{ArrayPattern, HashPattern, Pattern},
+        statements::{
+            BlockStatement, DeferStatement, DoWhileStatement, EnumStatement, EnumVariant,
+            ExpressionStatement, ForInStatement, LetStatement, ReturnStatement, SwitchCase,
+            SwitchStatement, ThrowStatement,
+        },
+        Comments, Expression,
     },
-    lexer::Lexer,
-    token::{Token, TokenType},
+    events::{Event, NoopSubscriber, Subscriber},
+    lexer::{self, Lexer},
+    token::{Comment, Span, Token, TokenType},
 };
 
 /// Enum containing the operators in the language, so we can assign
 /// them to a precedence level.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Precedence {
     /// The lowest level of precedence
     Lowest,
+    /// For `??` operators
+    Coalesce,
+    /// For `|>` operators
+    Pipe,
     /// For `==` operators
     Equals,
     /// For `>` or `<` operators
@@ -35,26 +58,112 @@ impl Precedence {
     pub fn value(&self) -> usize {
         match self {
             Precedence::Lowest => 1,
-            Precedence::Equals => 2,
-            Precedence::LessGreater => 3,
-            Precedence::Sum => 4,
-            Precedence::Product => 5,
-            Precedence::Prefix => 6,
-            Precedence::Call => 7,
+            Precedence::Coalesce => 2,
+            Precedence::Pipe => 3,
+            Precedence::Equals => 4,
+            Precedence::LessGreater => 5,
+            Precedence::Sum => 6,
+            Precedence::Product => 7,
+            Precedence::Prefix => 8,
+            Precedence::Call => 9,
+        }
+    }
+}
+
+/// How operators of the same precedence group together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Associativity {
+    /// `a op b op c` is parsed as `(a op b) op c`
+    Left,
+    /// `a op b op c` is parsed as `a op (b op c)`
+    Right,
+}
+
+/// A custom infix operator, registered with
+/// [`Parser::register_infix_operator`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfixOperator {
+    pub symbol: String,
+    pub precedence: Precedence,
+    pub associativity: Associativity,
+}
+
+/// An error found while parsing the input.
+///
+/// Each kind of error has a stable code, so tools can recognize it
+/// regardless of the wording of the message:
+///
+/// | Code  | Error                                            |
+/// |-------|--------------------------------------------------|
+/// | P0001 | unexpected token                                 |
+/// | P0002 | token that can't start an expression             |
+/// | P0003 | block not closed before the end of the input     |
+/// | P0004 | invalid number literal                           |
+/// | P0005 | named argument given twice                       |
+/// | P0006 | positional argument after named arguments        |
+/// | P0007 | switch with more than one `default`              |
+/// | P0008 | enum variant declared twice                      |
+/// | P0009 | code nested deeper than the limit                |
+/// | P0010 | characters that can't start any token            |
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserError {
+    pub code: &'static str,
+    pub message: String,
+    /// The span of the token the error was found at
+    pub span: Span,
+}
+
+impl ParserError {
+    pub fn new(code: &'static str, message: String, span: Span) -> Self {
+        Self {
+            code,
+            message,
+            span,
         }
     }
 }
 
-struct Parser<'a> {
+impl Display for ParserError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ParserError {}
+
+pub struct Parser<'a> {
     lexer: Lexer<'a>,
     /// The current token being parsed
     cur_token: Token,
     /// The next token to parse
     peek_token: Token,
     /// The list of parsing errors
-    errors: Vec<String>,
+    errors: Vec<ParserError>,
+    /// Custom infix operators, by symbol
+    operators: BTreeMap<String, InfixOperator>,
+    /// Receives the events of parsing the program
+    subscriber: Box<dyn Subscriber + 'a>,
+    /// How many expressions, blocks and patterns are being parsed, one
+    /// inside the other
+    depth: usize,
+    max_depth: usize,
+    /// The comments attached so far, if they're kept
+    comments: Comments,
+    /// Comments read by the lexer but not attached yet
+    pending_comments: Vec<Comment>,
 }
 
+/// How deeply expressions and blocks can be nested by default. Each level
+/// recurses, so without a limit a long run of `(` or `-` overflows the
+/// stack. This many levels fit in a 2 MiB thread stack, even in debug
+/// builds.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// How many operations of a flat chain like `1 + 2 + 3` count as one
+/// nesting level. The chain is parsed in a loop, but it nests the tree as
+/// deeply as it is long.
+const OPERATIONS_PER_LEVEL: usize = 8;
+
 impl<'a> Parser<'a> {
     pub fn new(lexer: Lexer<'a>) -> Parser<'a> {
         let mut lexer = lexer;
@@ -66,29 +175,124 @@ impl<'a> Parser<'a> {
             cur_token,
             peek_token,
             errors: Vec::new(),
+            operators: BTreeMap::new(),
+            subscriber: Box::new(NoopSubscriber),
+            depth: 0,
+            comments: Comments::default(),
+            pending_comments: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets how deeply expressions, blocks and patterns can be nested
+    /// before parsing fails, instead of [`DEFAULT_MAX_DEPTH`].
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Sets the subscriber receiving the events of parsing the program,
+    /// replacing the default one that ignores them.
+    pub fn set_subscriber(&mut self, subscriber: impl Subscriber + 'a) {
+        self.subscriber = Box::new(subscriber);
+    }
+
+    /// Registers a custom infix operator, e.g. `<>` for concatenation.
+    ///
+    /// The symbol must be made of punctuation and can't be one of the
+    /// language's own operators. It must be registered before parsing
+    /// starts, since the input is lexed again from the beginning.
+    pub fn register_infix_operator(&mut self, operator: InfixOperator) -> Result<(), String> {
+        self.lexer.add_operator(&operator.symbol)?;
+        self.operators.insert(operator.symbol.clone(), operator);
+
+        self.lexer.reset();
+        self.cur_token = self.lexer.next_token();
+        self.peek_token = self.lexer.next_token();
+
+        Ok(())
+    }
+
+    /// Keeps the comments of the input in
+    /// [`Program::comments`](ast::Program::comments), attached to the
+    /// statements around them, instead of skipping them.
+    ///
+    /// It must be called before parsing starts, since the input is lexed
+    /// again from the beginning.
+    pub fn keep_comments(&mut self) {
+        self.lexer.keep_comments();
+        self.pending_comments.clear();
+
+        self.lexer.reset();
+        self.cur_token = self.lexer.next_token();
+        self.peek_token = self.lexer.next_token();
+    }
+
+    /// Parses `source` into a program, returning every error found if the
+    /// source isn't valid.
+    pub fn parse(source: &str) -> Result<ast::Program, Vec<ParserError>> {
+        let mut parser = Parser::new(Lexer::new(source));
+        let program = parser.parse_program();
+
+        if parser.errors.is_empty() {
+            Ok(program)
+        } else {
+            Err(parser.errors)
         }
     }
 
-    /// Starts parsing the input
+    /// Starts parsing the input. Statements that fail to parse are left out
+    /// of the program and reported through [`Parser::errors`].
     pub fn parse_program(&mut self) -> ast::Program {
         let mut program = ast::Program::new();
+        self.subscriber.on_event(&Event::ParseStarted);
 
         while !self.cur_token_is(&TokenType::Eof) {
-            if let Some(s) = self.parse_statement() {
-                program.statements.push(s);
+            let errors = self.errors.len();
+            match self.parse_statement() {
+                Some(s) => {
+                    let span = s.span();
+                    program.statements.push(s);
+                    self.subscriber.on_event(&Event::StatementParsed { span });
+                }
+                None => self.skip_statement(),
+            }
+
+            for error in self.errors[errors..].iter() {
+                self.subscriber.on_event(&Event::ErrorRaised {
+                    code: error.code,
+                    message: &error.message,
+                    span: error.span,
+                });
             }
 
             self.next_token();
         }
 
+        for comment in self.take_comments_before(usize::MAX) {
+            self.comments.add_program_end(comment);
+        }
+        program.comments = mem::take(&mut self.comments);
+
+        self.subscriber.on_event(&Event::ParseFinished {
+            statements: program.statements.len(),
+            errors: self.errors.len(),
+        });
         program
     }
 
     /// Returns the list of parsing errors
-    pub fn errors(&self) -> &Vec<String> {
+    pub fn errors(&self) -> &Vec<ParserError> {
         &self.errors
     }
 
+    /// Skips the rest of a statement that failed to parse, so a single
+    /// mistake doesn't produce a cascade of errors for the tokens after it.
+    fn skip_statement(&mut self) {
+        while !self.cur_token_is(&TokenType::Semicolon) && !self.cur_token_is(&TokenType::Eof) {
+            self.next_token();
+        }
+    }
+
     /// Advance to the next token
     fn next_token(&mut self) {
         // Replaces the value of both `self.cur_token` and `self.peek_token`:
@@ -103,7 +307,16 @@ impl<'a> Parser<'a> {
     }
 
     fn cur_precedence(&self) -> Precedence {
-        self.cur_token.token_type.precedence()
+        self.precedence_of(&self.cur_token)
+    }
+
+    /// Returns the precedence of `token`, taking custom operators into
+    /// account.
+    fn precedence_of(&self, token: &Token) -> Precedence {
+        match self.operators.get(&token.literal) {
+            Some(operator) if token.token_type == TokenType::Operator => operator.precedence,
+            _ => token.token_type.precedence(),
+        }
     }
 
     /// Checks if the peek token is of a given type
@@ -113,16 +326,45 @@ impl<'a> Parser<'a> {
 
     /// Writes a parse error when the next token isn't the one expected
     fn peek_error(&mut self, token_type: &TokenType) {
+        if self.peek_token.token_type == TokenType::Illegal {
+            let token = self.peek_token.clone();
+            return self.illegal_error(&token);
+        }
+
         let error_msg = format!(
             "expected next token to be \"{}\", got \"{}\" instead",
             token_type.get_literal(),
             self.peek_token.token_type.get_literal()
         );
-        self.errors.push(error_msg);
+        let error = ParserError::new("P0001", error_msg, self.peek_token.span);
+        self.errors.push(error);
+    }
+
+    /// Writes a parse error when the current token can't start an expression
+    fn no_prefix_parse_error(&mut self) {
+        if self.cur_token.token_type == TokenType::Illegal {
+            let token = self.cur_token.clone();
+            return self.illegal_error(&token);
+        }
+
+        let error_msg = format!(
+            "no prefix parse function for \"{}\" found",
+            self.cur_token.token_type.get_literal()
+        );
+        let error = ParserError::new("P0002", error_msg, self.cur_token.span);
+        self.errors.push(error);
+    }
+
+    /// Writes a parse error for characters the lexer couldn't read as a
+    /// token, with a hint about what they may have been meant to be
+    fn illegal_error(&mut self, token: &Token) {
+        let error_msg = lexer::describe_illegal(&token.literal);
+        let error = ParserError::new("P0010", error_msg, token.span);
+        self.errors.push(error);
     }
 
     fn peek_precedence(&self) -> Precedence {
-        self.peek_token.token_type.precedence()
+        self.precedence_of(&self.peek_token)
     }
 
     fn expect_peek(&mut self, token_type: &TokenType) -> bool {
@@ -135,15 +377,76 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Returns the pending comments that end before `position`, in source
+    /// order.
+    fn take_comments_before(&mut self, position: usize) -> Vec<Comment> {
+        self.pending_comments.extend(self.lexer.take_comments());
+        let count = self
+            .pending_comments
+            .iter()
+            .take_while(|comment| comment.span.end <= position)
+            .count();
+        self.pending_comments.drain(..count).collect()
+    }
+
+    /// Parses the statement starting at `self.cur_token`, attaching the
+    /// comments before and inside it that no inner statement took.
     fn parse_statement(&mut self) -> Option<ast::Statement> {
+        let mut comments = self.take_comments_before(self.cur_token.span.start);
+
+        let Some(statement) = self.parse_statement_kind() else {
+            // Left for the node after the statement
+            comments.append(&mut self.pending_comments);
+            self.pending_comments = comments;
+            return None;
+        };
+
+        // The blank line is kept before the comments leading the statement
+        let start = comments
+            .first()
+            .map_or(statement.span().start, |c| c.span.start);
+        let space = &self.lexer.input[..start];
+        let space = &space[space.trim_end().len()..];
+        if self.lexer.keeps_comments() && space.matches('\n').count() > 1 {
+            self.comments.add_blank_line_before(&statement);
+        }
+
+        let end = self.cur_token.span.end;
+        comments.extend(self.take_comments_before(end));
+        for comment in comments {
+            self.comments.add_leading(&statement, comment);
+        }
+
+        // A comment on the same line as the end of the statement trails it
+        self.pending_comments.extend(self.lexer.take_comments());
+        if let Some(comment) = self.pending_comments.first() {
+            if !self.lexer.input[end..comment.span.start].contains('\n') {
+                let comment = self.pending_comments.remove(0);
+                self.comments.set_trailing(&statement, comment);
+            }
+        }
+
+        Some(statement)
+    }
+
+    fn parse_statement_kind(&mut self) -> Option<ast::Statement> {
         match self.cur_token.token_type {
-            TokenType::Let => self.parse_let_statement(),
+            TokenType::Let | TokenType::Const => self.parse_let_statement(),
             TokenType::Return => self.parse_return_statement(),
+            TokenType::Throw => self.parse_throw_statement(),
+            TokenType::Defer => self.parse_defer_statement(),
+            TokenType::Switch => self.parse_switch_statement(),
+            TokenType::Do => self.parse_do_while_statement(),
+            TokenType::For => self.parse_for_in_statement(),
+            TokenType::Enum => self.parse_enum_statement(),
             _ => self.parse_expression_statement(),
         }
     }
 
+    /// Parses `self.cur_token` as a `let` or `const` statement.
     fn parse_let_statement(&mut self) -> Option<ast::Statement> {
+        let token = self.cur_token.clone();
+
         if !self.expect_peek(&TokenType::Ident) {
             return None;
         }
@@ -157,213 +460,1190 @@ impl<'a> Parser<'a> {
             return None;
         }
 
-        while !self.cur_token_is(&TokenType::Semicolon) {
+        self.next_token();
+
+        let value = self.parse_expression(Precedence::Lowest.value())?;
+
+        if self.peek_token_is(&TokenType::Semicolon) {
             self.next_token();
         }
 
-        // TODO: The book left the value undefined, and should come back
-        // to this in the parsing expressions chapter, for now I will
-        // assign a dummy value
-        let dummy_value = IdentExpression {
-            token: self.cur_token.clone(),
-            value: self.cur_token.literal.clone(),
-        };
-
-        let let_stmt = LetStatement {
-            token: self.cur_token.clone(),
-            name,
-            value: Expression::Ident(dummy_value),
-        };
+        let let_stmt = LetStatement { token, name, value };
 
         Some(ast::Statement::Let(let_stmt))
     }
 
     /// Parsers `self.cur_token` as a return statement.
     fn parse_return_statement(&mut self) -> Option<ast::Statement> {
-        // TODO: The book left the value undefined, so I'm using dummy value until the
-        // comes back to this to implement it
-        let dummy_value = IdentExpression {
-            token: Token {
-                token_type: TokenType::Ident,
-                literal: "foo".to_string(),
-            },
-            value: "foo".to_string(),
-        };
-
-        let stmt = ReturnStatement {
-            token: self.cur_token.clone(),
-            value: Expression::Ident(dummy_value),
-        };
+        let token = self.cur_token.clone();
 
         self.next_token();
 
-        while !self.cur_token_is(&TokenType::Semicolon) {
+        let value = self.parse_expression(Precedence::Lowest.value())?;
+
+        if self.peek_token_is(&TokenType::Semicolon) {
             self.next_token();
         }
 
+        let stmt = ReturnStatement { token, value };
+
         Some(ast::Statement::Return(stmt))
     }
 
-    fn parse_identifier(&self) -> Option<ast::Expression> {
-        let ident = IdentExpression {
-            token: self.cur_token.clone(),
-            value: self.cur_token.literal.clone(),
-        };
+    /// Parsers `self.cur_token` as a throw statement.
+    fn parse_throw_statement(&mut self) -> Option<ast::Statement> {
+        let token = self.cur_token.clone();
 
-        Some(ast::Expression::Ident(ident))
-    }
+        self.next_token();
 
-    /// Parsers `self.cur_token` as an integer literal.
-    fn parse_integer_literal(&mut self) -> Option<ast::Expression> {
-        let value = match self.cur_token.literal.parse::<i64>() {
-            Ok(v) => v,
-            Err(e) => {
-                let msg = format!(
-                    "Could not parse {} as integer: {}",
-                    self.cur_token.literal, e
-                );
-                self.errors.push(msg);
-                return None;
-            }
-        };
+        let value = self.parse_expression(Precedence::Lowest.value())?;
 
-        let lit = IntegerLiteral {
-            token: self.cur_token.clone(),
-            value,
-        };
+        if self.peek_token_is(&TokenType::Semicolon) {
+            self.next_token();
+        }
 
-        Some(ast::Expression::Integer(lit))
+        let stmt = ThrowStatement { token, value };
+
+        Some(ast::Statement::Throw(stmt))
     }
 
-    fn parse_prefix_expression(&mut self) -> Option<ast::Expression> {
+    /// Parsers `self.cur_token` as a defer statement.
+    fn parse_defer_statement(&mut self) -> Option<ast::Statement> {
         let token = self.cur_token.clone();
-        let operator = token.literal.clone();
-        self.next_token();
 
-        let right = self.parse_expression(Precedence::Prefix.value())?;
-        let prefix = PrefixExpression {
-            token,
-            operator,
-            right: Box::new(right),
-        };
+        self.next_token();
 
-        Some(ast::Expression::Prefix(prefix))
-    }
+        let value = self.parse_expression(Precedence::Lowest.value())?;
 
-    fn prefix_parse(&mut self) -> Option<ast::Expression> {
-        match self.cur_token.token_type {
-            TokenType::Ident => self.parse_identifier(),
-            TokenType::Int => self.parse_integer_literal(),
-            TokenType::Minus => self.parse_prefix_expression(),
-            TokenType::Bang => self.parse_prefix_expression(),
-            _ => None,
+        if self.peek_token_is(&TokenType::Semicolon) {
+            self.next_token();
         }
+
+        let stmt = DeferStatement { token, value };
+
+        Some(ast::Statement::Defer(stmt))
     }
 
-    fn parse_infix_expression(&mut self, left: ast::Expression) -> Option<ast::Expression> {
+    /// Parses `switch (<subject>) { case <values>: ... default: ... }`,
+    /// leaving `self.cur_token` on the closing brace.
+    fn parse_switch_statement(&mut self) -> Option<ast::Statement> {
         let token = self.cur_token.clone();
-        let operator = token.literal.clone();
-        let precedence = self.cur_precedence();
+
+        if !self.expect_peek(&TokenType::LeftParen) {
+            return None;
+        }
 
         self.next_token();
+        let subject = self.parse_expression(Precedence::Lowest.value())?;
 
-        let right = self.parse_expression(precedence.value())?;
-        let infix = InfixExpression {
-            token,
-            operator,
-            left: Box::new(left),
-            right: Box::new(right),
-        };
+        if !self.expect_peek(&TokenType::RightParen) || !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
 
-        Some(Expression::Infix(infix))
-    }
+        let mut cases = Vec::new();
+        let mut default = None;
+        self.next_token();
 
-    fn parse_expression_statement(&mut self) -> Option<ast::Statement> {
-        let expression = self.parse_expression(Precedence::Lowest.value())?;
+        loop {
+            match self.cur_token.token_type {
+                TokenType::Case => {
+                    let case_token = self.cur_token.clone();
+                    let mut values = Vec::new();
 
-        let stmt = ExpressionStatement {
-            token: self.cur_token.clone(),
-            expression,
-        };
+                    loop {
+                        self.next_token();
+                        values.push(self.parse_expression(Precedence::Lowest.value())?);
+
+                        if !self.peek_token_is(&TokenType::Comma) {
+                            break;
+                        }
+                        self.next_token();
+                    }
+
+                    let body = self.parse_case_body()?;
+                    cases.push(SwitchCase {
+                        token: case_token,
+                        values,
+                        body,
+                    });
+                }
+                TokenType::Default if default.is_none() => {
+                    default = Some(self.parse_case_body()?);
+                }
+                TokenType::Default => {
+                    let error_msg = "switch can't have more than one default".to_string();
+                    let error = ParserError::new("P0007", error_msg, self.cur_token.span);
+                    self.errors.push(error);
+                    return None;
+                }
+                TokenType::RightBrace => break,
+                _ => {
+                    let error_msg = format!(
+                        "expected \"case\", \"default\" or \"}}\" in switch, got \"{}\" instead",
+                        self.cur_token.token_type.get_literal()
+                    );
+                    let error = ParserError::new("P0001", error_msg, self.cur_token.span);
+                    self.errors.push(error);
+                    return None;
+                }
+            }
+        }
 
         if self.peek_token_is(&TokenType::Semicolon) {
             self.next_token();
         }
 
-        Some(ast::Statement::Expression(stmt))
+        let span = token.span.to(self.cur_token.span);
+        let stmt = SwitchStatement {
+            token,
+            subject,
+            cases,
+            default,
+            span,
+        };
+
+        Some(ast::Statement::Switch(stmt))
     }
 
-    fn parse_expression(&mut self, precedence: usize) -> Option<ast::Expression> {
-        let mut left_expression = self.prefix_parse();
+    /// Parses `do { <body> } while (<condition>);`, where the semicolon is
+    /// optional.
+    fn parse_do_while_statement(&mut self) -> Option<ast::Statement> {
+        let token = self.cur_token.clone();
 
-        while !self.peek_token_is(&TokenType::Semicolon)
-            && precedence < self.peek_precedence().value()
-        {
-            if !self.peek_token.token_type.is_infix() {
-                return left_expression;
-            }
+        if !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
 
-            self.next_token();
+        let body = self.parse_block_statement()?;
 
-            left_expression = self.parse_infix_expression(left_expression?);
+        if !self.expect_peek(&TokenType::While) || !self.expect_peek(&TokenType::LeftParen) {
+            return None;
         }
 
-        left_expression
-    }
-}
+        self.next_token();
+        let condition = self.parse_expression(Precedence::Lowest.value())?;
 
-#[cfg(test)]
-mod tests {
-    use core::panic;
+        if !self.expect_peek(&TokenType::RightParen) {
+            return None;
+        }
 
-    use ast::{NodeTrait, Statement};
+        let span = token.span.to(self.cur_token.span);
 
-    use super::*;
+        if self.peek_token_is(&TokenType::Semicolon) {
+            self.next_token();
+        }
 
-    fn test_let_statement(statement: &ast::Statement, name: &str) -> bool {
-        let ast::Statement::Let(statement) = statement else {
-            panic!("Not a Let statement");
+        let stmt = DoWhileStatement {
+            token,
+            body,
+            condition,
+            span,
         };
 
-        if statement.name.value != name {
-            panic!("statement.name.value not {}", name);
+        Some(ast::Statement::DoWhile(stmt))
+    }
+
+    /// Parses `enum <name> { <variant>, <variant>(<fields>) }`.
+    fn parse_enum_statement(&mut self) -> Option<ast::Statement> {
+        let token = self.cur_token.clone();
+
+        if !self.expect_peek(&TokenType::Ident) {
+            return None;
         }
 
-        if statement.name.token_literal() != name {
-            panic!("statement.name.token_literal() not {}", name);
+        let name = IdentExpression {
+            token: self.cur_token.clone(),
+            value: self.cur_token.literal.clone(),
+        };
+
+        if !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
         }
 
-        true
-    }
+        let mut variants: Vec<EnumVariant> = Vec::new();
 
-    fn check_parser_errors(parser: &Parser) {
-        let errors = parser.errors();
-        if errors.len() == 0 {
-            return;
+        if self.peek_token_is(&TokenType::RightBrace) {
+            self.next_token();
+        } else {
+            loop {
+                let variant = self.parse_enum_variant()?;
+
+                if variants.iter().any(|v| v.name == variant.name) {
+                    let error_msg = format!(
+                        "duplicate variant \"{}\" in enum \"{}\"",
+                        variant.name.value, name.value
+                    );
+                    let error = ParserError::new("P0008", error_msg, variant.name.token.span);
+                    self.errors.push(error);
+                    return None;
+                }
+                variants.push(variant);
+
+                if !self.peek_token_is(&TokenType::Comma) {
+                    break;
+                }
+                self.next_token();
+            }
+
+            if !self.expect_peek(&TokenType::RightBrace) {
+                return None;
+            }
         }
 
-        eprintln!("Parser has {} errors", errors.len());
-        for error in errors.iter() {
-            eprintln!("Parser error: {}", error)
+        let span = token.span.to(self.cur_token.span);
+
+        if self.peek_token_is(&TokenType::Semicolon) {
+            self.next_token();
         }
-        panic!()
-    }
 
-    fn test_integer_literal(expression: &Expression, value: &i64) -> bool {
-        let Expression::Integer(int) = expression else {
-            eprintln!(
-                "Expression isn't an Integer, got {}",
-                expression.to_string()
-            );
-            return false;
+        let stmt = EnumStatement {
+            token,
+            name,
+            variants,
+            span,
         };
 
-        if &int.value != value {
-            eprintln!("Integer value is not \"{}\", got \"{}\"", value, int.value);
-            return false;
-        }
+        Some(ast::Statement::Enum(stmt))
+    }
 
-        if int.token_literal() != value.to_string() {
+    /// Parses a variant of an enum, starting at the token before its name.
+    fn parse_enum_variant(&mut self) -> Option<EnumVariant> {
+        if !self.expect_peek(&TokenType::Ident) {
+            return None;
+        }
+
+        let name = IdentExpression {
+            token: self.cur_token.clone(),
+            value: self.cur_token.literal.clone(),
+        };
+
+        let mut fields = Vec::new();
+        if self.peek_token_is(&TokenType::LeftParen) {
+            self.next_token();
+            loop {
+                if !self.expect_peek(&TokenType::Ident) {
+                    return None;
+                }
+
+                fields.push(IdentExpression {
+                    token: self.cur_token.clone(),
+                    value: self.cur_token.literal.clone(),
+                });
+
+                if !self.peek_token_is(&TokenType::Comma) {
+                    break;
+                }
+                self.next_token();
+            }
+
+            if !self.expect_peek(&TokenType::RightParen) {
+                return None;
+            }
+        }
+
+        Some(EnumVariant { name, fields })
+    }
+
+    /// Parses `for (<name> in <iterable>) { <body> }`, or
+    /// `for (<key>, <value> in <iterable>) { <body> }`.
+    fn parse_for_in_statement(&mut self) -> Option<ast::Statement> {
+        let token = self.cur_token.clone();
+
+        if !self.expect_peek(&TokenType::LeftParen) {
+            return None;
+        }
+
+        let mut bindings = Vec::new();
+        loop {
+            if !self.expect_peek(&TokenType::Ident) {
+                return None;
+            }
+
+            bindings.push(IdentExpression {
+                token: self.cur_token.clone(),
+                value: self.cur_token.literal.clone(),
+            });
+
+            if bindings.len() == 2 || !self.peek_token_is(&TokenType::Comma) {
+                break;
+            }
+            self.next_token();
+        }
+
+        if !self.expect_peek(&TokenType::In) {
+            return None;
+        }
+
+        self.next_token();
+        let iterable = self.parse_expression(Precedence::Lowest.value())?;
+
+        if !self.expect_peek(&TokenType::RightParen) || !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement()?;
+
+        if self.peek_token_is(&TokenType::Semicolon) {
+            self.next_token();
+        }
+
+        let stmt = ForInStatement {
+            token,
+            bindings,
+            iterable,
+            body,
+        };
+
+        Some(ast::Statement::ForIn(stmt))
+    }
+
+    /// Parses the `:` following the values of a `case` (or `default`) in
+    /// `self.cur_token`, and the statements up to the next arm, leaving
+    /// `self.cur_token` on the token after the last statement.
+    fn parse_case_body(&mut self) -> Option<BlockStatement> {
+        if !self.expect_peek(&TokenType::Colon) {
+            return None;
+        }
+
+        let token = self.cur_token.clone();
+        self.next_token();
+
+        let statements = self.parse_statements_until(&[
+            TokenType::Case,
+            TokenType::Default,
+            TokenType::RightBrace,
+        ]);
+
+        if self.cur_token_is(&TokenType::Eof) {
+            self.unclosed_block_error();
+            return None;
+        }
+
+        let span = statements
+            .last()
+            .map_or(token.span, |last| token.span.to(last.span()));
+
+        let block = BlockStatement {
+            token,
+            statements,
+            span,
+        };
+        self.attach_dangling_comments(&block);
+        Some(block)
+    }
+
+    fn parse_identifier(&self) -> Option<ast::Expression> {
+        let ident = IdentExpression {
+            token: self.cur_token.clone(),
+            value: self.cur_token.literal.clone(),
+        };
+
+        Some(ast::Expression::Ident(ident))
+    }
+
+    /// Parsers `self.cur_token` as an integer literal.
+    fn parse_integer_literal(&mut self) -> Option<ast::Expression> {
+        let value = match self.cur_token.literal.parse::<i64>() {
+            Ok(v) => v,
+            Err(e) => {
+                let msg = format!(
+                    "Could not parse {} as integer: {}",
+                    self.cur_token.literal, e
+                );
+                let error = ParserError::new("P0004", msg, self.cur_token.span);
+                self.errors.push(error);
+                return None;
+            }
+        };
+
+        let lit = IntegerLiteral {
+            token: self.cur_token.clone(),
+            value,
+        };
+
+        Some(ast::Expression::Integer(lit))
+    }
+
+    /// Parses `self.cur_token` as a float literal, rejecting malformed
+    /// exponents and floats too large to represent.
+    fn parse_float_literal(&mut self) -> Option<ast::Expression> {
+        let literal = &self.cur_token.literal;
+
+        if literal.ends_with(['e', 'E', '+', '-']) {
+            let msg =
+                format!("malformed exponent in {literal}: expected digits after the exponent");
+            let error = ParserError::new("P0004", msg, self.cur_token.span);
+            self.errors.push(error);
+            return None;
+        }
+
+        let value = match literal.parse::<f64>() {
+            Ok(v) if v.is_finite() => v,
+            Ok(_) => {
+                let msg = format!("float literal {literal} is out of range");
+                let error = ParserError::new("P0004", msg, self.cur_token.span);
+                self.errors.push(error);
+                return None;
+            }
+            Err(e) => {
+                let msg = format!("Could not parse {literal} as float: {e}");
+                let error = ParserError::new("P0004", msg, self.cur_token.span);
+                self.errors.push(error);
+                return None;
+            }
+        };
+
+        let lit = FloatLiteral {
+            token: self.cur_token.clone(),
+            value,
+        };
+
+        Some(ast::Expression::Float(lit))
+    }
+
+    /// Parsers `self.cur_token` as a boolean literal.
+    fn parse_boolean(&self) -> Option<ast::Expression> {
+        let boolean = BooleanLiteral {
+            token: self.cur_token.clone(),
+            value: self.cur_token_is(&TokenType::True),
+        };
+
+        Some(ast::Expression::Boolean(boolean))
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+        let operator = token.literal.clone();
+        self.next_token();
+
+        let right = self.parse_expression(Precedence::Prefix.value())?;
+        let prefix = PrefixExpression {
+            token,
+            operator,
+            right: Box::new(right),
+        };
+
+        Some(ast::Expression::Prefix(prefix))
+    }
+
+    /// Parses an expression wrapped in parentheses, returning the inner
+    /// expression since the grouping is already reflected in the tree.
+    fn parse_grouped_expression(&mut self) -> Option<ast::Expression> {
+        self.next_token();
+
+        let expression = self.parse_expression(Precedence::Lowest.value())?;
+
+        if !self.expect_peek(&TokenType::RightParen) {
+            return None;
+        }
+
+        Some(expression)
+    }
+
+    /// Parses `if (<condition>) { <consequence> } else { <alternative> }`,
+    /// where the `else` part is optional.
+    fn parse_if_expression(&mut self) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+
+        if !self.expect_peek(&TokenType::LeftParen) {
+            return None;
+        }
+
+        self.next_token();
+        let condition = self.parse_expression(Precedence::Lowest.value())?;
+
+        if !self.expect_peek(&TokenType::RightParen) || !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
+
+        let consequence = self.parse_block_statement()?;
+
+        let alternative = if self.peek_token_is(&TokenType::Else) {
+            self.next_token();
+
+            if !self.expect_peek(&TokenType::LeftBrace) {
+                return None;
+            }
+
+            Some(self.parse_block_statement()?)
+        } else {
+            None
+        };
+
+        let expression = IfExpression {
+            token,
+            condition: Box::new(condition),
+            consequence,
+            alternative,
+        };
+
+        Some(ast::Expression::If(expression))
+    }
+
+    /// Parses `try { <body> } catch (<param>) { <handler> }`.
+    fn parse_try_expression(&mut self) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+
+        if !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement()?;
+
+        if !self.expect_peek(&TokenType::Catch)
+            || !self.expect_peek(&TokenType::LeftParen)
+            || !self.expect_peek(&TokenType::Ident)
+        {
+            return None;
+        }
+
+        let param = IdentExpression {
+            token: self.cur_token.clone(),
+            value: self.cur_token.literal.clone(),
+        };
+
+        if !self.expect_peek(&TokenType::RightParen) || !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
+
+        let handler = self.parse_block_statement()?;
+
+        let expression = TryExpression {
+            token,
+            body,
+            param,
+            handler,
+        };
+
+        Some(ast::Expression::Try(expression))
+    }
+
+    /// Parses `#{<elements>}`.
+    fn parse_set_literal(&mut self) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+        let mut elements = Vec::new();
+
+        if self.peek_token_is(&TokenType::RightBrace) {
+            self.next_token();
+        } else {
+            loop {
+                self.next_token();
+                elements.push(self.parse_expression(Precedence::Lowest.value())?);
+
+                if !self.peek_token_is(&TokenType::Comma) {
+                    break;
+                }
+                self.next_token();
+            }
+
+            if !self.expect_peek(&TokenType::RightBrace) {
+                return None;
+            }
+        }
+
+        let span = token.span.to(self.cur_token.span);
+        let set = SetLiteral {
+            token,
+            elements,
+            span,
+        };
+
+        Some(ast::Expression::Set(set))
+    }
+
+    /// Parses `[<elements>]`, where elements may be spread.
+    fn parse_array_literal(&mut self) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+        let mut elements = Vec::new();
+
+        if self.peek_token_is(&TokenType::RightBracket) {
+            self.next_token();
+        } else {
+            loop {
+                self.next_token();
+                elements.push(self.parse_spreadable()?);
+
+                if !self.peek_token_is(&TokenType::Comma) {
+                    break;
+                }
+                self.next_token();
+            }
+
+            if !self.expect_peek(&TokenType::RightBracket) {
+                return None;
+            }
+        }
+
+        let span = token.span.to(self.cur_token.span);
+        let array = ArrayLiteral {
+            token,
+            elements,
+            span,
+        };
+
+        Some(ast::Expression::Array(array))
+    }
+
+    /// Parses the expression starting at `self.cur_token`, which may be
+    /// spread with a leading `...`.
+    fn parse_spreadable(&mut self) -> Option<ast::Expression> {
+        if !self.cur_token_is(&TokenType::Ellipsis) {
+            return self.parse_expression(Precedence::Lowest.value());
+        }
+
+        let token = self.cur_token.clone();
+        self.next_token();
+
+        let spread = SpreadExpression {
+            token,
+            value: Box::new(self.parse_expression(Precedence::Lowest.value())?),
+        };
+
+        Some(ast::Expression::Spread(spread))
+    }
+
+    /// Parses `fn(<parameters>) { <body> }`.
+    fn parse_function_literal(&mut self) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+
+        if !self.expect_peek(&TokenType::LeftParen) {
+            return None;
+        }
+
+        let (parameters, rest) = self.parse_function_parameters()?;
+
+        if !self.expect_peek(&TokenType::LeftBrace) {
+            return None;
+        }
+
+        let body = self.parse_block_statement()?;
+
+        let function = FunctionLiteral {
+            token,
+            parameters,
+            rest,
+            body,
+        };
+
+        Some(ast::Expression::Function(function))
+    }
+
+    /// Parses the comma separated parameters after the `(` in
+    /// `self.cur_token`, leaving `self.cur_token` on the closing `)`. The
+    /// last parameter may be a rest parameter, e.g. `...rest`.
+    fn parse_function_parameters(&mut self) -> Option<(Vec<Pattern>, Option<IdentExpression>)> {
+        let mut parameters = Vec::new();
+
+        if self.peek_token_is(&TokenType::RightParen) {
+            self.next_token();
+            return Some((parameters, None));
+        }
+
+        loop {
+            if self.peek_token_is(&TokenType::Ellipsis) {
+                self.next_token();
+                if !self.expect_peek(&TokenType::Ident) {
+                    return None;
+                }
+
+                let rest = IdentExpression {
+                    token: self.cur_token.clone(),
+                    value: self.cur_token.literal.clone(),
+                };
+
+                // Nothing can follow the rest parameter
+                if !self.expect_peek(&TokenType::RightParen) {
+                    return None;
+                }
+
+                return Some((parameters, Some(rest)));
+            }
+
+            parameters.push(self.parse_pattern()?);
+
+            if !self.peek_token_is(&TokenType::Comma) {
+                break;
+            }
+            self.next_token();
+        }
+
+        if !self.expect_peek(&TokenType::RightParen) {
+            return None;
+        }
+
+        Some((parameters, None))
+    }
+
+    /// Parses the pattern starting at `self.peek_token`: a name, an array
+    /// pattern like `[a, [b, c]]` or a hash pattern like `{x, y}`.
+    fn parse_pattern(&mut self) -> Option<Pattern> {
+        self.nested(Self::parse_nested_pattern)
+    }
+
+    fn parse_nested_pattern(&mut self) -> Option<Pattern> {
+        if self.peek_token_is(&TokenType::LeftBracket) {
+            self.next_token();
+            let token = self.cur_token.clone();
+            let mut elements = Vec::new();
+
+            if self.peek_token_is(&TokenType::RightBracket) {
+                self.next_token();
+            } else {
+                loop {
+                    elements.push(self.parse_pattern()?);
+
+                    if !self.peek_token_is(&TokenType::Comma) {
+                        break;
+                    }
+                    self.next_token();
+                }
+
+                if !self.expect_peek(&TokenType::RightBracket) {
+                    return None;
+                }
+            }
+
+            let span = token.span.to(self.cur_token.span);
+            return Some(Pattern::Array(ArrayPattern {
+                token,
+                elements,
+                span,
+            }));
+        }
+
+        if self.peek_token_is(&TokenType::LeftBrace) {
+            self.next_token();
+            let token = self.cur_token.clone();
+            let mut keys = Vec::new();
+
+            if self.peek_token_is(&TokenType::RightBrace) {
+                self.next_token();
+            } else {
+                loop {
+                    if !self.expect_peek(&TokenType::Ident) {
+                        return None;
+                    }
+
+                    keys.push(IdentExpression {
+                        token: self.cur_token.clone(),
+                        value: self.cur_token.literal.clone(),
+                    });
+
+                    if !self.peek_token_is(&TokenType::Comma) {
+                        break;
+                    }
+                    self.next_token();
+                }
+
+                if !self.expect_peek(&TokenType::RightBrace) {
+                    return None;
+                }
+            }
+
+            let span = token.span.to(self.cur_token.span);
+            return Some(Pattern::Hash(HashPattern { token, keys, span }));
+        }
+
+        if !self.expect_peek(&TokenType::Ident) {
+            return None;
+        }
+
+        Some(Pattern::Ident(IdentExpression {
+            token: self.cur_token.clone(),
+            value: self.cur_token.literal.clone(),
+        }))
+    }
+
+    /// Parses the statements between `self.cur_token`, a `{`, and the
+    /// matching `}`, leaving `self.cur_token` on the `}`.
+    fn parse_block_statement(&mut self) -> Option<BlockStatement> {
+        self.nested(Self::parse_nested_block_statement)
+    }
+
+    fn parse_nested_block_statement(&mut self) -> Option<BlockStatement> {
+        let token = self.cur_token.clone();
+
+        self.next_token();
+
+        let statements = self.parse_statements_until(&[TokenType::RightBrace]);
+
+        if self.cur_token_is(&TokenType::Eof) {
+            self.unclosed_block_error();
+            return None;
+        }
+
+        let span = token.span.to(self.cur_token.span);
+
+        let block = BlockStatement {
+            token,
+            statements,
+            span,
+        };
+        self.attach_dangling_comments(&block);
+        Some(block)
+    }
+
+    /// Attaches the comments left before `self.cur_token`, which ends
+    /// `block`, to the end of the block.
+    fn attach_dangling_comments(&mut self, block: &BlockStatement) {
+        for comment in self.take_comments_before(self.cur_token.span.start) {
+            self.comments.add_dangling(block, comment);
+        }
+    }
+
+    /// Parses statements starting at `self.cur_token` until the current token
+    /// is one of `terminators`, or the end of the input.
+    fn parse_statements_until(&mut self, terminators: &[TokenType]) -> Vec<ast::Statement> {
+        let mut statements = Vec::new();
+
+        while !terminators.contains(&self.cur_token.token_type)
+            && !self.cur_token_is(&TokenType::Eof)
+        {
+            match self.parse_statement() {
+                Some(s) => statements.push(s),
+                None => self.skip_statement(),
+            }
+
+            self.next_token();
+        }
+
+        statements
+    }
+
+    /// Writes a parse error when the input ends inside a block
+    fn unclosed_block_error(&mut self) {
+        let error_msg = format!(
+            "expected \"{}\" to close the block, got \"{}\" instead",
+            TokenType::RightBrace.get_literal(),
+            TokenType::Eof.get_literal()
+        );
+        let error = ParserError::new("P0003", error_msg, self.cur_token.span);
+        self.errors.push(error);
+    }
+
+    fn prefix_parse(&mut self) -> Option<ast::Expression> {
+        match self.cur_token.token_type {
+            TokenType::Ident => self.parse_identifier(),
+            TokenType::Int => self.parse_integer_literal(),
+            TokenType::Float => self.parse_float_literal(),
+            TokenType::True | TokenType::False => self.parse_boolean(),
+            TokenType::Plus => self.parse_prefix_expression(),
+            TokenType::Minus => self.parse_prefix_expression(),
+            TokenType::Bang => self.parse_prefix_expression(),
+            TokenType::LeftParen => self.parse_grouped_expression(),
+            TokenType::If => self.parse_if_expression(),
+            TokenType::Try => self.parse_try_expression(),
+            TokenType::Function => self.parse_function_literal(),
+            TokenType::SetBrace => self.parse_set_literal(),
+            TokenType::LeftBracket => self.parse_array_literal(),
+            _ => {
+                self.no_prefix_parse_error();
+                None
+            }
+        }
+    }
+
+    fn parse_infix_expression(&mut self, left: ast::Expression) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+        let operator = token.literal.clone();
+        let mut precedence = self.cur_precedence().value();
+
+        // Parsing the right operand with a lower precedence makes it absorb
+        // the next operator of the same level
+        if let Some(operator) = self.operators.get(&token.literal) {
+            if token.token_type == TokenType::Operator
+                && operator.associativity == Associativity::Right
+            {
+                precedence -= 1;
+            }
+        }
+
+        self.next_token();
+
+        let right = self.parse_expression(precedence)?;
+        let infix = InfixExpression {
+            token,
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        };
+
+        Some(Expression::Infix(infix))
+    }
+
+    /// Parses the arguments of a call to `function`, starting at the `(` in
+    /// `self.cur_token`.
+    fn parse_call_expression(&mut self, function: ast::Expression) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+        let mut arguments = Vec::new();
+        let mut named_arguments: Vec<NamedArgument> = Vec::new();
+
+        if self.peek_token_is(&TokenType::RightParen) {
+            self.next_token();
+        } else {
+            loop {
+                self.next_token();
+
+                // `name: value` is a named argument, anything else is an
+                // expression
+                if self.cur_token_is(&TokenType::Ident) && self.peek_token_is(&TokenType::Colon) {
+                    let name = IdentExpression {
+                        token: self.cur_token.clone(),
+                        value: self.cur_token.literal.clone(),
+                    };
+
+                    if named_arguments.iter().any(|arg| arg.name == name) {
+                        let error_msg = format!("duplicate named argument \"{}\"", name.value);
+                        let error = ParserError::new("P0005", error_msg, name.token.span);
+                        self.errors.push(error);
+                        return None;
+                    }
+
+                    self.next_token();
+                    self.next_token();
+                    let value = self.parse_argument()?;
+                    named_arguments.push(NamedArgument { name, value });
+                } else if !named_arguments.is_empty() {
+                    let error_msg = "positional argument after named arguments".to_string();
+                    let error = ParserError::new("P0006", error_msg, self.cur_token.span);
+                    self.errors.push(error);
+                    return None;
+                } else if self.cur_token_is(&TokenType::Ellipsis) {
+                    arguments.push(self.parse_spreadable()?);
+                } else {
+                    arguments.push(self.parse_argument()?);
+                }
+
+                if !self.peek_token_is(&TokenType::Comma) {
+                    break;
+                }
+                self.next_token();
+            }
+
+            if !self.expect_peek(&TokenType::RightParen) {
+                return None;
+            }
+        }
+
+        let span = function.span().to(self.cur_token.span);
+        let call = CallExpression {
+            token,
+            function: Box::new(function),
+            arguments,
+            named_arguments,
+            span,
+        };
+
+        Some(Expression::Call(call))
+    }
+
+    /// Parses the property after the `.` or `?.` in `self.cur_token`, e.g.
+    /// the `method` of `obj.method`.
+    fn parse_member_expression(&mut self, object: ast::Expression) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+
+        if !self.expect_peek(&TokenType::Ident) {
+            return None;
+        }
+
+        let property = IdentExpression {
+            token: self.cur_token.clone(),
+            value: self.cur_token.literal.clone(),
+        };
+
+        let member = MemberExpression {
+            token,
+            object: Box::new(object),
+            property,
+        };
+
+        Some(Expression::Member(member))
+    }
+
+    /// Parses the argument of a call starting at `self.cur_token`, which may
+    /// be a `_` placeholder. `_` is an identifier anywhere else, so it's only
+    /// a placeholder when it's the whole argument.
+    fn parse_argument(&mut self) -> Option<ast::Expression> {
+        if self.cur_token_is(&TokenType::Ident)
+            && self.cur_token.literal == "_"
+            && (self.peek_token_is(&TokenType::Comma) || self.peek_token_is(&TokenType::RightParen))
+        {
+            let placeholder = PlaceholderExpression {
+                token: Token {
+                    token_type: TokenType::Placeholder,
+                    ..self.cur_token.clone()
+                },
+            };
+            return Some(Expression::Placeholder(placeholder));
+        }
+
+        self.parse_expression(Precedence::Lowest.value())
+    }
+
+    /// Parses `<left> |> <right>`, which calls `right` with `left` as the
+    /// first argument, so `x |> f` stands for `f(x)` and `x |> g(1)` for
+    /// `g(x, 1)`. A call in parentheses is called like any other function,
+    /// so `x |> (g(1))` stands for `g(1)(x)`.
+    fn parse_pipe_expression(&mut self, left: ast::Expression) -> Option<ast::Expression> {
+        let token = self.cur_token.clone();
+
+        self.next_token();
+
+        let right = self.parse_expression(Precedence::Pipe.value())?;
+
+        // Parentheses aren't kept in the tree, but a parenthesized call ends
+        // before the closing parenthesis the parser stopped at
+        let splices =
+            matches!(&right, Expression::Call(call) if call.span.end == self.cur_token.span.end);
+
+        Some(Expression::Pipe(PipeExpression {
+            token,
+            left: Box::new(left),
+            right: Box::new(right),
+            splices,
+        }))
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<ast::Statement> {
+        let token = self.cur_token.clone();
+        let expression = self.parse_expression(Precedence::Lowest.value())?;
+
+        let stmt = ExpressionStatement { token, expression };
+
+        if self.peek_token_is(&TokenType::Semicolon) {
+            self.next_token();
+        }
+
+        Some(ast::Statement::Expression(stmt))
+    }
+
+    fn parse_expression(&mut self, precedence: usize) -> Option<ast::Expression> {
+        self.nested(|parser| parser.parse_nested_expression(precedence))
+    }
+
+    /// Runs `parse` one nesting level deeper, failing instead if that's
+    /// past the limit.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if !self.enter_level() {
+            return None;
+        }
+
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Goes one nesting level deeper, or reports an error at the current
+    /// token if that's past the limit.
+    fn enter_level(&mut self) -> bool {
+        if self.depth >= self.max_depth {
+            let error_msg = format!(
+                "code nested too deeply, the limit is {} levels",
+                self.max_depth
+            );
+            let error = ParserError::new("P0009", error_msg, self.cur_token.span);
+            self.errors.push(error);
+            return false;
+        }
+
+        self.depth += 1;
+        true
+    }
+
+    fn parse_nested_expression(&mut self, precedence: usize) -> Option<ast::Expression> {
+        let depth = self.depth;
+        let expression = self.parse_operations(precedence);
+        self.depth = depth;
+        expression
+    }
+
+    /// Parses an operand followed by the operations applied to it, e.g.
+    /// `a + b`, `f(x)` or `a.b`.
+    fn parse_operations(&mut self, precedence: usize) -> Option<ast::Expression> {
+        let mut left_expression = self.prefix_parse()?;
+
+        // Operations are parsed in a loop rather than by recursing, so a flat
+        // chain like `1 + 2 + 3` or `f(a)(b)` only counts as a fraction of a
+        // level per operation. It still nests the tree, which the printers
+        // and passes walk recursively, so it can't be unbounded.
+        let mut operations = 0;
+        while !self.peek_token_is(&TokenType::Semicolon)
+            && precedence < self.peek_precedence().value()
+        {
+            if !self.peek_token.token_type.is_infix() {
+                break;
+            }
+
+            self.next_token();
+
+            operations += 1;
+            if operations % OPERATIONS_PER_LEVEL == 0 && !self.enter_level() {
+                return None;
+            }
+
+            left_expression = match self.cur_token.token_type {
+                TokenType::LeftParen => self.parse_call_expression(left_expression),
+                TokenType::Pipe => self.parse_pipe_expression(left_expression),
+                TokenType::Dot | TokenType::QuestionDot => {
+                    self.parse_member_expression(left_expression)
+                }
+                _ => self.parse_infix_expression(left_expression),
+            }?;
+        }
+
+        Some(left_expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::panic;
+
+    use ast::{NodeTrait, Statement};
+
+    use super::*;
+
+    fn test_let_statement(statement: &ast::Statement, name: &str) -> bool {
+        let ast::Statement::Let(statement) = statement else {
+            panic!("Not a Let statement");
+        };
+
+        if statement.name.value != name {
+            panic!("statement.name.value not {}", name);
+        }
+
+        if statement.name.token_literal() != name {
+            panic!("statement.name.token_literal() not {}", name);
+        }
+
+        true
+    }
+
+    fn check_parser_errors(parser: &Parser) {
+        let errors = parser.errors();
+        if errors.is_empty() {
+            return;
+        }
+
+        eprintln!("Parser has {} errors", errors.len());
+        for error in errors.iter() {
+            eprintln!("Parser error: {}", error)
+        }
+        panic!()
+    }
+
+    fn test_integer_literal(expression: &Expression, value: &i64) -> bool {
+        let Expression::Integer(int) = expression else {
+            eprintln!("Expression isn't an Integer, got {}", expression);
+            return false;
+        };
+
+        if &int.value != value {
+            eprintln!("Integer value is not \"{}\", got \"{}\"", value, int.value);
+            return false;
+        }
+
+        if int.token_literal() != value.to_string() {
             eprintln!(
                 "Integer token literal isn't \"{}\", got \"{}\"",
                 value,
@@ -372,228 +1652,1230 @@ mod tests {
             return false;
         }
 
-        true
+        true
+    }
+
+    #[test]
+    fn test_let_statements() {
+        let input = r#"
+          let x = 5;
+          let y = 10;
+          let foobar = 838383;
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+
+        let program = parser.parse_program();
+        check_parser_errors(&parser);
+
+        assert_eq!(program.statements.len(), 3);
+        assert!(test_let_statement(&program.statements[0], "x"));
+        assert!(test_let_statement(&program.statements[1], "y"));
+        assert!(test_let_statement(&program.statements[2], "foobar"));
+    }
+
+    #[test]
+    fn test_return_statements() {
+        let input = r#"
+          return 5;
+          return 10;
+          return 993322;
+        "#;
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+
+        let program = parser.parse_program();
+        check_parser_errors(&parser);
+
+        if program.statements.len() != 3 {
+            panic!(
+                "program.statements does not contain 3 statements. Got {}",
+                program.statements.len()
+            );
+        }
+
+        for stmt in program.statements.iter() {
+            use ast::Statement::*;
+            match stmt {
+                Return(s) => {
+                    if s.token_literal() != "return" {
+                        panic!(
+                            "ReturnStatement literal is not \"return\", got \"{}\"",
+                            s.token_literal()
+                        )
+                    }
+                }
+                _ => panic!(
+                    "stmt is not a ReturnStatement. Got {}",
+                    stmt.token_literal()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_identifier_expression() {
+        let input = "foobar;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        check_parser_errors(&parser);
+
+        assert_eq!(
+            program.statements.len(),
+            1,
+            "The program should contain 1 statement"
+        );
+
+        let Statement::Expression(stmt) = &program.statements[0] else {
+            panic!("Statement isn't an expression");
+        };
+
+        let Expression::Ident(ident) = &stmt.expression else {
+            panic!("Expression isn't an identifier");
+        };
+
+        assert_eq!(ident.token_literal(), "foobar");
+    }
+
+    #[test]
+    fn test_integer_literal_expression() {
+        let input = "5;";
+
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        check_parser_errors(&parser);
+
+        assert_eq!(
+            program.statements.len(),
+            1,
+            "The program should contain 1 statement"
+        );
+
+        let Statement::Expression(stmt) = &program.statements[0] else {
+            panic!("Statement isn't an expression");
+        };
+
+        let Expression::Integer(integer_literal) = &stmt.expression else {
+            panic!("Expression isn't an Integer");
+        };
+
+        assert_eq!(integer_literal.value, 5);
+        assert_eq!(integer_literal.token_literal(), "5");
+    }
+
+    #[test]
+    fn test_float_literal_expression() {
+        let tests = vec![
+            ("2.5", 2.5),
+            ("1e9", 1e9),
+            ("2.5e-3", 0.0025),
+            ("3E+2", 300.0),
+        ];
+
+        for (input, expected) in tests {
+            let program = Parser::parse(input).unwrap();
+
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("Statement isn't an expression");
+            };
+            let Expression::Float(float_literal) = &stmt.expression else {
+                panic!("Expression isn't a Float");
+            };
+
+            assert_eq!(float_literal.value, expected);
+            assert_eq!(float_literal.token_literal(), input);
+        }
+    }
+
+    #[test]
+    fn test_parsing_prefix_expressions() {
+        let tests: Vec<(&str, &str, i64)> =
+            vec![("!5;", "!", 5), ("-15;", "-", 15), ("+15;", "+", 15)];
+
+        for (input, operator, value) in tests.iter() {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+            check_parser_errors(&parser);
+
+            assert_eq!(
+                program.statements.len(),
+                1,
+                "The program should have 1 statement"
+            );
+
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("Statement isn't an expression");
+            };
+
+            let Expression::Prefix(prefix) = &stmt.expression else {
+                panic!("Statement isn't a prefix expression");
+            };
+
+            assert_eq!(
+                &prefix.operator, operator,
+                "Operator is not \"{}\", got \"{}\"",
+                operator, prefix.operator
+            );
+
+            assert!(test_integer_literal(prefix.right.as_ref(), value));
+        }
     }
 
     #[test]
-    fn test_let_statements() {
-        let input = r#"
-          let x = 5;
-          let y = 10;
-          let foobar = 838383;
-        "#;
+    fn test_parsing_infix_expressions() {
+        let tests: Vec<(&str, i64, &str, i64)> = vec![
+            ("5 + 5", 5, "+", 5),
+            ("5 - 5", 5, "-", 5),
+            ("5 * 5", 5, "*", 5),
+            ("5 / 5", 5, "/", 5),
+            ("5 > 5", 5, ">", 5),
+            ("5 < 5", 5, "<", 5),
+            ("5 == 5", 5, "==", 5),
+            ("5 != 5", 5, "!=", 5),
+        ];
 
-        let lexer = Lexer::new(input);
-        let mut parser = Parser::new(lexer);
+        for (input, left_value, operator, right_value) in tests.iter() {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+            check_parser_errors(&parser);
 
-        let program = parser.parse_program();
-        check_parser_errors(&parser);
+            assert_eq!(
+                program.statements.len(),
+                1,
+                "The program should have 1 statement"
+            );
 
-        assert_eq!(program.statements.len(), 3);
-        assert!(test_let_statement(&program.statements[0], "x"));
-        assert!(test_let_statement(&program.statements[1], "y"));
-        assert!(test_let_statement(&program.statements[2], "foobar"));
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("Statement isn't an expression");
+            };
+
+            let Expression::Infix(infix) = &stmt.expression else {
+                panic!("Statement isn't a prefix expression");
+            };
+
+            assert!(test_integer_literal(infix.left.as_ref(), left_value));
+
+            assert_eq!(
+                &infix.operator, operator,
+                "Operator is not \"{}\", got \"{}\"",
+                operator, infix.operator
+            );
+
+            assert!(test_integer_literal(infix.right.as_ref(), right_value));
+        }
     }
 
     #[test]
-    fn test_return_statements() {
-        let input = r#"
-          return 5;
-          return 10;
-          return 993322;
-        "#;
+    fn test_operator_precedence_parsing() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("-a * b", "((-a) * b)"),
+            ("!-a", "(!(-a))"),
+            ("a + b + c", "((a + b) + c)"),
+            ("a + b - c", "((a + b) - c)"),
+            ("a * b * c", "((a * b) * c)"),
+            ("a * b / c", "((a * b) / c)"),
+            ("a + b / c", "(a + (b / c))"),
+            ("a + b * c + d / e - f", "(((a + (b * c)) + (d / e)) - f)"),
+            ("3 + 4; -5 * 5", "(3 + 4)((-5) * 5)"),
+            ("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))"),
+            ("5 < 4 != 3 > 4", "((5 < 4) != (3 > 4))"),
+            (
+                "3 + 4 * 5 == 3 * 1 + 4 * 5",
+                "((3 + (4 * 5)) == ((3 * 1) + (4 * 5)))",
+            ),
+            ("1 + (2 + 3) + 4", "((1 + (2 + 3)) + 4)"),
+            ("(5 + 5) * 2", "((5 + 5) * 2)"),
+            ("2 / (5 + 5)", "(2 / (5 + 5))"),
+            ("-(5 + 5)", "(-(5 + 5))"),
+            ("!(a == b)", "(!(a == b))"),
+            ("a + add(b * c) + d", "((a + add((b * c))) + d)"),
+            (
+                "add(a, b, 1, 2 * 3, 4 + 5, add(6, 7 * 8))",
+                "add(a, b, 1, (2 * 3), (4 + 5), add(6, (7 * 8)))",
+            ),
+            (
+                "add(a + b + c * d / f + g)",
+                "add((((a + b) + ((c * d) / f)) + g))",
+            ),
+            ("-f(x)", "(-f(x))"),
+            ("f(x)(y)", "f(x)(y)"),
+            ("a.b.c", "a.b.c"),
+            ("obj.method(1, 2 * 3)", "obj.method(1, (2 * 3))"),
+            ("-a.b * c", "((-a.b) * c)"),
+            ("f(x).y", "f(x).y"),
+            ("x |> obj.method", "(x |> obj.method)"),
+            ("a?.b.c?.d()", "a?.b.c?.d()"),
+            ("a ?? b?.c", "(a ?? b?.c)"),
+            ("x |> f", "(x |> f)"),
+            ("x |> f |> g(1)", "((x |> f) |> g(1))"),
+            ("a + b |> f(c: 1)", "((a + b) |> f(c: 1))"),
+            ("a |> f == g", "(a |> (f == g))"),
+            ("a == b |> f", "((a == b) |> f)"),
+            ("a ?? b ?? c", "((a ?? b) ?? c)"),
+            ("a == b ?? c + d", "((a == b) ?? (c + d))"),
+            ("a ?? b |> f", "(a ?? (b |> f))"),
+            ("-a ?? b.c", "((-a) ?? b.c)"),
+            ("x |> f(1)(2)", "(x |> f(1)(2))"),
+            ("x |> fn(a) { a }", "(x |> fn(a) a)"),
+            ("x |> (g(1))", "(x |> (g(1)))"),
+            ("x |> (f |> g)", "(x |> (f |> g))"),
+            ("x |> (g)(1)", "(x |> g(1))"),
+            ("true", "true"),
+            ("3 > 5 == false", "((3 > 5) == false)"),
+            ("!true == !false", "((!true) == (!false))"),
+        ];
 
-        let lexer = Lexer::new(&input);
-        let mut parser = Parser::new(lexer);
+        for (input, expected) in tests.iter() {
+            let lexer = Lexer::new(input);
+            let mut parser = Parser::new(lexer);
+            let program = parser.parse_program();
+            check_parser_errors(&parser);
 
-        let program = parser.parse_program();
-        check_parser_errors(&parser);
+            assert_eq!(&program.to_string(), expected);
+        }
+    }
 
-        if program.statements.len() != 3 {
-            panic!(
-                "program.statements does not contain 3 statements. Got {}",
-                program.statements.len()
+    #[test]
+    fn test_pipe_calls() {
+        let tests = [
+            ("x |> f", "f(x)"),
+            ("x |> obj.method", "obj.method(x)"),
+            ("x |> f |> g(1)", "g((x |> f), 1)"),
+            ("a + b |> f(c: 1)", "f((a + b), c: 1)"),
+            ("a |> f == g", "(f == g)(a)"),
+            ("x |> f(1)(2)", "f(1)(x, 2)"),
+            ("x |> (g(1))", "g(1)(x)"),
+            ("x |> (f |> g)", "(f |> g)(x)"),
+            ("x |> (g)(1)", "g(x, 1)"),
+        ];
+
+        for (input, expected) in tests {
+            let program = Parser::parse(input).unwrap();
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("statement is not an ExpressionStatement");
+            };
+            let Expression::Pipe(pipe) = &stmt.expression else {
+                panic!("expression is not a PipeExpression");
+            };
+
+            let call = pipe.call();
+            let arguments: Vec<String> = call.arguments.iter().map(ToString::to_string).collect();
+            let named = call
+                .named_arguments
+                .iter()
+                .map(|arg| format!("{}: {}", arg.name.value, arg.value));
+            let arguments: Vec<String> = arguments.into_iter().chain(named).collect();
+            assert_eq!(
+                format!("{}({})", call.function, arguments.join(", ")),
+                expected,
+                "{input}"
             );
         }
+    }
 
-        for stmt in program.statements.iter() {
-            use ast::Statement::*;
-            match stmt {
-                Return(s) => {
-                    if s.token_literal() != "return" {
-                        panic!(
-                            "ReturnStatement literal is not \"return\", got \"{}\"",
-                            s.token_literal()
-                        )
-                    }
-                }
-                _ => panic!(
-                    "stmt is not a ReturnStatement. Got {}",
-                    stmt.token_literal()
-                ),
-            }
+    #[test]
+    fn test_if_expressions() {
+        let tests: Vec<(&str, &str, &str, Option<&str>)> = vec![
+            ("if (x < y) { x }", "(x < y)", "x", None),
+            (
+                "if (x) { x; y } else { return y; }",
+                "x",
+                "xy",
+                Some("return y;"),
+            ),
+            ("if (true) {} else {}", "true", "", Some("")),
+        ];
+
+        for (input, condition, consequence, alternative) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.statements.len(), 1);
+
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("stmt is not an ExpressionStatement");
+            };
+            let Expression::If(expression) = &stmt.expression else {
+                panic!("expression is not an IfExpression");
+            };
+            assert_eq!(&expression.condition.to_string(), condition);
+            assert_eq!(&expression.consequence.to_string(), consequence);
+            assert_eq!(
+                expression
+                    .alternative
+                    .as_ref()
+                    .map(|a| a.to_string())
+                    .as_deref(),
+                *alternative
+            );
         }
     }
 
     #[test]
-    fn test_identifier_expression() {
-        let input = "foobar;";
+    fn test_try_expressions() {
+        let input = "try { throw x + 1; } catch (err) { err }";
+        let program = Parser::parse(input).unwrap();
+        assert_eq!(program.statements.len(), 1);
+
+        let Statement::Expression(stmt) = &program.statements[0] else {
+            panic!("stmt is not an ExpressionStatement");
+        };
+        let Expression::Try(expression) = &stmt.expression else {
+            panic!("expression is not a TryExpression");
+        };
+        assert_eq!(expression.body.to_string(), "throw (x + 1);");
+        assert_eq!(expression.param.value, "err");
+        assert_eq!(expression.handler.to_string(), "err");
+
+        let Statement::Throw(throw) = &expression.body.statements[0] else {
+            panic!("stmt is not a ThrowStatement");
+        };
+        assert_eq!(throw.token_literal(), "throw");
+    }
+
+    #[test]
+    fn test_defer_statements() {
+        let program = Parser::parse("defer a; defer b + c").unwrap();
+        assert_eq!(program.statements.len(), 2);
+
+        let Statement::Defer(stmt) = &program.statements[1] else {
+            panic!("stmt is not a DeferStatement");
+        };
+        assert_eq!(stmt.token_literal(), "defer");
+        assert_eq!(stmt.value.to_string(), "(b + c)");
+    }
+
+    #[test]
+    fn test_switch_statements() {
+        let input = "switch (x + 1) { case 1: a; case 2, 3: b; c default: d } e";
+        let program = Parser::parse(input).unwrap();
+        assert_eq!(program.statements.len(), 2);
+
+        let Statement::Switch(stmt) = &program.statements[0] else {
+            panic!("stmt is not a SwitchStatement");
+        };
+        assert_eq!(stmt.subject.to_string(), "(x + 1)");
+        assert_eq!(stmt.cases.len(), 2);
+        assert_eq!(stmt.cases[0].values.len(), 1);
+        assert_eq!(stmt.cases[0].body.to_string(), "a");
+        assert_eq!(stmt.cases[1].values.len(), 2);
+        assert_eq!(stmt.cases[1].body.to_string(), "bc");
+        assert_eq!(stmt.default.as_ref().unwrap().to_string(), "d");
+        assert_eq!(
+            stmt.to_string(),
+            "switch(x + 1) {case 1: acase 2, 3: bcdefault: d}"
+        );
+
+        let program = Parser::parse("switch (x) {}").unwrap();
+        let Statement::Switch(stmt) = &program.statements[0] else {
+            panic!("stmt is not a SwitchStatement");
+        };
+        assert!(stmt.cases.is_empty());
+        assert!(stmt.default.is_none());
+    }
+
+    #[test]
+    fn test_do_while_statements() {
+        let program = Parser::parse("do { x; y } while (x < 10); z").unwrap();
+        assert_eq!(program.statements.len(), 2);
+
+        let Statement::DoWhile(stmt) = &program.statements[0] else {
+            panic!("stmt is not a DoWhileStatement");
+        };
+        assert_eq!(stmt.body.to_string(), "xy");
+        assert_eq!(stmt.condition.to_string(), "(x < 10)");
+        assert_eq!(stmt.to_string(), "do xywhile(x < 10);");
+    }
+
+    #[test]
+    fn test_enum_statements() {
+        let input = "enum Shape { Circle(r), Rect(w, h), Point }; Shape.Circle(1)";
+        let program = Parser::parse(input).unwrap();
+        assert_eq!(program.statements.len(), 2);
+
+        let Statement::Enum(stmt) = &program.statements[0] else {
+            panic!("stmt is not an EnumStatement");
+        };
+        assert_eq!(stmt.name.value, "Shape");
+        assert_eq!(stmt.variants.len(), 3);
+        assert_eq!(stmt.variants[0].to_string(), "Circle(r)");
+        assert_eq!(stmt.variants[1].fields.len(), 2);
+        assert!(stmt.variants[2].fields.is_empty());
+        assert_eq!(
+            stmt.to_string(),
+            "enum Shape {Circle(r), Rect(w, h), Point}"
+        );
+        assert_eq!(program.statements[1].to_string(), "Shape.Circle(1)");
+
+        let program = Parser::parse("enum Empty {}").unwrap();
+        let Statement::Enum(stmt) = &program.statements[0] else {
+            panic!("stmt is not an EnumStatement");
+        };
+        assert!(stmt.variants.is_empty());
+    }
+
+    #[test]
+    fn test_for_in_statements() {
+        let tests: Vec<(&str, Vec<&str>, &str, &str)> = vec![
+            ("for (x in xs) { x }", vec!["x"], "xs", "x"),
+            (
+                "for (k, v in a + b) { k; v }",
+                vec!["k", "v"],
+                "(a + b)",
+                "kv",
+            ),
+        ];
+
+        for (input, bindings, iterable, body) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.statements.len(), 1);
+
+            let Statement::ForIn(stmt) = &program.statements[0] else {
+                panic!("stmt is not a ForInStatement");
+            };
+            let names: Vec<&str> = stmt.bindings.iter().map(|b| b.value.as_str()).collect();
+            assert_eq!(&names, bindings);
+            assert_eq!(&stmt.iterable.to_string(), iterable);
+            assert_eq!(&stmt.body.to_string(), body);
+        }
+    }
+
+    #[test]
+    fn test_function_literals() {
+        let tests: Vec<(&str, Vec<&str>, Option<&str>, &str)> = vec![
+            ("fn() {}", vec![], None, ""),
+            ("fn(x) { x }", vec!["x"], None, "x"),
+            ("fn(x, y) { x + y; }", vec!["x", "y"], None, "(x + y)"),
+            ("fn(...rest) { rest }", vec![], Some("rest"), "rest"),
+            ("fn(first, ...rest) {}", vec!["first"], Some("rest"), ""),
+            (
+                "fn([a, [b]], {x, y}, []) { a }",
+                vec!["[a, [b]]", "{x, y}", "[]"],
+                None,
+                "a",
+            ),
+        ];
+
+        for (input, parameters, rest, body) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.statements.len(), 1);
+
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("stmt is not an ExpressionStatement");
+            };
+            let Expression::Function(function) = &stmt.expression else {
+                panic!("expression is not a FunctionLiteral");
+            };
+            let names: Vec<String> = function
+                .parameters
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            assert_eq!(&names, parameters, "input: {input}");
+            assert_eq!(function.rest.as_ref().map(|r| r.value.as_str()), *rest);
+            assert_eq!(&function.body.to_string(), body);
+        }
+    }
+
+    #[test]
+    fn test_call_expressions() {
+        let program = Parser::parse("add(1, 2 * 3, 4 + 5);").unwrap();
+        assert_eq!(program.statements.len(), 1);
+
+        let Statement::Expression(stmt) = &program.statements[0] else {
+            panic!("stmt is not an ExpressionStatement");
+        };
+        let Expression::Call(call) = &stmt.expression else {
+            panic!("expression is not a CallExpression");
+        };
+        assert_eq!(call.function.to_string(), "add");
+        let arguments: Vec<String> = call.arguments.iter().map(|a| a.to_string()).collect();
+        assert_eq!(arguments, vec!["1", "(2 * 3)", "(4 + 5)"]);
+        assert!(call.named_arguments.is_empty());
+    }
+
+    #[test]
+    fn test_named_arguments() {
+        let program = Parser::parse("make_point(z, y: 2, x: a + 1)").unwrap();
+
+        let Statement::Expression(stmt) = &program.statements[0] else {
+            panic!("stmt is not an ExpressionStatement");
+        };
+        let Expression::Call(call) = &stmt.expression else {
+            panic!("expression is not a CallExpression");
+        };
+        assert_eq!(call.arguments.len(), 1);
+        let named: Vec<(&str, String)> = call
+            .named_arguments
+            .iter()
+            .map(|a| (a.name.value.as_str(), a.value.to_string()))
+            .collect();
+        assert_eq!(
+            named,
+            vec![("y", "2".to_string()), ("x", "(a + 1)".to_string())]
+        );
+        assert_eq!(call.to_string(), "make_point(z, y: 2, x: (a + 1))");
+    }
+
+    #[test]
+    fn test_placeholder_arguments() {
+        let tests: Vec<(&str, bool)> = vec![
+            ("f(_, 2)", true),
+            ("f(1, x: _)", true),
+            ("f(g(_))", false),
+            ("f(_a)", false),
+        ];
+
+        for (input, partial) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("stmt is not an ExpressionStatement");
+            };
+            let Expression::Call(call) = &stmt.expression else {
+                panic!("expression is not a CallExpression");
+            };
+            assert_eq!(call.is_partial(), *partial, "input: {input}");
+            assert_eq!(&call.to_string(), input);
+        }
+    }
 
-        let lexer = Lexer::new(&input);
-        let mut parser = Parser::new(lexer);
-        let program = parser.parse_program();
-        check_parser_errors(&parser);
+    #[test]
+    fn test_underscore_is_an_identifier() {
+        let tests = vec![
+            ("let _ = 1;", "let _ = 1;"),
+            ("fn(_) { 1 }", "fn(_) 1"),
+            ("f(_ + 1)", "f((_ + 1))"),
+            ("_(_)", "_(_)"),
+        ];
 
-        assert_eq!(
-            program.statements.len(),
-            1,
-            "The program should contain 1 statement"
-        );
+        for (input, expected) in tests {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.to_string(), expected, "input: {input}");
+        }
 
+        let program = Parser::parse("f(_ + 1)").unwrap();
         let Statement::Expression(stmt) = &program.statements[0] else {
-            panic!("Statement isn't an expression");
+            panic!("stmt is not an ExpressionStatement");
         };
-
-        let Expression::Ident(ident) = &stmt.expression else {
-            panic!("Expression isn't an identifier");
+        let Expression::Call(call) = &stmt.expression else {
+            panic!("expression is not a CallExpression");
         };
+        assert!(!call.is_partial());
+    }
 
-        assert_eq!(ident.token_literal(), "foobar");
+    #[test]
+    fn test_set_literals() {
+        let tests: Vec<(&str, Vec<&str>)> = vec![
+            ("#{}", vec![]),
+            ("#{1}", vec!["1"]),
+            ("#{1, a + b, #{c}}", vec!["1", "(a + b)", "#{c}"]),
+        ];
+
+        for (input, elements) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("stmt is not an ExpressionStatement");
+            };
+            let Expression::Set(set) = &stmt.expression else {
+                panic!("expression is not a SetLiteral");
+            };
+            let actual: Vec<String> = set.elements.iter().map(|e| e.to_string()).collect();
+            assert_eq!(&actual, elements);
+        }
     }
 
     #[test]
-    fn test_integer_literal_expression() {
-        let input = "5;";
+    fn test_array_literals() {
+        let tests: Vec<(&str, Vec<&str>)> = vec![
+            ("[]", vec![]),
+            ("[1, a + b, [c]]", vec!["1", "(a + b)", "[c]"]),
+            ("[1, ...rest, 9]", vec!["1", "...rest", "9"]),
+            ("[...a + b]", vec!["...(a + b)"]),
+        ];
 
-        let lexer = Lexer::new(&input);
-        let mut parser = Parser::new(lexer);
-        let program = parser.parse_program();
-        check_parser_errors(&parser);
+        for (input, elements) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
 
-        assert_eq!(
-            program.statements.len(),
-            1,
-            "The program should contain 1 statement"
-        );
+            let Statement::Expression(stmt) = &program.statements[0] else {
+                panic!("stmt is not an ExpressionStatement");
+            };
+            let Expression::Array(array) = &stmt.expression else {
+                panic!("expression is not an ArrayLiteral");
+            };
+            let actual: Vec<String> = array.elements.iter().map(|e| e.to_string()).collect();
+            assert_eq!(&actual, elements);
+        }
+    }
+
+    #[test]
+    fn test_spread_arguments() {
+        let program = Parser::parse("f(a, ...args, ...g(b))").unwrap();
 
         let Statement::Expression(stmt) = &program.statements[0] else {
-            panic!("Statement isn't an expression");
+            panic!("stmt is not an ExpressionStatement");
         };
-
-        let Expression::Integer(integer_literal) = &stmt.expression else {
-            panic!("Expression isn't an Integer");
+        let Expression::Call(call) = &stmt.expression else {
+            panic!("expression is not a CallExpression");
         };
-
-        assert_eq!(integer_literal.value, 5);
-        assert_eq!(integer_literal.token_literal(), "5");
+        assert!(matches!(call.arguments[1], Expression::Spread(_)));
+        assert_eq!(call.arguments[2].to_string(), "...g(b)");
     }
 
     #[test]
-    fn test_parsing_prefix_expressions() {
-        let tests: Vec<(&str, &str, i64)> = vec![("!5;", "!", 5), ("-15;", "-", 15)];
+    fn test_error_codes() {
+        let tests = vec![
+            ("let = 5", "P0001", "="),
+            ("switch (x) { 1 }", "P0001", "1"),
+            ("a + ;", "P0002", ";"),
+            ("if (a) { b", "P0003", ""),
+            ("1e+", "P0004", "1e+"),
+            ("f(x: 1, x: 2)", "P0005", "x"),
+            ("f(x: 1, 2)", "P0006", "2"),
+            ("switch (x) { default: a default: b }", "P0007", "default"),
+            ("enum E { A, A }", "P0008", "A"),
+            ("let x = @@ 5", "P0010", "@@"),
+            ("let x@ = 5", "P0010", "@"),
+        ];
 
-        for (input, operator, value) in tests.iter() {
-            let lexer = Lexer::new(input);
-            let mut parser = Parser::new(lexer);
-            let program = parser.parse_program();
-            check_parser_errors(&parser);
+        for (input, code, text) in tests {
+            let errors = Parser::parse(input).unwrap_err();
+            let error = &errors[0];
 
+            assert_eq!(error.code, code, "input: {input}");
             assert_eq!(
-                program.statements.len(),
-                1,
-                "The program should have 1 statement"
+                &input[error.span.start..error.span.end],
+                text,
+                "input: {input}"
             );
+        }
+    }
 
-            let Statement::Expression(stmt) = &program.statements[0] else {
-                panic!("Statement isn't an expression");
-            };
+    #[test]
+    fn test_nesting_limit() {
+        let input = format!("{}1", "-".repeat(DEFAULT_MAX_DEPTH - 1));
+        assert!(Parser::parse(&input).is_ok());
+
+        let input = format!("{}1", "-".repeat(DEFAULT_MAX_DEPTH));
+        let errors = Parser::parse(&input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "P0009");
+        assert_eq!(errors[0].span, Span::new(input.len() - 1, input.len()));
+
+        let mut parser = Parser::new(Lexer::new("((1))"));
+        parser.set_max_depth(2);
+        parser.parse_program();
+        assert_eq!(parser.errors().len(), 1);
+        assert_eq!(parser.errors()[0].code, "P0009");
+
+        // The enclosing parentheses don't report their closing ones missing
+        let input = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+        let errors = Parser::parse(&input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "P0009");
+
+        // Flat chains are parsed without recursing
+        for operation in [" + 1", "(1)", ".a", " |> f"] {
+            let input = format!("let x = 1{};", operation.repeat(999));
+            assert!(Parser::parse(&input).is_ok(), "chain of {operation:?}");
+        }
+        // But they count towards the limit, a fraction of a level each
+        let input = format!("{}1{}", "(".repeat(100), " + 1".repeat(300));
+        let input = format!("{input}{}", ")".repeat(100));
+        let errors = Parser::parse(&input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "P0009");
+        let input = format!("1{}", " + 1".repeat(2000));
+        let errors = Parser::parse(&input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "P0009");
+    }
 
-            let Expression::Prefix(prefix) = &stmt.expression else {
-                panic!("Statement isn't a prefix expression");
-            };
+    /// Found by fuzzing: each nesting level recursed, overflowing the stack
+    #[test]
+    fn test_deep_nesting_does_not_overflow() {
+        for (open, close) in [
+            ("-", ""),
+            ("!", ""),
+            ("(", ")"),
+            ("[", "]"),
+            ("f(", ")"),
+            ("fn() { ", " }"),
+            ("if (x) { ", " }"),
+            ("x |> ", ""),
+            ("", " + 1"),
+            ("", "(1)"),
+            ("", ".a"),
+        ] {
+            let input = format!("{}a{}", open.repeat(100_000), close.repeat(100_000));
+            let errors = Parser::parse(&input).unwrap_err();
+            assert_eq!(errors[0].code, "P0009", "nesting {open:?}");
+        }
 
-            assert_eq!(
-                &prefix.operator, operator,
-                "Operator is not \"{}\", got \"{}\"",
-                operator, prefix.operator
-            );
+        let input = format!("fn({}a{}) {{}}", "[".repeat(100_000), "]".repeat(100_000));
+        let errors = Parser::parse(&input).unwrap_err();
+        assert_eq!(errors[0].code, "P0009", "nesting patterns");
+    }
 
-            assert!(test_integer_literal(prefix.right.as_ref(), value));
-        }
+    #[test]
+    fn test_spans() {
+        let input = "let x = 1 + 2;\nif (x) { y } else { z }";
+        let program = Parser::parse(input).unwrap();
+
+        let spans: Vec<&str> = program
+            .statements
+            .iter()
+            .map(|s| {
+                let span = s.span();
+                &input[span.start..span.end]
+            })
+            .collect();
+        assert_eq!(spans, vec!["let x = 1 + 2", "if (x) { y } else { z }"]);
     }
 
     #[test]
-    fn test_parsing_infix_expressions() {
-        let tests: Vec<(&str, i64, &str, i64)> = vec![
-            ("5 + 5", 5, "+", 5),
-            ("5 - 5", 5, "-", 5),
-            ("5 * 5", 5, "*", 5),
-            ("5 / 5", 5, "/", 5),
-            ("5 > 5", 5, ">", 5),
-            ("5 < 5", 5, "<", 5),
-            ("5 == 5", 5, "==", 5),
-            ("5 != 5", 5, "!=", 5),
+    fn test_malformed_input_does_not_hang_or_panic() {
+        let inputs = vec![
+            "",
+            "let",
+            "let x",
+            "let x =",
+            "let x = 5",
+            "return",
+            "return 5",
+            "-",
+            "5 +",
+            "é",
+            "let é = 1",
+            "if",
+            "if (",
+            "if (x) {",
+            "if (x) { y } else",
+            "}",
+            "throw",
+            "try",
+            "try {} catch",
+            "try {} catch (e",
+            "switch",
+            "switch (x) {",
+            "switch (x) { case",
+            "switch (x) { case 1",
+            "switch (x) { case 1: a",
+            "switch (x) { default",
+            "do",
+            "do {}",
+            "do {} while",
+            "do {} while (x",
+            "for",
+            "for (",
+            "for (x",
+            "for (x,",
+            "for (x in",
+            "for (x in y) {",
+            "fn",
+            "fn(",
+            "fn(x",
+            "fn(x,",
+            "fn(...",
+            "fn(...x",
+            "fn(x) {",
+            "..",
+            "f(",
+            "f(a",
+            "f(a,",
+            "f(a:",
+            "f(a: 1,",
+            "x |>",
+            "|> f",
+            "x |",
+            "f(_",
+            "_",
+            "#",
+            "#{",
+            "#{1,",
+            "[1,",
+            "f(...)",
+            "a.",
+            ".a",
+            "a?.",
         ];
 
-        for (input, left_value, operator, right_value) in tests.iter() {
-            let lexer = Lexer::new(input);
-            let mut parser = Parser::new(lexer);
-            let program = parser.parse_program();
-            check_parser_errors(&parser);
+        for input in inputs.iter() {
+            let mut parser = Parser::new(Lexer::new(input));
+            parser.parse_program();
+        }
+    }
 
-            assert_eq!(
-                program.statements.len(),
-                1,
-                "The program should have 1 statement"
-            );
+    #[test]
+    fn test_let_statement_values() {
+        let tests: Vec<(&str, &str, &str)> = vec![
+            ("let x = 5;", "x", "5"),
+            ("let y = a + b * 2;", "y", "(a + (b * 2))"),
+            ("let foobar = y", "foobar", "y"),
+        ];
 
-            let Statement::Expression(stmt) = &program.statements[0] else {
-                panic!("Statement isn't an expression");
-            };
+        for (input, name, value) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.statements.len(), 1);
+            assert!(test_let_statement(&program.statements[0], name));
 
-            let Expression::Infix(infix) = &stmt.expression else {
-                panic!("Statement isn't a prefix expression");
+            let Statement::Let(stmt) = &program.statements[0] else {
+                unreachable!();
             };
+            assert_eq!(&stmt.value.to_string(), value);
+            assert_eq!(stmt.token_literal(), "let");
+        }
+    }
 
-            assert!(test_integer_literal(infix.left.as_ref(), left_value));
+    #[test]
+    fn test_const_statements() {
+        let program = Parser::parse("const x = 5; let y = x;").unwrap();
+        assert_eq!(program.statements.len(), 2);
+        assert!(test_let_statement(&program.statements[0], "x"));
+        assert!(test_let_statement(&program.statements[1], "y"));
 
-            assert_eq!(
-                &infix.operator, operator,
-                "Operator is not \"{}\", got \"{}\"",
-                operator, infix.operator
-            );
+        let (Statement::Let(first), Statement::Let(second)) =
+            (&program.statements[0], &program.statements[1])
+        else {
+            unreachable!();
+        };
+        assert!(first.is_const());
+        assert!(!second.is_const());
+        assert_eq!(program.to_string(), "const x = 5;let y = x;");
+    }
 
-            assert!(test_integer_literal(infix.right.as_ref(), right_value));
+    #[test]
+    fn test_return_statement_values() {
+        let tests: Vec<(&str, &str)> = vec![("return 5;", "5"), ("return -x * y", "((-x) * y)")];
+
+        for (input, value) in tests.iter() {
+            let program = Parser::parse(input).unwrap();
+            assert_eq!(program.statements.len(), 1);
+
+            let Statement::Return(stmt) = &program.statements[0] else {
+                panic!("stmt is not a ReturnStatement");
+            };
+            assert_eq!(&stmt.value.to_string(), value);
         }
     }
 
     #[test]
-    fn test_operator_precedence_parsing() {
-        let tests: Vec<(&str, &str)> = vec![
-            ("-a * b", "((-a) * b)"),
-            ("!-a", "(!(-a))"),
-            ("a + b + c", "((a + b) + c)"),
-            ("a + b - c", "((a + b) - c)"),
-            ("a * b * c", "((a * b) * c)"),
-            ("a * b / c", "((a * b) / c)"),
-            ("a + b / c", "(a + (b / c))"),
-            ("a + b * c + d / e - f", "(((a + (b * c)) + (d / e)) - f)"),
-            ("3 + 4; -5 * 5", "(3 + 4)((-5) * 5)"),
-            ("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))"),
-            ("5 < 4 != 3 > 4", "((5 < 4) != (3 > 4))"),
+    fn test_parse_errors() {
+        let tests: Vec<(&str, Vec<&str>)> = vec![
             (
-                "3 + 4 * 5 == 3 * 1 + 4 * 5",
-                "((3 + (4 * 5)) == ((3 * 1) + (4 * 5)))",
+                "let = 5;",
+                vec!["expected next token to be \"identifier\", got \"=\" instead"],
+            ),
+            (
+                "let x 5;",
+                vec!["expected next token to be \"=\", got \"int\" instead"],
+            ),
+            (
+                "let x = ;",
+                vec!["no prefix parse function for \";\" found"],
+            ),
+            (
+                "return",
+                vec!["no prefix parse function for \"end of input\" found"],
+            ),
+            (
+                "let = 1; 2 +; 3;",
+                vec![
+                    "expected next token to be \"identifier\", got \"=\" instead",
+                    "no prefix parse function for \";\" found",
+                ],
+            ),
+            (
+                "(1 + 2",
+                vec!["expected next token to be \")\", got \"end of input\" instead"],
+            ),
+            (
+                "let x = (;",
+                vec!["no prefix parse function for \";\" found"],
+            ),
+            (
+                "if (x) { y",
+                vec!["expected \"}\" to close the block, got \"end of input\" instead"],
+            ),
+            (
+                "try { a }",
+                vec!["expected next token to be \"catch\", got \"end of input\" instead"],
+            ),
+            (
+                "try { a } catch { b }",
+                vec!["expected next token to be \"(\", got \"{\" instead"],
+            ),
+            (
+                "switch (x) { a }",
+                vec![
+                    "expected \"case\", \"default\" or \"}\" in switch, got \"identifier\" instead",
+                ],
+            ),
+            (
+                "switch (x) { case 1 a }",
+                vec!["expected next token to be \":\", got \"identifier\" instead"],
+            ),
+            (
+                "switch (x) { default: a default: b }",
+                vec!["switch can't have more than one default"],
+            ),
+            (
+                "switch (x) { case 1: a",
+                vec!["expected \"}\" to close the block, got \"end of input\" instead"],
+            ),
+            (
+                "do { x } (y)",
+                vec!["expected next token to be \"while\", got \"(\" instead"],
+            ),
+            (
+                "for (a, b, c in d) {}",
+                vec!["expected next token to be \"in\", got \",\" instead"],
+            ),
+            (
+                "for (x of y) {}",
+                vec!["expected next token to be \"in\", got \"identifier\" instead"],
+            ),
+            (
+                "fn(...rest, last) {}",
+                vec!["expected next token to be \")\", got \",\" instead"],
+            ),
+            (
+                "fn([a, 1]) {}",
+                vec!["expected next token to be \"identifier\", got \"int\" instead"],
+            ),
+            (
+                "fn({x: y}) {}",
+                vec!["expected next token to be \"}\", got \":\" instead"],
+            ),
+            (
+                "fn(x, 1) {}",
+                vec!["expected next token to be \"identifier\", got \"int\" instead"],
+            ),
+            ("f(x: 1, x: 2)", vec!["duplicate named argument \"x\""]),
+            ("...a", vec!["no prefix parse function for \"...\" found"]),
+            (
+                "1e",
+                vec!["malformed exponent in 1e: expected digits after the exponent"],
+            ),
+            (
+                "2.5e+ 1",
+                vec!["malformed exponent in 2.5e+: expected digits after the exponent"],
+            ),
+            ("1e999", vec!["float literal 1e999 is out of range"]),
+            (
+                "#{...a}",
+                vec!["no prefix parse function for \"...\" found"],
+            ),
+            (
+                "f(x: ...a)",
+                vec!["no prefix parse function for \"...\" found"],
+            ),
+            (
+                "enum Color { Red, Red }",
+                vec!["duplicate variant \"Red\" in enum \"Color\""],
+            ),
+            (
+                "enum Shape { Circle() }",
+                vec!["expected next token to be \"identifier\", got \")\" instead"],
+            ),
+            (
+                "f(x: 1, 2)",
+                vec!["positional argument after named arguments"],
+            ),
+            (
+                "f(1 2)",
+                vec!["expected next token to be \")\", got \"int\" instead"],
+            ),
+            (
+                "#{1 2}",
+                vec!["expected next token to be \"}\", got \"int\" instead"],
+            ),
+            (
+                "a.1",
+                vec!["expected next token to be \"identifier\", got \"int\" instead"],
+            ),
+            (
+                "if x { y }",
+                vec!["expected next token to be \"(\", got \"identifier\" instead"],
             ),
         ];
 
         for (input, expected) in tests.iter() {
-            let lexer = Lexer::new(input);
-            let mut parser = Parser::new(lexer);
+            let errors = Parser::parse(input).unwrap_err();
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            assert_eq!(&messages, expected, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_custom_infix_operators() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("a <> b <> c", "((a <> b) <> c)"),
+            ("a + b <> c", "((a + b) <> c)"),
+            ("a <> b * c", "(a <> (b * c))"),
+            ("a ^ b ^ c", "(a ^ (b ^ c))"),
+            ("a * b ^ c", "((a * b) ^ c)"),
+            ("a + b ^ c", "(a + (b ^ c))"),
+            ("-a ^ b", "((-a) ^ b)"),
+            ("a<>b", "(a <> b)"),
+            // Built-in operators sharing characters with custom ones still lex
+            ("a |> f", "(a |> f)"),
+            ("a ?? b", "(a ?? b)"),
+            ("a?.b", "a?.b"),
+            ("[...a]", "[...a]"),
+            ("!!a", "(!(!a))"),
+            ("a<-b", "(a < (-b))"),
+            ("a |>> b |> f", "((a |>> b) |> f)"),
+        ];
+
+        for (input, expected) in tests.iter() {
+            let mut parser = Parser::new(Lexer::new(input));
+            parser
+                .register_infix_operator(InfixOperator {
+                    symbol: "<>".to_string(),
+                    precedence: Precedence::Sum,
+                    associativity: Associativity::Left,
+                })
+                .unwrap();
+            parser
+                .register_infix_operator(InfixOperator {
+                    symbol: "^".to_string(),
+                    precedence: Precedence::Product,
+                    associativity: Associativity::Right,
+                })
+                .unwrap();
+            parser
+                .register_infix_operator(InfixOperator {
+                    symbol: "|>>".to_string(),
+                    precedence: Precedence::Sum,
+                    associativity: Associativity::Left,
+                })
+                .unwrap();
+
             let program = parser.parse_program();
             check_parser_errors(&parser);
-
             assert_eq!(&program.to_string(), expected);
         }
     }
+
+    #[test]
+    fn test_invalid_custom_infix_operators() {
+        for symbol in [
+            "", "==", "+", "ab", "<a", "a b", "//", ";;", "(*", ":",
+            // Prefixes of built-in operators
+            "|", "?", "..", "#", // Built-in operators followed by prefix operators
+            "!!", "--", "<-", "*+", "==!",
+        ] {
+            let mut parser = Parser::new(Lexer::new(""));
+            let result = parser.register_infix_operator(InfixOperator {
+                symbol: symbol.to_string(),
+                precedence: Precedence::Sum,
+                associativity: Associativity::Left,
+            });
+            assert!(result.is_err(), "symbol {symbol:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_subscriber_events() {
+        struct Recorder(Vec<String>);
+
+        impl Subscriber for Recorder {
+            fn on_event(&mut self, event: &Event) {
+                self.0.push(format!("{event:?}"));
+            }
+        }
+
+        let mut recorder = Recorder(Vec::new());
+        let mut parser = Parser::new(Lexer::new("let a = 1; let = 2; a"));
+        parser.set_subscriber(&mut recorder);
+        parser.parse_program();
+        drop(parser);
+
+        assert_eq!(
+            recorder.0,
+            [
+                "ParseStarted",
+                "StatementParsed { span: Span { start: 0, end: 9 } }",
+                "ErrorRaised { code: \"P0001\", message: \"expected next token to be \\\"identifier\\\", got \\\"=\\\" instead\", span: Span { start: 15, end: 16 } }",
+                "StatementParsed { span: Span { start: 20, end: 21 } }",
+                "ParseFinished { statements: 2, errors: 1 }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unregistered_operator_is_illegal() {
+        let errors = Parser::parse("a ^ b").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "unexpected character '^'; there is no `^` operator"
+        );
+    }
+
+    #[test]
+    fn test_keep_comments() {
+        let input = "// leading
+let f = fn() { // inner
+    a; // trailing
+    // dangling
+};
+// end";
+        let mut parser = Parser::new(Lexer::new(input));
+        parser.keep_comments();
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty());
+
+        let comments = &program.comments;
+        let texts = |comments: &[Comment]| -> Vec<String> {
+            comments.iter().map(|c| c.text.clone()).collect()
+        };
+        let let_stmt = &program.statements[0];
+        assert_eq!(texts(comments.leading(let_stmt)), vec!["// leading"]);
+        assert_eq!(comments.trailing(let_stmt), None);
+
+        let ast::Statement::Let(LetStatement {
+            value: ast::Expression::Function(function),
+            ..
+        }) = let_stmt
+        else {
+            panic!("expected a function, got {let_stmt:?}");
+        };
+        let body = &function.body;
+        assert_eq!(
+            texts(comments.leading(&body.statements[0])),
+            vec!["// inner"]
+        );
+        assert_eq!(
+            comments
+                .trailing(&body.statements[0])
+                .map(|c| c.text.as_str()),
+            Some("// trailing")
+        );
+        assert_eq!(texts(comments.dangling(body)), vec!["// dangling"]);
+        assert_eq!(texts(comments.program_end()), vec!["// end"]);
+        assert_eq!(comments.iter().count(), 5);
+
+        assert!(Parser::parse(input).unwrap().comments.is_empty());
+    }
+
+    #[test]
+    fn test_errors_after_illegal_characters() {
+        let errors = Parser::parse("let a = $x; let = 1;").unwrap_err();
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "unexpected character '$'; identifiers may contain letters and '_'",
+                "expected next token to be \"identifier\", got \"=\" instead",
+            ]
+        );
+    }
 }
@@ -0,0 +1,341 @@
+//! Automated changes to source code, returned as [`TextEdit`]s so editors
+//! can apply them to their buffers.
+
+use std::fmt::Display;
+
+use crate::{
+    analyzer::{scope, Binding, BindingKind, Index},
+    ast::{
+        patterns::Pattern,
+        visit::{self, Visitor},
+        Expression, Program, Statement,
+    },
+    lexer::Lexer,
+    token::{Span, TokenType},
+};
+
+/// A replacement of the text of `span` by `new_text`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+/// Why a refactoring can't be done.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefactorError {
+    pub message: String,
+    /// Where the problem is in the source
+    pub span: Span,
+}
+
+impl RefactorError {
+    fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl Display for RefactorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for RefactorError {}
+
+/// Returns the edits renaming the binding of the identifier at `offset`,
+/// and every use of it, to `new_name`.
+///
+/// The rename is refused if `new_name` isn't an identifier or is the `_`
+/// placeholder, or if it would change the binding any name refers to: a
+/// use of the binding would be shadowed by another binding of `new_name`,
+/// or the binding would capture a use of another `new_name`. Names bound by hash patterns can't be
+/// renamed, since they're also the keys their values are read from.
+///
+/// Renaming a parameter of a function bound with `let` also renames the
+/// named arguments passing it in the calls of the function by its name.
+pub fn rename(
+    program: &Program,
+    offset: usize,
+    new_name: &str,
+) -> Result<Vec<TextEdit>, RefactorError> {
+    let index = Index::new(program);
+    let at = Span::new(offset, offset);
+    let Some(binding) = index.definition_at(offset) else {
+        return Err(RefactorError::new("no bound name to rename here", at));
+    };
+
+    if !is_identifier(new_name) {
+        return Err(RefactorError::new(
+            format!("`{new_name}` isn't a valid name"),
+            at,
+        ));
+    }
+    if binding.kind == BindingKind::Key {
+        return Err(RefactorError::new(
+            format!(
+                "`{}` is also the key its value is read from, so it can't be renamed",
+                binding.name
+            ),
+            binding.span,
+        ));
+    }
+
+    let mut spans = index.references(binding);
+    spans.push(binding.span);
+    spans.sort_by_key(|span| span.start);
+
+    let before = scope::resolve(program);
+    let renames = spans.iter().map(|span| (span.start, new_name)).collect();
+    let after = scope::resolve_renamed(program, renames);
+    let changed = before
+        .references
+        .iter()
+        .zip(after.references.iter())
+        .find(|(before, after)| before.binding != after.binding);
+    if let Some((reference, _)) = changed {
+        let message = match spans.contains(&reference.span) {
+            true => format!(
+                "renaming `{}` to `{new_name}` would make this use refer to another `{new_name}`",
+                binding.name
+            ),
+            false => format!(
+                "renaming `{}` to `{new_name}` would make this use of `{new_name}` refer to it",
+                binding.name
+            ),
+        };
+        return Err(RefactorError::new(message, reference.span));
+    }
+
+    if binding.kind == BindingKind::Parameter {
+        spans.extend(named_arguments(program, &index, binding, new_name)?);
+        spans.sort_by_key(|span| span.start);
+    }
+
+    Ok(spans
+        .into_iter()
+        .map(|span| TextEdit {
+            span,
+            new_text: new_name.to_string(),
+        })
+        .collect())
+}
+
+/// Returns the spans of the names of the named arguments passing
+/// `parameter` in the calls of its function, if the function is bound with
+/// `let` and called by that name.
+fn named_arguments(
+    program: &Program,
+    index: &Index,
+    parameter: &Binding,
+    new_name: &str,
+) -> Result<Vec<Span>, RefactorError> {
+    let mut finder = FunctionFinder {
+        parameter: parameter.span,
+        function: None,
+    };
+    finder.visit_program(program);
+    let Some(function) = finder
+        .function
+        .and_then(|span| index.definition_at(span.start))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut finder = NamedArgumentFinder {
+        index,
+        function,
+        name: &parameter.name,
+        new_name,
+        spans: Vec::new(),
+        conflict: None,
+    };
+    finder.visit_program(program);
+    match finder.conflict {
+        Some(span) => Err(RefactorError::new(
+            format!(
+                "renaming `{}` to `{new_name}` would pass `{new_name}` twice in this call",
+                parameter.name
+            ),
+            span,
+        )),
+        None => Ok(finder.spans),
+    }
+}
+
+/// Finds the name a `let` binds the function with `parameter` to.
+struct FunctionFinder {
+    parameter: Span,
+    function: Option<Span>,
+}
+
+impl Visitor for FunctionFinder {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Statement::Let(s) = stmt {
+            if let Expression::Function(function) = &s.value {
+                let has_parameter = function.parameters.iter().any(
+                    |parameter| matches!(parameter, Pattern::Ident(p) if p.token.span == self.parameter),
+                );
+                if has_parameter {
+                    self.function = Some(s.name.token.span);
+                }
+            }
+        }
+
+        visit::walk_statement(self, stmt);
+    }
+}
+
+/// Finds the named arguments called `name` in the calls of `function`.
+struct NamedArgumentFinder<'a> {
+    index: &'a Index,
+    function: &'a Binding,
+    name: &'a str,
+    new_name: &'a str,
+    spans: Vec<Span>,
+    /// A call already passing `new_name`
+    conflict: Option<Span>,
+}
+
+impl Visitor for NamedArgumentFinder<'_> {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Call(call) = expression {
+            let callee = match call.function.as_ref() {
+                Expression::Ident(ident) => self.index.definition_at(ident.token.span.start),
+                _ => None,
+            };
+            if callee == Some(self.function) {
+                let names = || call.named_arguments.iter().map(|argument| &argument.name);
+                if let Some(name) = names().find(|name| name.value == self.name) {
+                    self.spans.push(name.token.span);
+                    if let Some(other) = names().find(|name| name.value == self.new_name) {
+                        self.conflict.get_or_insert(other.token.span);
+                    }
+                }
+            }
+        }
+
+        visit::walk_expression(self, expression);
+    }
+}
+
+/// Checks if `name` lexes to a single identifier, and so isn't a keyword.
+/// `_` is refused too, since as an argument it's a placeholder, making
+/// `f(x)` renamed to `f(_)` a partial application.
+fn is_identifier(name: &str) -> bool {
+    if name == "_" {
+        return false;
+    }
+    let mut lexer = Lexer::new(name);
+    let token = lexer.next_token();
+    token.token_type == TokenType::Ident
+        && token.literal == name
+        && lexer.next_token().token_type == TokenType::Eof
+}
+
+/// Returns `source` with `edits` applied. The edits must not overlap.
+pub fn apply(source: &str, edits: &[TextEdit]) -> String {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|edit| edit.span.start);
+
+    let mut out = String::new();
+    let mut position = 0;
+    for edit in edits {
+        out.push_str(&source[position..edit.span.start]);
+        out.push_str(&edit.new_text);
+        position = edit.span.end;
+    }
+    out.push_str(&source[position..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn rename_source(source: &str, at: &str, new_name: &str) -> Result<String, String> {
+        let program = Parser::parse(source).unwrap();
+        let offset = source.find(at).unwrap();
+        rename(&program, offset, new_name)
+            .map(|edits| apply(source, &edits))
+            .map_err(|e| e.message)
+    }
+
+    #[test]
+    fn test_rename() {
+        let source = "let x = 1; let f = fn(a) { a + x }; f(x);";
+        assert_eq!(
+            rename_source(source, "x", "count"),
+            Ok("let count = 1; let f = fn(a) { a + count }; f(count);".to_string())
+        );
+        // From a use of the name
+        assert_eq!(
+            rename_source(source, "a +", "b"),
+            Ok("let x = 1; let f = fn(b) { b + x }; f(x);".to_string())
+        );
+        // Only the binding at the offset is renamed
+        assert_eq!(
+            rename_source("let x = 1; let x = x + 1; x", "x", "y"),
+            Ok("let y = 1; let x = y + 1; x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rename_parameter_with_named_arguments() {
+        let source = "let f = fn(x, z) { x + z }; f(x: 1, z: 2); x |> f(z: 3); let g = fn(x) { x }; g(x: 4);";
+        assert_eq!(
+            rename_source(source, "x,", "y"),
+            Ok(
+                "let f = fn(y, z) { y + z }; f(y: 1, z: 2); x |> f(z: 3); let g = fn(x) { x }; g(x: 4);"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            rename_source("let f = fn(x, z) { z }; f(x: 1, z: 2);", "x,", "z"),
+            Err("renaming `x` to `z` would pass `z` twice in this call".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rename_errors() {
+        let tests = vec![
+            ("let x = 1; x", "1", "y", "no bound name to rename here"),
+            ("let x = 1; y", "y", "z", "no bound name to rename here"),
+            ("let x = 1;", "x", "fn", "`fn` isn't a valid name"),
+            ("let x = 1;", "x", "a b", "`a b` isn't a valid name"),
+            ("let x = 1; f(x);", "x", "_", "`_` isn't a valid name"),
+            (
+                "let f = fn({a}) { a };",
+                "a",
+                "b",
+                "`a` is also the key its value is read from, so it can't be renamed",
+            ),
+            // The parameter would shadow the outer binding
+            (
+                "let y = 1; let f = fn(x) { x + y };",
+                "x)",
+                "y",
+                "renaming `x` to `y` would make this use of `y` refer to it",
+            ),
+            // The outer binding would be shadowed by the parameter
+            (
+                "let x = 1; let f = fn(y) { x + y };",
+                "x",
+                "y",
+                "renaming `x` to `y` would make this use refer to another `y`",
+            ),
+        ];
+
+        for (source, at, new_name, expected) in tests {
+            assert_eq!(
+                rename_source(source, at, new_name),
+                Err(expected.to_string()),
+                "source: {source}"
+            );
+        }
+    }
+}
@@ -1,18 +1,37 @@
 use std::io::{self, Write};
 
-use crate::{lexer::Lexer, token::TokenType};
+use writing_an_interpreter_book::{
+    analyzer::Diagnostic,
+    ast::{Node, Statement},
+    lexer::Lexer,
+    parser::{Parser, ParserError},
+    token::TokenType,
+};
 
 pub fn start() {
     let mut input = String::new();
     loop {
         print!(">> ");
         io::stdout().flush().unwrap();
+        input.clear();
         match io::stdin().read_line(&mut input) {
             Ok(_) => {
-                if input == "\n" {
+                if input == "\n" || input.is_empty() {
                     break;
                 }
 
+                // `:ast CODE` prints the tree of CODE instead of its tokens
+                if let Some(code) = command(&input, ":ast") {
+                    print_ast(code);
+                    continue;
+                }
+
+                // `:inspect CODE` walks the tree of CODE one node at a time
+                if let Some(code) = command(&input, ":inspect") {
+                    inspect(code);
+                    continue;
+                }
+
                 println!("input: {input}");
 
                 let mut lexer = Lexer::new(&input);
@@ -24,8 +43,116 @@ pub fn start() {
                 }
 
                 println!();
+
+                let code = input.trim_end_matches('\n');
+                if let Err(errors) = Parser::parse(code) {
+                    print_errors(&errors, code);
+                }
             }
             Err(error) => println!("Error: {error}"),
         }
     }
 }
+
+/// Returns the code after the REPL command `name` at the start of
+/// `input`, if it's followed by a space or the end of the line.
+fn command<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    input
+        .strip_prefix(name)
+        .filter(|code| code.is_empty() || code.starts_with(char::is_whitespace))
+}
+
+/// Prints each error with the line of `code` it's on.
+fn print_errors(errors: &[ParserError], code: &str) {
+    for error in errors.iter() {
+        print!("{}", Diagnostic::from(error).render_snippet(code));
+    }
+}
+
+fn print_ast(code: &str) {
+    // Spans are relative to the code, so it's echoed without the command
+    let code = code.trim_end_matches('\n');
+    match Parser::parse(code) {
+        Ok(program) => print!("{}", program.to_tree_string()),
+        Err(errors) => print_errors(&errors, code),
+    }
+}
+
+fn inspect(code: &str) {
+    let code = code.trim_end_matches('\n');
+    let program = match Parser::parse(code) {
+        Ok(program) => program,
+        Err(errors) => {
+            print_errors(&errors, code);
+            return;
+        }
+    };
+
+    // A single expression is inspected without its statement
+    let root = match program.statements.as_slice() {
+        [Statement::Expression(stmt)] => Node::Expression(&stmt.expression),
+        _ => Node::Program(&program),
+    };
+    // The nodes from the root to the current one
+    let mut path = vec![root];
+    let mut input = String::new();
+    loop {
+        let node = path[path.len() - 1];
+        let children = node.children();
+        print_node(&node, code);
+        for (i, (field, child)) in children.iter().enumerate() {
+            let token = child.token().map(|t| format!(" `{}`", t.literal));
+            println!(
+                "  {}. {field}: {}{}",
+                i + 1,
+                child.kind(),
+                token.unwrap_or_default()
+            );
+        }
+
+        loop {
+            print!("inspect> ");
+            io::stdout().flush().unwrap();
+            input.clear();
+            if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+                return;
+            }
+            match input.trim() {
+                "quit" | "q" => return,
+                "up" | "u" if path.len() > 1 => {
+                    path.pop();
+                    break;
+                }
+                "up" | "u" => println!("Already at the root"),
+                command => match command.parse::<usize>() {
+                    Ok(n) if (1..=children.len()).contains(&n) => {
+                        path.push(children[n - 1].1);
+                        break;
+                    }
+                    _ => println!(
+                        "Enter the number of a child (1 to {}), up or quit",
+                        children.len()
+                    ),
+                },
+            }
+        }
+    }
+}
+
+/// Prints the type, token, span and pretty form of `node`, whose spans are
+/// relative to `code`.
+fn print_node(node: &Node, code: &str) {
+    let span = node.span();
+    let (line, column) = span.location(code);
+    let token = node.token().map(|t| format!(" `{}`", t.literal));
+    println!(
+        "\n{}{} at {line}:{column} ({}..{})",
+        node.kind(),
+        token.unwrap_or_default(),
+        span.start,
+        span.end
+    );
+    for line in node.to_pretty_string().lines() {
+        println!("  | {line}");
+    }
+}
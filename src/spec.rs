@@ -0,0 +1,193 @@
+//! Executable language specification.
+//!
+//! A spec file is Monkey source split into cases by `// case: NAME`
+//! comments. Each case lists what it expects in comments:
+//!
+//! ```text
+//! // case: let needs a name
+//! let = 5;
+//! // expect-error: expected next token to be
+//!
+//! // case: arithmetic
+//! 1 + 2 * 3;
+//! // expect: 7
+//! ```
+//!
+//! `expect-error` lines are matched, in order, against the errors the
+//! case produces; each expected text must be contained in the actual
+//! message. `expect` lines describe the printed output of the case.
+//! Source before the first `case:` header, if any, forms a case named
+//! after the file.
+
+use crate::parser::Parser;
+
+/// A single case of a spec file.
+#[derive(Debug, PartialEq)]
+pub struct SpecCase {
+    pub name: String,
+    /// Line of the file (1-based) where the case starts
+    pub line: usize,
+    pub source: String,
+    pub expected_output: Vec<String>,
+    pub expected_errors: Vec<String>,
+}
+
+/// The result of running a [`SpecCase`].
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    /// The case needs a stage the interpreter doesn't have yet
+    Skipped(String),
+}
+
+/// Splits the contents of a spec file into its cases.
+pub fn parse_spec(file_name: &str, contents: &str) -> Vec<SpecCase> {
+    let mut cases: Vec<SpecCase> = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let comment = line.trim().strip_prefix("//").map(str::trim);
+
+        if let Some(name) = comment.and_then(|c| c.strip_prefix("case:")) {
+            cases.push(SpecCase::new(name.trim(), i + 1));
+            continue;
+        }
+
+        if cases.is_empty() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            cases.push(SpecCase::new(file_name, i + 1));
+        }
+
+        let case = cases.last_mut().unwrap();
+        if let Some(expected) = comment.and_then(|c| c.strip_prefix("expect:")) {
+            case.expected_output.push(expected.trim().to_string());
+        } else if let Some(expected) = comment.and_then(|c| c.strip_prefix("expect-error:")) {
+            case.expected_errors.push(expected.trim().to_string());
+        }
+
+        case.source.push_str(line);
+        case.source.push('\n');
+    }
+
+    cases
+}
+
+impl SpecCase {
+    fn new(name: &str, line: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            line,
+            source: String::new(),
+            expected_output: Vec::new(),
+            expected_errors: Vec::new(),
+        }
+    }
+
+    /// Runs the case through the interpreter.
+    ///
+    /// Only parsing is available for now, so cases expecting output are
+    /// skipped once their source is known to parse.
+    pub fn run(&self) -> Outcome {
+        let errors: Vec<String> = match Parser::parse(&self.source) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.iter().map(ToString::to_string).collect(),
+        };
+
+        if let Some(failure) = compare_errors(&self.expected_errors, &errors) {
+            return Outcome::Failed(failure);
+        }
+
+        if !self.expected_errors.is_empty() || self.expected_output.is_empty() {
+            Outcome::Passed
+        } else {
+            Outcome::Skipped("no evaluation backend available".to_string())
+        }
+    }
+}
+
+fn compare_errors(expected: &[String], actual: &[String]) -> Option<String> {
+    for (i, expected) in expected.iter().enumerate() {
+        match actual.get(i) {
+            Some(actual) if actual.contains(expected.as_str()) => {}
+            Some(actual) => {
+                return Some(format!("expected error \"{expected}\", got \"{actual}\""));
+            }
+            None => return Some(format!("expected error \"{expected}\", got none")),
+        }
+    }
+
+    actual
+        .get(expected.len())
+        .map(|unexpected| format!("unexpected error \"{unexpected}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec() {
+        let contents = r#"
+// case: first
+let x = 5;
+// expect: 5
+
+// case: second
+let = 5;
+// expect-error: expected next token
+"#;
+
+        let cases = parse_spec("file.monkey", contents);
+        assert_eq!(cases.len(), 2);
+
+        assert_eq!(cases[0].name, "first");
+        assert_eq!(cases[0].line, 2);
+        assert_eq!(cases[0].source, "let x = 5;\n// expect: 5\n\n");
+        assert_eq!(cases[0].expected_output, vec!["5"]);
+        assert!(cases[0].expected_errors.is_empty());
+
+        assert_eq!(cases[1].name, "second");
+        assert_eq!(cases[1].expected_errors, vec!["expected next token"]);
+    }
+
+    #[test]
+    fn test_parse_spec_without_headers() {
+        let cases = parse_spec("file.monkey", "\n5;\n// expect: 5\n");
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].name, "file.monkey");
+        assert_eq!(cases[0].line, 2);
+    }
+
+    #[test]
+    fn test_run() {
+        let tests = vec![
+            ("a + b;", Outcome::Passed),
+            (
+                "let = 5;\n// expect-error: expected next token",
+                Outcome::Passed,
+            ),
+            (
+                "let x = 5;\n// expect-error: expected next token",
+                Outcome::Failed("expected error \"expected next token\", got none".to_string()),
+            ),
+            (
+                "let 5;",
+                Outcome::Failed(
+                    "unexpected error \"expected next token to be \"identifier\", got \"int\" instead\""
+                        .to_string(),
+                ),
+            ),
+            (
+                "1 + 2;\n// expect: 3",
+                Outcome::Skipped("no evaluation backend available".to_string()),
+            ),
+        ];
+
+        for (source, expected) in tests {
+            let case = &parse_spec("test", source)[0];
+            assert_eq!(case.run(), expected, "source: {source}");
+        }
+    }
+}
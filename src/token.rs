@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 use crate::parser::Precedence;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -6,6 +8,7 @@ pub enum TokenType {
     Eof,
     Ident,
     Int,
+    Float,
     Assign,
     Plus,
     Minus,
@@ -16,25 +19,59 @@ pub enum TokenType {
     LessThan,
     GreaterThan,
     Semicolon,
+    Colon,
+    Ellipsis,
+    Dot,
+    /// The `?.` of an optional member access
+    QuestionDot,
     LeftParen,
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
+    /// The `#{` opening a set literal
+    SetBrace,
     Function,
     Let,
+    Const,
     True,
     False,
     If,
     Else,
     Return,
+    Try,
+    Catch,
+    Throw,
+    Defer,
+    Switch,
+    Case,
+    Default,
+    Do,
+    While,
+    For,
+    In,
+    Enum,
+    /// The `_` argument of a partial application. The lexer reads `_` as an
+    /// identifier, and the parser gives placeholder arguments this type
+    Placeholder,
     Equal,
     NotEqual,
+    Pipe,
+    /// The `??` null-coalescing operator
+    Coalesce,
+    /// A custom infix operator, see `Parser::register_infix_operator`
+    Operator,
 }
 
 impl TokenType {
     pub fn get_literal(&self) -> &str {
         match self {
+            TokenType::Illegal => "illegal",
+            TokenType::Eof => "end of input",
+            TokenType::Ident => "identifier",
             TokenType::Int => "int",
+            TokenType::Float => "float",
             TokenType::Assign => "=",
             TokenType::Plus => "+",
             TokenType::Minus => "-",
@@ -45,20 +82,43 @@ impl TokenType {
             TokenType::LessThan => "<",
             TokenType::GreaterThan => ">",
             TokenType::Semicolon => ";",
+            TokenType::Colon => ":",
+            TokenType::Ellipsis => "...",
+            TokenType::Dot => ".",
+            TokenType::QuestionDot => "?.",
             TokenType::LeftParen => "(",
             TokenType::RightParen => ")",
             TokenType::LeftBrace => "{",
             TokenType::RightBrace => "}",
+            TokenType::LeftBracket => "[",
+            TokenType::RightBracket => "]",
+            TokenType::SetBrace => "#{",
             TokenType::Function => "function",
             TokenType::Let => "let",
+            TokenType::Const => "const",
             TokenType::True => "true",
             TokenType::False => "false",
             TokenType::If => "if",
             TokenType::Else => "else",
             TokenType::Return => "return",
+            TokenType::Try => "try",
+            TokenType::Catch => "catch",
+            TokenType::Throw => "throw",
+            TokenType::Defer => "defer",
+            TokenType::Switch => "switch",
+            TokenType::Case => "case",
+            TokenType::Default => "default",
+            TokenType::Do => "do",
+            TokenType::While => "while",
+            TokenType::For => "for",
+            TokenType::In => "in",
+            TokenType::Enum => "enum",
+            TokenType::Placeholder => "_",
             TokenType::Equal => "==",
             TokenType::NotEqual => "!=",
-            _ => "",
+            TokenType::Pipe => "|>",
+            TokenType::Coalesce => "??",
+            TokenType::Operator => "operator",
         }
     }
 
@@ -69,6 +129,9 @@ impl TokenType {
             Asterisk | Slash => Precedence::Product,
             LessThan | GreaterThan => Precedence::LessGreater,
             Equal | NotEqual => Precedence::Equals,
+            Pipe => Precedence::Pipe,
+            Coalesce => Precedence::Coalesce,
+            LeftParen | Dot | QuestionDot => Precedence::Call,
             _ => Precedence::Lowest,
         }
     }
@@ -77,15 +140,58 @@ impl TokenType {
         use TokenType::*;
         matches!(
             self,
-            Plus | Minus | Asterisk | Slash | LessThan | GreaterThan | Equal | NotEqual
+            Plus | Minus
+                | Asterisk
+                | Slash
+                | LessThan
+                | GreaterThan
+                | Equal
+                | NotEqual
+                | Operator
+                | LeftParen
+                | Dot
+                | QuestionDot
+                | Pipe
+                | Coalesce
         )
     }
 }
 
+/// A range of the source code, as byte offsets.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// Returns the span covering both `self` and `other`
+    pub fn to(&self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    /// Returns the line and column, both starting at 1, where the span
+    /// starts in `source`. Columns are counted in characters.
+    pub fn location(&self, source: &str) -> (usize, usize) {
+        let before = source.get(..self.start).unwrap_or(source);
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        (line, column)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal: String,
+    /// Where the token is in the source. Tokens that weren't read from
+    /// source code have an empty span at the start.
+    pub span: Span,
 }
 
 impl Token {
@@ -93,6 +199,37 @@ impl Token {
         Token {
             token_type,
             literal,
+            span: Span::default(),
+        }
+    }
+}
+
+/// A `//` comment, which the lexer skips unless asked to keep it, see
+/// [`Lexer::keep_comments`](crate::lexer::Lexer::keep_comments).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// The text of the comment, including the `//`
+    pub text: String,
+    pub span: Span,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_location() {
+        let source = "let x = 5;\nlet é = 10;\n";
+        let tests = vec![
+            (0, (1, 1)),
+            (4, (1, 5)),
+            (11, (2, 1)),
+            (15, (2, 5)),
+            (18, (2, 7)),
+        ];
+
+        for (offset, expected) in tests {
+            assert_eq!(Span::new(offset, offset).location(source), expected);
         }
     }
 }
@@ -0,0 +1,98 @@
+//! The round-trip properties of `tests/round_trip.rs`, checked on the
+//! hand-written programs of `tests/fixtures` and `tests/spec`.
+//!
+//! Reading the spec files needs the standard library.
+
+#![cfg(feature = "std")]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use writing_an_interpreter_book::{ast::Program, lexer::Lexer, parser::Parser, spec::parse_spec};
+
+fn parse(source: &str) -> Program {
+    match Parser::parse(source) {
+        Ok(program) => program,
+        Err(errors) => panic!("parser errors for {source:?}: {errors:?}"),
+    }
+}
+
+/// Returns every program of the fixture and spec corpus that parses,
+/// named after the file or spec case it comes from. Cases expecting
+/// errors are left out.
+fn corpus() -> Vec<(String, String)> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut files: Vec<PathBuf> = ["tests/fixtures", "tests/spec"]
+        .iter()
+        .flat_map(|dir| fs::read_dir(root.join(dir)).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "monkey"))
+        .collect();
+    files.sort();
+
+    let mut programs = Vec::new();
+    for file in files.iter() {
+        let name = file.strip_prefix(root).unwrap().display().to_string();
+        let contents = fs::read_to_string(file).unwrap();
+        for case in parse_spec(&name, &contents) {
+            if Parser::parse(&case.source).is_ok() {
+                programs.push((format!("{name}:{}", case.line), case.source));
+            }
+        }
+    }
+
+    assert!(!programs.is_empty(), "no programs in the corpus");
+    programs
+}
+
+#[test]
+fn test_corpus_print_then_parse_yields_equal_tree() {
+    for (name, source) in corpus() {
+        let program = parse(&source);
+
+        for printed in [program.to_source(), program.to_pretty_string()] {
+            assert_eq!(parse(&printed), program, "{name}, printed:\n{printed}");
+        }
+    }
+}
+
+#[test]
+fn test_corpus_formatting_is_idempotent() {
+    for (name, source) in corpus() {
+        let formatted = parse(&source).to_pretty_string();
+        let reformatted = parse(&formatted).to_pretty_string();
+
+        assert_eq!(reformatted, formatted, "{name}");
+
+        let printed = parse(&source).to_source();
+        assert_eq!(parse(&printed).to_source(), printed, "{name}");
+    }
+}
+
+/// Parses `source`, keeping its comments.
+fn parse_with_comments(source: &str) -> Program {
+    let mut parser = Parser::new(Lexer::new(source));
+    parser.keep_comments();
+    let program = parser.parse_program();
+    assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+    program
+}
+
+#[test]
+fn test_corpus_formatting_keeps_comments() {
+    for (name, source) in corpus() {
+        let program = parse_with_comments(&source);
+        let formatted = program.to_pretty_string();
+        let reformatted = parse_with_comments(&formatted);
+
+        assert_eq!(reformatted, program, "{name}, formatted:\n{formatted}");
+        assert_eq!(reformatted.to_pretty_string(), formatted, "{name}");
+
+        let texts = |program: &Program| -> Vec<String> {
+            program.comments.iter().map(|c| c.text.clone()).collect()
+        };
+        assert_eq!(texts(&reformatted), texts(&program), "{name}");
+    }
+}
@@ -0,0 +1,32 @@
+//! Checks the parser events written by `monkey ast --events jsonl`.
+
+use std::{path::Path, process::Command};
+
+#[test]
+fn test_events_jsonl() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/errors.monkey");
+    let output = Command::new(env!("CARGO_BIN_EXE_monkey"))
+        .arg("ast")
+        .arg(fixture)
+        .arg("--events=jsonl")
+        .output()
+        .expect("failed to run monkey");
+    assert_eq!(output.status.code(), Some(1));
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let events: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        // The times vary between runs
+        .map(|line| line.split_once(", ").unwrap().1)
+        .collect();
+    assert_eq!(
+        events,
+        [
+            r#""event": "parse_started"}"#,
+            r#""event": "error_raised", "code": "P0001", "message": "expected next token to be \"identifier\", got \"=\" instead", "span": {"start": 4, "end": 5}}"#,
+            r#""event": "error_raised", "code": "P0001", "message": "expected next token to be \"=\", got \"int\" instead", "span": {"start": 15, "end": 17}}"#,
+            r#""event": "parse_finished", "statements": 0, "errors": 2}"#,
+        ]
+    );
+}
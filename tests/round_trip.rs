@@ -0,0 +1,459 @@
+//! Property-based tests checking that the printers and the parser agree.
+//!
+//! Random programs are generated from a seeded xorshift generator, so any
+//! failure names the seed that reproduces it. The same properties are
+//! checked on the hand-written programs of `tests/fixtures` and
+//! `tests/spec` in `tests/corpus.rs`.
+
+use writing_an_interpreter_book::{
+    ast::{
+        expressions::{
+            ArrayLiteral, BooleanLiteral, CallExpression, FloatLiteral, FunctionLiteral,
+            IdentExpression, IfExpression, InfixExpression, IntegerLiteral, MemberExpression,
+            NamedArgument, PlaceholderExpression, PrefixExpression, SetLiteral, SpreadExpression,
+            TryExpression,
+        },
+        patterns::{ArrayPattern, HashPattern, Pattern},
+        statements::{
+            BlockStatement, DeferStatement, DoWhileStatement, EnumStatement, EnumVariant,
+            ExpressionStatement, ForInStatement, LetStatement, ReturnStatement, SwitchCase,
+            SwitchStatement, ThrowStatement,
+        },
+        BraceStyle, Expression, FormatConfig, Program, Statement,
+    },
+    parser::Parser,
+    token::{Span, Token, TokenType},
+};
+
+/// Number of random programs checked by each property.
+const CASES: u64 = 1000;
+
+/// Maximum nesting depth of generated expressions.
+const MAX_DEPTH: u32 = 6;
+
+const IDENTS: [&str; 5] = ["a", "b", "foo", "bar", "snake_case"];
+
+const PREFIX_OPERATORS: [(TokenType, &str); 3] = [
+    (TokenType::Plus, "+"),
+    (TokenType::Minus, "-"),
+    (TokenType::Bang, "!"),
+];
+
+const INFIX_OPERATORS: [(TokenType, &str); 9] = [
+    (TokenType::Plus, "+"),
+    (TokenType::Minus, "-"),
+    (TokenType::Asterisk, "*"),
+    (TokenType::Slash, "/"),
+    (TokenType::LessThan, "<"),
+    (TokenType::GreaterThan, ">"),
+    (TokenType::Equal, "=="),
+    (TokenType::NotEqual, "!="),
+    (TokenType::Coalesce, "??"),
+];
+
+/// xorshift64* pseudo-random number generator.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // The state must never be zero
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+fn gen_ident(rng: &mut Rng) -> IdentExpression {
+    let name = rng.pick(&IDENTS).to_string();
+    IdentExpression {
+        token: Token::new(TokenType::Ident, name.clone()),
+        value: name,
+    }
+}
+
+fn gen_expression(rng: &mut Rng, depth: u32) -> Expression {
+    let leaf = depth == 0 || rng.below(3) == 0;
+
+    if leaf && rng.below(5) == 0 {
+        let value = rng.below(2) == 0;
+        let token_type = if value {
+            TokenType::True
+        } else {
+            TokenType::False
+        };
+        return Expression::Boolean(BooleanLiteral {
+            token: Token::new(token_type, value.to_string()),
+            value,
+        });
+    }
+
+    if leaf && rng.below(2) == 0 {
+        return Expression::Ident(gen_ident(rng));
+    }
+
+    if leaf && rng.below(4) == 0 {
+        let value = (rng.next() >> rng.below(64)) as f64 / (1u64 << rng.below(40)) as f64;
+        // Written the way the literal reads back, e.g. `2.0` rather than `2`
+        let mut float = FloatLiteral {
+            token: Token::new(TokenType::Float, String::new()),
+            value,
+        };
+        float.token.literal = float.to_string();
+        return Expression::Float(float);
+    }
+
+    if leaf {
+        let value = (rng.next() >> rng.below(64)) as i64 & i64::MAX;
+        return Expression::Integer(IntegerLiteral {
+            token: Token::new(TokenType::Int, value.to_string()),
+            value,
+        });
+    }
+
+    if rng.below(6) == 0 {
+        Expression::If(IfExpression {
+            token: Token::new(TokenType::If, "if".to_string()),
+            condition: Box::new(gen_expression(rng, depth - 1)),
+            consequence: gen_block(rng, depth - 1),
+            alternative: (rng.below(2) == 0).then(|| gen_block(rng, depth - 1)),
+        })
+    } else if rng.below(10) == 0 {
+        let token = if rng.below(3) == 0 {
+            Token::new(TokenType::QuestionDot, "?.".to_string())
+        } else {
+            Token::new(TokenType::Dot, ".".to_string())
+        };
+        Expression::Member(MemberExpression {
+            token,
+            object: Box::new(gen_expression(rng, depth - 1)),
+            property: gen_ident(rng),
+        })
+    } else if rng.below(10) == 0 {
+        let elements = (0..rng.below(4))
+            .map(|_| gen_expression(rng, depth - 1))
+            .collect();
+        Expression::Set(SetLiteral {
+            token: Token::new(TokenType::SetBrace, "#{".to_string()),
+            elements,
+            span: Span::default(),
+        })
+    } else if rng.below(10) == 0 {
+        let elements = (0..rng.below(4))
+            .map(|_| {
+                if rng.below(4) == 0 {
+                    gen_spread(rng, depth - 1)
+                } else {
+                    gen_expression(rng, depth - 1)
+                }
+            })
+            .collect();
+        Expression::Array(ArrayLiteral {
+            token: Token::new(TokenType::LeftBracket, "[".to_string()),
+            elements,
+            span: Span::default(),
+        })
+    } else if rng.below(6) == 0 {
+        let arguments = (0..rng.below(3))
+            .map(|_| {
+                if rng.below(5) == 0 {
+                    gen_spread(rng, depth - 1)
+                } else {
+                    gen_argument(rng, depth - 1)
+                }
+            })
+            .collect();
+        let mut named_arguments: Vec<NamedArgument> = Vec::new();
+        for _ in 0..rng.below(3) {
+            let name = gen_ident(rng);
+            let value = gen_argument(rng, depth - 1);
+            if !named_arguments.iter().any(|arg| arg.name == name) {
+                named_arguments.push(NamedArgument { name, value });
+            }
+        }
+        Expression::Call(CallExpression {
+            token: Token::new(TokenType::LeftParen, "(".to_string()),
+            function: Box::new(gen_expression(rng, depth - 1)),
+            arguments,
+            named_arguments,
+            span: Span::default(),
+        })
+    } else if rng.below(10) == 0 {
+        let parameters = (0..rng.below(3)).map(|_| gen_pattern(rng, 2)).collect();
+        let rest = (rng.below(3) == 0).then(|| gen_ident(rng));
+        Expression::Function(FunctionLiteral {
+            token: Token::new(TokenType::Function, "fn".to_string()),
+            parameters,
+            rest,
+            body: gen_block(rng, depth - 1),
+        })
+    } else if rng.below(10) == 0 {
+        Expression::Try(TryExpression {
+            token: Token::new(TokenType::Try, "try".to_string()),
+            body: gen_block(rng, depth - 1),
+            param: gen_ident(rng),
+            handler: gen_block(rng, depth - 1),
+        })
+    } else if rng.below(3) == 0 {
+        let (token_type, operator) = rng.pick(&PREFIX_OPERATORS).clone();
+        Expression::Prefix(PrefixExpression {
+            token: Token::new(token_type, operator.to_string()),
+            operator: operator.to_string(),
+            right: Box::new(gen_expression(rng, depth - 1)),
+        })
+    } else {
+        let (token_type, operator) = rng.pick(&INFIX_OPERATORS).clone();
+        Expression::Infix(InfixExpression {
+            token: Token::new(token_type, operator.to_string()),
+            left: Box::new(gen_expression(rng, depth - 1)),
+            operator: operator.to_string(),
+            right: Box::new(gen_expression(rng, depth - 1)),
+        })
+    }
+}
+
+fn gen_pattern(rng: &mut Rng, depth: u32) -> Pattern {
+    if depth == 0 || rng.below(3) != 0 {
+        return Pattern::Ident(gen_ident(rng));
+    }
+
+    if rng.below(2) == 0 {
+        Pattern::Array(ArrayPattern {
+            token: Token::new(TokenType::LeftBracket, "[".to_string()),
+            elements: (0..rng.below(3))
+                .map(|_| gen_pattern(rng, depth - 1))
+                .collect(),
+            span: Span::default(),
+        })
+    } else {
+        Pattern::Hash(HashPattern {
+            token: Token::new(TokenType::LeftBrace, "{".to_string()),
+            keys: (0..rng.below(3)).map(|_| gen_ident(rng)).collect(),
+            span: Span::default(),
+        })
+    }
+}
+
+/// Generates a spread, only valid as a positional argument or an array
+/// element.
+fn gen_spread(rng: &mut Rng, depth: u32) -> Expression {
+    Expression::Spread(SpreadExpression {
+        token: Token::new(TokenType::Ellipsis, "...".to_string()),
+        value: Box::new(gen_expression(rng, depth)),
+    })
+}
+
+/// Generates the argument of a call, which may be a placeholder.
+fn gen_argument(rng: &mut Rng, depth: u32) -> Expression {
+    if rng.below(5) == 0 {
+        Expression::Placeholder(PlaceholderExpression {
+            token: Token::new(TokenType::Placeholder, "_".to_string()),
+        })
+    } else {
+        gen_expression(rng, depth)
+    }
+}
+
+fn gen_block(rng: &mut Rng, depth: u32) -> BlockStatement {
+    let count = rng.below(3);
+    let statements = (0..count).map(|_| gen_statement(rng, depth)).collect();
+
+    BlockStatement {
+        token: Token::new(TokenType::LeftBrace, "{".to_string()),
+        statements,
+        span: Span::default(),
+    }
+}
+
+fn gen_switch(rng: &mut Rng, depth: u32) -> Statement {
+    let cases = (0..rng.below(3))
+        .map(|_| SwitchCase {
+            token: Token::new(TokenType::Case, "case".to_string()),
+            values: (0..1 + rng.below(2))
+                .map(|_| gen_expression(rng, depth - 1))
+                .collect(),
+            body: gen_block(rng, depth - 1),
+        })
+        .collect();
+
+    Statement::Switch(SwitchStatement {
+        token: Token::new(TokenType::Switch, "switch".to_string()),
+        subject: gen_expression(rng, depth - 1),
+        cases,
+        default: (rng.below(2) == 0).then(|| gen_block(rng, depth - 1)),
+        span: Span::default(),
+    })
+}
+
+fn gen_enum(rng: &mut Rng) -> Statement {
+    let mut variants: Vec<EnumVariant> = Vec::new();
+    for _ in 0..rng.below(4) {
+        let name = gen_ident(rng);
+        let fields = (0..rng.below(3)).map(|_| gen_ident(rng)).collect();
+        if !variants.iter().any(|v| v.name == name) {
+            variants.push(EnumVariant { name, fields });
+        }
+    }
+
+    Statement::Enum(EnumStatement {
+        token: Token::new(TokenType::Enum, "enum".to_string()),
+        name: gen_ident(rng),
+        variants,
+        span: Span::default(),
+    })
+}
+
+fn gen_statement(rng: &mut Rng, depth: u32) -> Statement {
+    if rng.below(20) == 0 {
+        return gen_enum(rng);
+    }
+
+    if depth > 0 && rng.below(10) == 0 {
+        return gen_switch(rng, depth);
+    }
+
+    if depth > 0 && rng.below(10) == 0 {
+        let bindings = (0..1 + rng.below(2)).map(|_| gen_ident(rng)).collect();
+
+        return Statement::ForIn(ForInStatement {
+            token: Token::new(TokenType::For, "for".to_string()),
+            bindings,
+            iterable: gen_expression(rng, depth - 1),
+            body: gen_block(rng, depth - 1),
+        });
+    }
+
+    if depth > 0 && rng.below(10) == 0 {
+        return Statement::DoWhile(DoWhileStatement {
+            token: Token::new(TokenType::Do, "do".to_string()),
+            body: gen_block(rng, depth - 1),
+            condition: gen_expression(rng, depth - 1),
+            span: Span::default(),
+        });
+    }
+
+    let value = gen_expression(rng, depth);
+
+    match rng.below(5) {
+        0 => {
+            let token = if rng.below(2) == 0 {
+                Token::new(TokenType::Let, "let".to_string())
+            } else {
+                Token::new(TokenType::Const, "const".to_string())
+            };
+            Statement::Let(LetStatement {
+                token,
+                name: gen_ident(rng),
+                value,
+            })
+        }
+        1 => Statement::Return(ReturnStatement {
+            token: Token::new(TokenType::Return, "return".to_string()),
+            value,
+        }),
+        2 => Statement::Throw(ThrowStatement {
+            token: Token::new(TokenType::Throw, "throw".to_string()),
+            value,
+        }),
+        3 => Statement::Defer(DeferStatement {
+            token: Token::new(TokenType::Defer, "defer".to_string()),
+            value,
+        }),
+        _ => Statement::Expression(ExpressionStatement {
+            token: Token::new(TokenType::Ident, value.to_string()),
+            expression: value,
+        }),
+    }
+}
+
+fn gen_program(rng: &mut Rng) -> Program {
+    let count = 1 + rng.below(4);
+    let statements = (0..count).map(|_| gen_statement(rng, MAX_DEPTH)).collect();
+
+    Program {
+        statements,
+        ..Program::default()
+    }
+}
+
+fn parse(source: &str) -> Program {
+    match Parser::parse(source) {
+        Ok(program) => program,
+        Err(errors) => panic!("parser errors for {source:?}: {errors:?}"),
+    }
+}
+
+#[test]
+fn test_print_then_parse_yields_equal_tree() {
+    for seed in 0..CASES {
+        let program = gen_program(&mut Rng::new(seed));
+        let source = program.to_source();
+
+        assert_eq!(parse(&source), program, "seed {seed}, source:\n{source}");
+    }
+}
+
+#[test]
+fn test_precedence_printing_is_stable() {
+    for seed in 0..CASES {
+        let source = gen_program(&mut Rng::new(seed)).to_source();
+        let reprinted = parse(&source).to_source();
+
+        assert_eq!(reprinted, source, "seed {seed}");
+    }
+}
+
+#[test]
+fn test_pretty_print_then_parse_yields_equal_tree() {
+    for seed in 0..CASES {
+        let program = gen_program(&mut Rng::new(seed));
+        let source = program.to_pretty_string();
+
+        assert_eq!(parse(&source), program, "seed {seed}, source:\n{source}");
+    }
+}
+
+#[test]
+fn test_pretty_precedence_printing_is_stable() {
+    for seed in 0..CASES {
+        let source = gen_program(&mut Rng::new(seed)).to_pretty_string();
+        let reprinted = parse(&source).to_pretty_string();
+
+        assert_eq!(reprinted, source, "seed {seed}");
+    }
+}
+
+#[test]
+fn test_styled_print_then_parse_yields_equal_tree() {
+    let config = FormatConfig {
+        indent_width: 2,
+        use_tabs: true,
+        brace_style: BraceStyle::NextLine,
+        max_width: 30,
+    };
+
+    for seed in 0..CASES {
+        let program = gen_program(&mut Rng::new(seed));
+        let source = program.to_pretty_string_with(&config);
+        let reparsed = parse(&source);
+
+        assert_eq!(reparsed, program, "seed {seed}, source:\n{source}");
+        assert_eq!(
+            reparsed.to_pretty_string_with(&config),
+            source,
+            "seed {seed}"
+        );
+    }
+}
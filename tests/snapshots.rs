@@ -0,0 +1,85 @@
+//! Golden tests for parser output.
+//!
+//! Every `tests/fixtures/*.monkey` file is run through `monkey ast` in each
+//! of the formats listed in `FORMATS`, and the output is compared against
+//! `tests/snapshots/<fixture>.<format>`. Files that fail to parse are
+//! snapshotted with their error output instead.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1 cargo test --test snapshots` to (re)write
+//! the snapshot files after an intentional change, then review the diff.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const FORMATS: [&str; 2] = ["pretty", "json"];
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn snapshots_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+/// Runs `monkey ast FILE --format FORMAT` inside the fixtures directory, so
+/// paths in error messages don't depend on where the repo is checked out.
+fn render(fixture: &str, format: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_monkey"))
+        .args(["ast", fixture, "--format", format])
+        .current_dir(fixtures_dir())
+        .output()
+        .expect("failed to run monkey");
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).unwrap()
+    } else {
+        String::from_utf8(output.stderr).unwrap()
+    }
+}
+
+#[test]
+fn test_parser_snapshots() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    let mut fixtures: Vec<String> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.ends_with(".monkey"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no fixtures found");
+
+    let mut failures = Vec::new();
+
+    for fixture in fixtures.iter() {
+        let name = fixture.trim_end_matches(".monkey");
+
+        for format in FORMATS {
+            let actual = render(fixture, format);
+            let snapshot = snapshots_dir().join(format!("{name}.{format}"));
+
+            if update {
+                fs::write(&snapshot, &actual).unwrap();
+                continue;
+            }
+
+            match fs::read_to_string(&snapshot) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => failures.push(format!(
+                    "{} does not match:\n--- expected\n{expected}--- actual\n{actual}",
+                    snapshot.display()
+                )),
+                Err(_) => failures.push(format!("{} is missing", snapshot.display())),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRun with UPDATE_SNAPSHOTS=1 to accept the new output.",
+        failures.join("\n")
+    );
+}
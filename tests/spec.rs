@@ -0,0 +1,19 @@
+//! Runs the language spec in `tests/spec` through `monkey spec`.
+
+use std::{path::Path, process::Command};
+
+#[test]
+fn test_spec() {
+    let spec_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/spec");
+    let output = Command::new(env!("CARGO_BIN_EXE_monkey"))
+        .arg("spec")
+        .arg(spec_dir)
+        .output()
+        .expect("failed to run monkey");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}